@@ -1,7 +1,8 @@
 use crate::{
     sys,
     utils::{charbuf_to_str, write_charbuf},
-    ActivityKind, ClientID, UnixTimestamp,
+    validation_error::validate_str,
+    ActivityKind, ClientID, UnixTimestamp, ValidationError,
 };
 use std::convert::TryInto;
 
@@ -252,6 +253,162 @@ impl Activity {
         write_charbuf(&mut self.0.secrets.spectate, value);
         self
     }
+
+    /// [`with_state`](#method.with_state), checking that `value` fits
+    ///
+    /// ## Errors
+    ///
+    /// If `value` is longer than 127 bytes or contains a nul byte, the activity is left
+    /// untouched.
+    pub fn try_with_state(
+        &mut self,
+        value: &str,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        let value = validate_str("state", value, self.0.state.len() - 1)?;
+        Ok(self.with_state(value))
+    }
+
+    /// [`with_details`](#method.with_details), checking that `value` fits
+    ///
+    /// ## Errors
+    ///
+    /// If `value` is longer than 127 bytes or contains a nul byte, the activity is left
+    /// untouched.
+    pub fn try_with_details(
+        &mut self,
+        value: &str,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        let value = validate_str("details", value, self.0.details.len() - 1)?;
+        Ok(self.with_details(value))
+    }
+
+    /// [`with_large_image_key`](#method.with_large_image_key), checking that `value` fits
+    ///
+    /// ## Errors
+    ///
+    /// If `value` is longer than 127 bytes or contains a nul byte, the activity is left
+    /// untouched.
+    pub fn try_with_large_image_key(
+        &mut self,
+        value: &str,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        let value = validate_str(
+            "large_image_key",
+            value,
+            self.0.assets.large_image.len() - 1,
+        )?;
+        Ok(self.with_large_image_key(value))
+    }
+
+    /// [`with_large_image_tooltip`](#method.with_large_image_tooltip), checking that `value` fits
+    ///
+    /// ## Errors
+    ///
+    /// If `value` is longer than 127 bytes or contains a nul byte, the activity is left
+    /// untouched.
+    pub fn try_with_large_image_tooltip(
+        &mut self,
+        value: &str,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        let value = validate_str(
+            "large_image_tooltip",
+            value,
+            self.0.assets.large_text.len() - 1,
+        )?;
+        Ok(self.with_large_image_tooltip(value))
+    }
+
+    /// [`with_small_image_key`](#method.with_small_image_key), checking that `value` fits
+    ///
+    /// ## Errors
+    ///
+    /// If `value` is longer than 127 bytes or contains a nul byte, the activity is left
+    /// untouched.
+    pub fn try_with_small_image_key(
+        &mut self,
+        value: &str,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        let value = validate_str(
+            "small_image_key",
+            value,
+            self.0.assets.small_image.len() - 1,
+        )?;
+        Ok(self.with_small_image_key(value))
+    }
+
+    /// [`with_small_image_tooltip`](#method.with_small_image_tooltip), checking that `value` fits
+    ///
+    /// ## Errors
+    ///
+    /// If `value` is longer than 127 bytes or contains a nul byte, the activity is left
+    /// untouched.
+    pub fn try_with_small_image_tooltip(
+        &mut self,
+        value: &str,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        let value = validate_str(
+            "small_image_tooltip",
+            value,
+            self.0.assets.small_text.len() - 1,
+        )?;
+        Ok(self.with_small_image_tooltip(value))
+    }
+
+    /// [`with_party_id`](#method.with_party_id), checking that `value` fits
+    ///
+    /// ## Errors
+    ///
+    /// If `value` is longer than 127 bytes or contains a nul byte, the activity is left
+    /// untouched.
+    pub fn try_with_party_id(
+        &mut self,
+        value: &str,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        let value = validate_str("party_id", value, self.0.party.id.len() - 1)?;
+        Ok(self.with_party_id(value))
+    }
+
+    /// [`with_match_secret`](#method.with_match_secret), checking that `value` fits
+    ///
+    /// ## Errors
+    ///
+    /// If `value` is longer than 127 bytes or contains a nul byte, the activity is left
+    /// untouched.
+    pub fn try_with_match_secret(
+        &mut self,
+        value: &str,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        let value = validate_str("match_secret", value, self.0.secrets.match_.len() - 1)?;
+        Ok(self.with_match_secret(value))
+    }
+
+    /// [`with_join_secret`](#method.with_join_secret), checking that `value` fits
+    ///
+    /// ## Errors
+    ///
+    /// If `value` is longer than 127 bytes or contains a nul byte, the activity is left
+    /// untouched.
+    pub fn try_with_join_secret(
+        &mut self,
+        value: &str,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        let value = validate_str("join_secret", value, self.0.secrets.join.len() - 1)?;
+        Ok(self.with_join_secret(value))
+    }
+
+    /// [`with_spectate_secret`](#method.with_spectate_secret), checking that `value` fits
+    ///
+    /// ## Errors
+    ///
+    /// If `value` is longer than 127 bytes or contains a nul byte, the activity is left
+    /// untouched.
+    pub fn try_with_spectate_secret(
+        &mut self,
+        value: &str,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        let value = validate_str("spectate_secret", value, self.0.secrets.spectate.len() - 1)?;
+        Ok(self.with_spectate_secret(value))
+    }
 }
 
 impl std::fmt::Debug for Activity {
@@ -0,0 +1,321 @@
+use crate::{Activity, Discord, Error, Result};
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// Keeps the current user's activity in sync with the latest desired state, within Discord's
+/// rate limit
+///
+/// Desired activities can be given at any rate, only the latest one is sent once the rate limit
+/// allows it and states equal to the current one are not sent again. Requests that fail with
+/// transient errors (such as `RateLimited` or `ServiceUnavailable`) are retried with an
+/// exponential backoff, unless a newer state was given in the meantime.
+///
+/// [`update`](#method.update) must be called regularly, along with
+/// [`Discord::run_callbacks`](struct.Discord.html#method.run_callbacks).
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # fn example(mut discord: Discord<'_, ()>, mut level: u32) -> Result<()> {
+/// let mut presence = ActivityUpdater::new();
+///
+/// loop {
+///     level += 1;
+///
+///     // Calling this every frame is fine
+///     presence.set(Activity::empty().with_state(&format!("Level {}", level)));
+///     presence.update(&discord);
+///
+///     discord.run_callbacks()?;
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ActivityUpdater {
+    desired: Option<Option<Activity>>,
+    shared: Rc<RefCell<Shared>>,
+    sent_at: VecDeque<Instant>,
+    retry_at: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    // Only cleared by the callback, which is called with `TransactionAborted` at the latest when
+    // the instance is dropped
+    in_flight: Option<Option<Activity>>,
+    acknowledged: Option<Option<Activity>>,
+    failed: Option<(Option<Activity>, Error)>,
+    // Consecutive failures
+    retries: u32,
+    last_error: Option<Error>,
+}
+
+impl ActivityUpdater {
+    /// Number of updates allowed in [`RATE_LIMIT_WINDOW`](#associatedconstant.RATE_LIMIT_WINDOW)
+    pub const RATE_LIMIT: usize = 5;
+
+    /// Period over which [`RATE_LIMIT`](#associatedconstant.RATE_LIMIT) applies
+    pub const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(20);
+
+    /// Delay before the first retry, doubled on each consecutive failure
+    const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+    /// Creates an updater that has not sent anything yet
+    pub fn new() -> Self {
+        Self {
+            desired: None,
+            shared: Rc::new(RefCell::new(Shared::default())),
+            sent_at: VecDeque::with_capacity(Self::RATE_LIMIT),
+            retry_at: None,
+        }
+    }
+
+    /// Sets the activity that should be displayed, replacing any state that was not sent yet
+    pub fn set(&mut self, activity: &Activity) {
+        self.desired = Some(Some(activity.clone()));
+    }
+
+    /// Requests that no activity be displayed, replacing any state that was not sent yet
+    pub fn clear(&mut self) {
+        self.desired = Some(None);
+    }
+
+    /// The last activity Discord acknowledged, `None` if it was cleared or nothing was sent yet
+    pub fn acknowledged(&self) -> Option<Activity> {
+        self.shared.borrow().acknowledged.clone().flatten()
+    }
+
+    /// Whether a state is waiting to be sent or acknowledged
+    ///
+    /// A request stays in flight until Discord answers it, dropping the `Discord` instance it
+    /// was sent with aborts it.
+    pub fn is_pending(&self) -> bool {
+        self.desired.is_some() || self.shared.borrow().in_flight.is_some()
+    }
+
+    /// The error of the last request that failed, cleared when a request succeeds
+    pub fn last_error(&self) -> Option<Error> {
+        self.shared.borrow().last_error.clone()
+    }
+
+    /// Sends the desired state if needed and allowed by the rate limit
+    ///
+    /// Results of previous requests are received during `run_callbacks`.
+    pub fn update<E>(&mut self, discord: &Discord<'_, E>) {
+        self.update_at(discord, Instant::now())
+    }
+
+    pub(crate) fn update_at<E>(&mut self, discord: &Discord<'_, E>, now: Instant) {
+        let failed = self.shared.borrow_mut().failed.take();
+
+        if let Some((activity, error)) = failed {
+            let retries = self.shared.borrow().retries;
+
+            if is_transient(error) {
+                // A newer state supersedes the one that failed
+                if self.desired.is_none() {
+                    self.desired = Some(activity);
+                }
+
+                self.retry_at = Some(now + Self::retry_delay(retries));
+            } else {
+                self.shared.borrow_mut().retries = 0;
+                self.retry_at = None;
+            }
+        }
+
+        {
+            let shared = self.shared.borrow();
+
+            if shared.in_flight.is_some() {
+                return;
+            }
+
+            if self.desired.is_some() && self.desired == shared.acknowledged {
+                self.desired = None;
+            }
+        }
+
+        if self.desired.is_none() || matches!(self.retry_at, Some(at) if now < at) {
+            return;
+        }
+
+        while matches!(self.sent_at.front(), Some(&at) if now.duration_since(at) >= Self::RATE_LIMIT_WINDOW)
+        {
+            self.sent_at.pop_front();
+        }
+
+        if self.sent_at.len() >= Self::RATE_LIMIT {
+            return;
+        }
+
+        let activity = match self.desired.take() {
+            Some(activity) => activity,
+            None => return,
+        };
+
+        self.sent_at.push_back(now);
+        self.retry_at = None;
+        self.shared.borrow_mut().in_flight = Some(activity.clone());
+
+        let shared = self.shared.clone();
+        let callback = move |_: &Discord<'_, E>, res: Result<()>| {
+            let mut shared = shared.borrow_mut();
+            let activity = shared.in_flight.take().unwrap_or(None);
+
+            match res {
+                Ok(()) => {
+                    shared.acknowledged = Some(activity);
+                    shared.last_error = None;
+                    shared.retries = 0;
+                }
+                Err(error) => {
+                    log::warn!("failed to update activity: {}", error);
+                    shared.failed = Some((activity, error.clone()));
+                    shared.retries += 1;
+                    shared.last_error = Some(error);
+                }
+            }
+        };
+
+        match &activity {
+            Some(activity) => discord.update_activity(activity, callback),
+            None => discord.clear_activity(callback),
+        }
+    }
+
+    fn retry_delay(retries: u32) -> Duration {
+        let factor = 1_u32 << retries.saturating_sub(1).min(16);
+
+        (Self::RETRY_DELAY * factor).min(Self::RATE_LIMIT_WINDOW)
+    }
+}
+
+impl Default for ActivityUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_transient(error: Error) -> bool {
+    matches!(
+        error,
+        Error::ServiceUnavailable | Error::LockFailed | Error::Internal | Error::RateLimited
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{
+        fixtures::{faulty, run},
+        MockFaults,
+    };
+
+    #[test]
+    fn activity_updates_are_coalesced_and_rate_limited() {
+        let level = |level: u32| {
+            Activity::empty()
+                .with_state(&format!("Level {}", level))
+                .clone()
+        };
+        let acknowledged = |updater: &ActivityUpdater| {
+            updater
+                .acknowledged()
+                .map(|activity| activity.state().to_string())
+        };
+
+        let mut discord = Discord::<()>::mock();
+        let mut updater = ActivityUpdater::new();
+        let start = Instant::now();
+
+        for n in 0..10 {
+            updater.set(&level(n));
+        }
+        updater.update_at(&discord, start);
+        run(&mut discord);
+        assert_eq!(acknowledged(&updater), Some("Level 9".to_string()));
+
+        // Already displayed, nothing is sent
+        updater.set(&level(9));
+        updater.update_at(&discord, start);
+        assert!(!updater.is_pending());
+
+        for n in 10..14 {
+            updater.set(&level(n));
+            updater.update_at(&discord, start);
+            run(&mut discord);
+        }
+        assert_eq!(acknowledged(&updater), Some("Level 13".to_string()));
+
+        updater.set(&level(14));
+        updater.update_at(&discord, start);
+        run(&mut discord);
+        assert_eq!(acknowledged(&updater), Some("Level 13".to_string()));
+        assert!(updater.is_pending());
+
+        updater.update_at(&discord, start + ActivityUpdater::RATE_LIMIT_WINDOW);
+        run(&mut discord);
+        assert_eq!(acknowledged(&updater), Some("Level 14".to_string()));
+
+        updater.clear();
+        updater.update_at(&discord, start + Duration::from_secs(21));
+        run(&mut discord);
+        assert_eq!(acknowledged(&updater), None);
+        assert!(!updater.is_pending());
+    }
+
+    #[test]
+    fn transient_failures_are_retried_with_backoff() {
+        let mut faults = MockFaults::new();
+        faults.with_error_times("update_activity", Error::ServiceUnavailable, 2);
+
+        let mut discord = Discord::<()>::mock_with(&faulty(faults));
+        let mut updater = ActivityUpdater::new();
+        let start = Instant::now();
+        let at = |millis: u64| start + Duration::from_millis(millis);
+
+        updater.set(Activity::empty().with_state("Level 1"));
+        updater.update_at(&discord, at(0));
+        run(&mut discord);
+        assert_eq!(updater.last_error(), Some(Error::ServiceUnavailable));
+
+        // First retry 1 s after the failure
+        updater.update_at(&discord, at(0));
+        updater.update_at(&discord, at(999));
+        assert_eq!(updater.sent_at.len(), 1);
+        updater.update_at(&discord, at(1_000));
+        assert_eq!(updater.sent_at.len(), 2);
+        run(&mut discord);
+        assert_eq!(updater.last_error(), Some(Error::ServiceUnavailable));
+
+        // Second retry 2 s after the second failure
+        updater.update_at(&discord, at(1_000));
+        updater.update_at(&discord, at(2_999));
+        assert_eq!(updater.sent_at.len(), 2);
+        assert!(updater.is_pending());
+        updater.update_at(&discord, at(3_000));
+        assert_eq!(updater.sent_at.len(), 3);
+        run(&mut discord);
+
+        assert_eq!(
+            updater
+                .acknowledged()
+                .map(|activity| activity.state().to_string()),
+            Some("Level 1".to_string())
+        );
+        assert_eq!(updater.last_error(), None);
+        assert!(!updater.is_pending());
+
+        // Dropping the instance aborts the request in flight
+        updater.set(Activity::empty().with_state("Level 2"));
+        updater.update_at(&discord, at(3_000));
+        assert!(updater.is_pending());
+        drop(discord);
+        assert!(!updater.is_pending());
+        assert_eq!(updater.last_error(), Some(Error::TransactionAborted));
+    }
+}
@@ -0,0 +1,129 @@
+// What the backends that wrap the SDK or stand in for it have in common: the tables of function
+// pointers they hand out, and the `IDiscordCore` leading to their managers.
+
+use crate::sys;
+use std::{cell::Cell, ffi::c_void};
+
+pub(crate) type LogHook = unsafe extern "C" fn(*mut c_void, sys::EDiscordLogLevel, *const u8);
+
+// Every table of function pointers handed to the SDK user is wrapped with a pointer back to the
+// backend it belongs to, the SDK only ever gives us the pointer to the table itself
+#[repr(C)]
+pub(crate) struct Interface<T, B> {
+    vtable: T,
+    pub(crate) backend: *const B,
+    // The table of the SDK calls are forwarded to, for backends wrapping it
+    pub(crate) real: Cell<*mut T>,
+}
+
+impl<T, B> Interface<T, B> {
+    pub(crate) fn new(vtable: T) -> Self {
+        Self {
+            vtable,
+            backend: std::ptr::null(),
+            real: Cell::new(std::ptr::null_mut()),
+        }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut T {
+        &self.vtable as *const T as *mut T
+    }
+}
+
+// SAFETY: `interface` must point to the `vtable` of an `Interface<T, B>` whose backend is alive
+pub(crate) unsafe fn interface<'a, T, B>(interface: *mut T) -> &'a Interface<T, B> {
+    &*(interface as *mut Interface<T, B>)
+}
+
+// SAFETY: `interface` must point to the `vtable` of an `Interface<T, B>` owned by a live `B`
+pub(crate) unsafe fn backend<'a, T, B>(interface: *mut T) -> &'a B {
+    &*self::interface::<T, B>(interface).backend
+}
+
+/// What the `IDiscordCore` made by `core` does
+pub(crate) trait Backend: Sized {
+    fn managers(&self) -> &Managers<Self>;
+
+    /// Frees the backend, along with everything still pending
+    unsafe fn destroy(backend: *mut Self);
+
+    unsafe fn run_callbacks(&self) -> sys::EDiscordResult;
+
+    unsafe fn set_log_hook(
+        &self,
+        min_level: sys::EDiscordLogLevel,
+        hook: Option<(*mut c_void, LogHook)>,
+    );
+}
+
+macro_rules! backend_managers {
+    ($( $getter:ident $field:ident $iface:ident ),*) => {
+        pub(crate) struct Managers<B> {
+            $( pub(crate) $field: Interface<sys::$iface, B>, )*
+        }
+
+        impl<B> Managers<B> {
+            /// Points every table back to the backend, once it has its final address
+            pub(crate) fn attach(&mut self, backend: *const B) {
+                $( self.$field.backend = backend; )*
+            }
+
+            /// Makes every table forward to the managers of the SDK instance `real`
+            #[cfg(feature = "replay")]
+            pub(crate) unsafe fn wrap(&self, real: *mut sys::IDiscordCore) {
+                $( self.$field.real.set((*real).$getter.unwrap()(real)); )*
+            }
+        }
+
+        /// The `IDiscordCore` of backend `B`, pointed back to it once it has its final address
+        pub(crate) fn core<B: Backend>() -> Interface<sys::IDiscordCore, B> {
+            Interface::new(sys::IDiscordCore {
+                destroy: {
+                    unsafe extern "C" fn destroy<B: Backend>(core: *mut sys::IDiscordCore) {
+                        B::destroy(backend::<_, B>(core) as *const B as *mut B)
+                    }
+
+                    Some(destroy::<B>)
+                },
+
+                run_callbacks: {
+                    unsafe extern "C" fn run_callbacks<B: Backend>(
+                        core: *mut sys::IDiscordCore,
+                    ) -> sys::EDiscordResult {
+                        backend::<_, B>(core).run_callbacks()
+                    }
+
+                    Some(run_callbacks::<B>)
+                },
+
+                set_log_hook: {
+                    unsafe extern "C" fn set_log_hook<B: Backend>(
+                        core: *mut sys::IDiscordCore,
+                        min_level: sys::EDiscordLogLevel,
+                        hook_data: *mut c_void,
+                        hook: Option<LogHook>,
+                    ) {
+                        let hook = hook.map(|hook| (hook_data, hook));
+                        backend::<_, B>(core).set_log_hook(min_level, hook)
+                    }
+
+                    Some(set_log_hook::<B>)
+                },
+
+                $(
+                    $getter: {
+                        unsafe extern "C" fn $getter<B: Backend>(
+                            core: *mut sys::IDiscordCore,
+                        ) -> *mut sys::$iface {
+                            backend::<_, B>(core).managers().$field.as_ptr()
+                        }
+
+                        Some($getter::<B>)
+                    },
+                )*
+            })
+        }
+    };
+}
+
+sdk_managers!(backend_managers);
@@ -1,5 +1,12 @@
-use crate::{sys, ClientID};
-use std::{cell::UnsafeCell, marker::PhantomData, mem::ManuallyDrop};
+use crate::{sys, ClientID, LogLevel};
+use std::{
+    cell::{Cell, UnsafeCell},
+    marker::PhantomData,
+    mem::ManuallyDrop,
+};
+
+/// Receives the SDK log messages instead of `log`, see `Discord::set_log_sink`
+pub(crate) type LogSink<'d> = Box<dyn 'd + FnMut(LogLevel, &str)>;
 
 /// Main interface with SDK
 ///
@@ -112,6 +119,14 @@ pub(crate) struct DiscordInner<'d, E> {
     pub(crate) core: *mut sys::IDiscordCore,
     pub(crate) client_id: sys::DiscordClientId,
     pub(crate) event_handler: UnsafeCell<Option<E>>,
+    pub(crate) log_level: Cell<LogLevel>,
+    pub(crate) log_sink: UnsafeCell<Option<LogSink<'d>>>,
+    // Changed whenever the sink is set or cleared
+    pub(crate) log_sink_generation: Cell<u64>,
+
+    // Dropped after `core` is destroyed, see `Drop for Discord`
+    #[cfg(feature = "dynamic")]
+    pub(crate) library: Option<sys::DynamicLibrary>,
 
     pub(crate) achievement_events: sys::IDiscordAchievementEvents,
     pub(crate) activity_events: sys::IDiscordActivityEvents,
@@ -140,6 +155,7 @@ impl<E: std::fmt::Debug> std::fmt::Debug for DiscordInner<'_, E> {
             .field("ffi_ptr", &self.core)
             .field("client_id", &self.client_id)
             .field("event_handler", self.event_handler())
+            .field("log_level", &self.log_level.get())
             .finish()
     }
 }
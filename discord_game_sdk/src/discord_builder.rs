@@ -0,0 +1,233 @@
+use crate::{
+    discord::LogSink, Activity, ClientID, CreateFlags, Discord, EventHandler, LogLevel, Managers,
+    Result,
+};
+use std::fmt;
+
+/// Options applied while the SDK is set up, before the instance is handed out
+pub(crate) struct Setup<'d> {
+    pub(crate) managers: Managers,
+    pub(crate) log_level: LogLevel,
+    pub(crate) log_sink: Option<LogSink<'d>>,
+}
+
+impl Default for Setup<'_> {
+    fn default() -> Self {
+        Self {
+            managers: Managers::all(),
+            log_level: LogLevel::Debug,
+            log_sink: None,
+        }
+    }
+}
+
+/// Creates a [`Discord`](struct.Discord.html) instance with everything set up at once
+///
+/// ```rust,no_run
+/// # use discord_game_sdk::*;
+/// # const DISCORD_CLIENT_ID: ClientID = 0;
+/// # #[derive(Default)] struct MyEventHandler;
+/// # impl EventHandler for MyEventHandler {}
+/// # fn example() -> Result<()> {
+/// let mut discord = DiscordBuilder::new(DISCORD_CLIENT_ID)
+///     .with_create_flags(CreateFlags::NoRequireDiscord)
+///     .with_log_level(LogLevel::Warn)
+///     .with_managers(Managers::ACTIVITIES | Managers::USERS)
+///     .with_event_handler(MyEventHandler::default())
+///     .with_activity(Activity::empty().with_state("In the menus"))
+///     .with_launch_command("my-game --discord")
+///     .build()?;
+///
+/// loop {
+///     discord.run_callbacks()?;
+/// }
+/// # }
+/// ```
+pub struct DiscordBuilder<'d, E> {
+    client_id: ClientID,
+    flags: CreateFlags,
+    log_level: LogLevel,
+    log_sink: Option<LogSink<'d>>,
+    managers: Managers,
+    event_handler: Option<E>,
+    activity: Option<Activity>,
+    launch_command: Option<String>,
+}
+
+impl<'d, E> DiscordBuilder<'d, E> {
+    /// Creates a builder for the application `client_id`, with the defaults of
+    /// [`Discord::new`](struct.Discord.html#method.new)
+    pub fn new(client_id: ClientID) -> Self {
+        let setup = Setup::default();
+
+        Self {
+            client_id,
+            flags: CreateFlags::Default,
+            log_level: setup.log_level,
+            log_sink: setup.log_sink,
+            managers: setup.managers,
+            event_handler: None,
+            activity: None,
+            launch_command: None,
+        }
+    }
+
+    /// Whether Discord must be running, `CreateFlags::Default` by default
+    pub fn with_create_flags(&mut self, flags: CreateFlags) -> &mut Self {
+        self.flags = flags;
+        self
+    }
+
+    /// The minimum level of the SDK log messages to forward, see
+    /// [`Discord::set_log_level`](struct.Discord.html#method.set_log_level)
+    pub fn with_log_level(&mut self, level: LogLevel) -> &mut Self {
+        self.log_level = level;
+        self
+    }
+
+    /// Where SDK log messages go instead of `log`, see
+    /// [`Discord::set_log_sink`](struct.Discord.html#method.set_log_sink)
+    ///
+    /// The sink is moved into the instance built next.
+    pub fn with_log_sink(&mut self, sink: impl 'd + FnMut(LogLevel, &str)) -> &mut Self {
+        self.log_sink = Some(Box::new(sink));
+        self
+    }
+
+    /// The managers initialized during creation, so that their events are received before they
+    /// are first used, all of them by default
+    pub fn with_managers(&mut self, managers: Managers) -> &mut Self {
+        self.managers = managers;
+        self
+    }
+
+    /// The [`EventHandler`](trait.EventHandler.html) of the instance, in place before any event
+    /// can be received
+    ///
+    /// The event handler is moved into the instance built next.
+    pub fn with_event_handler(&mut self, event_handler: E) -> &mut Self {
+        self.event_handler = Some(event_handler);
+        self
+    }
+
+    /// The activity to display, sent once the instance is created
+    ///
+    /// Its result is received during `run_callbacks` and only logged if it fails.
+    pub fn with_activity(&mut self, activity: &Activity) -> &mut Self {
+        self.activity = Some(activity.clone());
+        self
+    }
+
+    /// The command that launches the game, see
+    /// [`Discord::register_launch_command`](struct.Discord.html#method.register_launch_command)
+    pub fn with_launch_command(&mut self, command: &str) -> &mut Self {
+        self.launch_command = Some(command.to_string());
+        self
+    }
+
+    /// Creates the instance, as [`Discord::with_create_flags`](struct.Discord.html#method.with_create_flags)
+    /// does, and sets it up
+    ///
+    /// ## Errors
+    ///
+    /// Those of `with_create_flags`, and those of `register_launch_command` if a launch command
+    /// was given.
+    pub fn build(&mut self) -> Result<Discord<'d, E>>
+    where
+        E: EventHandler,
+    {
+        let setup = Setup {
+            managers: self.managers,
+            log_level: self.log_level,
+            log_sink: self.log_sink.take(),
+        };
+
+        #[cfg(feature = "dynamic")]
+        let library = Discord::<E>::open_library(crate::sys::DEFAULT_LIBRARY_NAME.as_ref())?;
+
+        let mut discord = Discord::create(
+            self.client_id,
+            self.flags,
+            #[cfg(feature = "dynamic")]
+            library,
+            #[cfg(feature = "replay")]
+            None,
+            setup,
+        )?;
+
+        self.finish(&mut discord)?;
+
+        Ok(discord)
+    }
+
+    pub(crate) fn finish(&mut self, discord: &mut Discord<'d, E>) -> Result<()> {
+        if let Some(event_handler) = self.event_handler.take() {
+            *discord.event_handler_mut() = Some(event_handler);
+        }
+
+        if let Some(command) = &self.launch_command {
+            discord.register_launch_command(command.as_str())?;
+        }
+
+        if let Some(activity) = &self.activity {
+            discord.update_activity(activity, |_, res| {
+                if let Err(error) = res {
+                    log::warn!("failed to update activity: {}", error);
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<E> fmt::Debug for DiscordBuilder<'_, E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("DiscordBuilder")
+            .field("client_id", &self.client_id)
+            .field("flags", &self.flags)
+            .field("log_level", &self.log_level)
+            .field("managers", &self.managers)
+            .field("activity", &self.activity)
+            .field("launch_command", &self.launch_command)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{
+            fixtures::{faulty, run},
+            MockFaults,
+        },
+        Error, Event, EventQueue,
+    };
+
+    #[test]
+    fn sets_up_the_instance() {
+        let mut builder = DiscordBuilder::new(0);
+        builder
+            .with_event_handler(EventQueue::default())
+            .with_activity(Activity::empty().with_state("In the menus"))
+            .with_launch_command("game --join");
+
+        let mut discord = Discord::<EventQueue>::mock();
+        builder.finish(&mut discord).unwrap();
+        run(&mut discord);
+
+        assert!(discord
+            .drain_events()
+            .any(|event| event == Event::CurrentUserUpdate));
+
+        let mut faults = MockFaults::new();
+        faults.with_error("register_command", Error::InvalidCommand);
+
+        let mut discord = Discord::<EventQueue>::mock_with(&faulty(faults));
+        assert_eq!(
+            builder.with_launch_command("game").finish(&mut discord),
+            Err(Error::InvalidCommand)
+        );
+    }
+}
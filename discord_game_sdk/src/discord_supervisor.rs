@@ -0,0 +1,376 @@
+use crate::{
+    Activity, ActivityUpdater, ClientID, CreateFlags, Discord, Error, EventHandler, Result,
+};
+use std::{
+    collections::{vec_deque, VecDeque},
+    fmt,
+    time::{Duration, Instant},
+};
+
+type Create<'d, E> = Box<dyn 'd + FnMut() -> Result<Discord<'d, E>>>;
+
+/// Changes of the connection to Discord, see
+/// [`DiscordSupervisor::connection_events`](struct.DiscordSupervisor.html#method.connection_events)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConnectionEvent {
+    /// An instance was created, the activity and launch commands were applied to it again
+    Connected,
+
+    /// The instance failed with this error and was dropped
+    ///
+    /// Callbacks that were still pending are called with `Error::TransactionAborted`.
+    Disconnected(Error),
+
+    /// Creating an instance failed, the next attempt is made in `retry_in`
+    ReconnectFailed { error: Error, retry_in: Duration },
+}
+
+/// Keeps a [`Discord`](struct.Discord.html) instance alive across restarts of the Discord client
+///
+/// When [`run_callbacks`](#method.run_callbacks) fails, usually with `Error::NotRunning` after
+/// Discord was closed, the instance is dropped and a new one is created on later calls, with an
+/// exponential backoff between failed attempts. Once connected again, the desired activity and
+/// the registered launch command or Steam game are applied to the new instance.
+///
+/// The event handler is moved from one instance to the next. Changes of the connection are
+/// reported by [`connection_events`](#method.connection_events).
+///
+/// ```rust,no_run
+/// # use discord_game_sdk::*;
+/// # const DISCORD_CLIENT_ID: ClientID = 0;
+/// # fn example() {
+/// let mut discord = DiscordSupervisor::<()>::new(DISCORD_CLIENT_ID, CreateFlags::NoRequireDiscord);
+///
+/// discord.set_activity(Activity::empty().with_state("In the menus"));
+///
+/// loop {
+///     discord.run_callbacks();
+///
+///     for event in discord.connection_events() {
+///         match event {
+///             ConnectionEvent::Connected => println!("connected to Discord"),
+///             ConnectionEvent::Disconnected(error) => println!("lost Discord: {}", error),
+///             ConnectionEvent::ReconnectFailed { .. } => {}
+///         }
+///     }
+///
+///     if let Some(discord) = discord.discord() {
+///         // ...
+///     }
+/// }
+/// # }
+/// ```
+pub struct DiscordSupervisor<'d, E> {
+    create: Create<'d, E>,
+    discord: Option<Discord<'d, E>>,
+    // Held while disconnected
+    event_handler: Option<E>,
+    updater: ActivityUpdater,
+    activity: Option<Option<Activity>>,
+    launch_command: Option<String>,
+    steam_game_id: Option<u32>,
+    // Consecutive failed attempts
+    retries: u32,
+    retry_at: Option<Instant>,
+    events: VecDeque<ConnectionEvent>,
+}
+
+impl<'d, E: EventHandler> DiscordSupervisor<'d, E> {
+    /// Creates a supervisor of instances created with
+    /// [`Discord::with_create_flags`](struct.Discord.html#method.with_create_flags)
+    ///
+    /// The first instance is created during the first call to
+    /// [`run_callbacks`](#method.run_callbacks).
+    pub fn new(client_id: ClientID, flags: CreateFlags) -> Self {
+        Self::with_create(move || Discord::with_create_flags(client_id, flags))
+    }
+}
+
+impl<'d, E> DiscordSupervisor<'d, E> {
+    /// Delay before the first reconnection attempt, doubled on each consecutive failure
+    const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+    /// Longest delay between two reconnection attempts
+    pub const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+    /// Creates a supervisor of instances created by `create`
+    ///
+    /// The first instance is created during the first call to
+    /// [`run_callbacks`](#method.run_callbacks).
+    pub fn with_create(create: impl 'd + FnMut() -> Result<Discord<'d, E>>) -> Self {
+        Self {
+            create: Box::new(create),
+            discord: None,
+            event_handler: None,
+            updater: ActivityUpdater::new(),
+            activity: None,
+            launch_command: None,
+            steam_game_id: None,
+            retries: 0,
+            retry_at: None,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// The current instance, `None` while disconnected
+    pub fn discord(&self) -> Option<&Discord<'d, E>> {
+        self.discord.as_ref()
+    }
+
+    /// The current instance, `None` while disconnected
+    pub fn discord_mut(&mut self) -> Option<&mut Discord<'d, E>> {
+        self.discord.as_mut()
+    }
+
+    /// Whether an instance is currently running
+    pub fn is_connected(&self) -> bool {
+        self.discord.is_some()
+    }
+
+    /// The event handler, kept while disconnected and given to every new instance
+    pub fn event_handler_mut(&mut self) -> &mut Option<E> {
+        match &mut self.discord {
+            Some(discord) => discord.event_handler_mut(),
+            None => &mut self.event_handler,
+        }
+    }
+
+    /// Sets the activity that should be displayed, now and after reconnecting
+    ///
+    /// Updates are sent with an [`ActivityUpdater`](struct.ActivityUpdater.html).
+    pub fn set_activity(&mut self, activity: &Activity) {
+        self.activity = Some(Some(activity.clone()));
+        self.updater.set(activity);
+    }
+
+    /// Requests that no activity be displayed, now and after reconnecting
+    pub fn clear_activity(&mut self) {
+        self.activity = Some(None);
+        self.updater.clear();
+    }
+
+    /// The last activity the current instance acknowledged
+    pub fn activity(&self) -> Option<Activity> {
+        self.updater.acknowledged()
+    }
+
+    /// Registers the command that launches the game, now and after reconnecting
+    ///
+    /// > [`Discord::register_launch_command`](struct.Discord.html#method.register_launch_command)
+    ///
+    /// ## Errors
+    ///
+    /// Those of the current instance, if any. Failures after reconnecting are logged.
+    pub fn register_launch_command(&mut self, command: &str) -> Result<()> {
+        self.launch_command = Some(command.to_string());
+
+        match &self.discord {
+            Some(discord) => discord.register_launch_command(command),
+            None => Ok(()),
+        }
+    }
+
+    /// Registers the Steam game that launches the game, now and after reconnecting
+    ///
+    /// > [`Discord::register_steam`](struct.Discord.html#method.register_steam)
+    ///
+    /// ## Errors
+    ///
+    /// Those of the current instance, if any. Failures after reconnecting are logged.
+    pub fn register_steam(&mut self, steam_game_id: u32) -> Result<()> {
+        self.steam_game_id = Some(steam_game_id);
+
+        match &self.discord {
+            Some(discord) => discord.register_steam(steam_game_id),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs the callbacks of the current instance, or attempts to create one when due
+    ///
+    /// Must be called regularly, as [`Discord::run_callbacks`](struct.Discord.html#method.run_callbacks)
+    /// would be.
+    pub fn run_callbacks(&mut self) {
+        self.run_callbacks_at(Instant::now())
+    }
+
+    pub(crate) fn run_callbacks_at(&mut self, now: Instant) {
+        match &mut self.discord {
+            Some(discord) => {
+                self.updater.update_at(discord, now);
+
+                if let Err(error) = discord.run_callbacks() {
+                    self.disconnect(error, now);
+                }
+            }
+
+            None if matches!(self.retry_at, Some(at) if now < at) => {}
+
+            None => self.connect(now),
+        }
+    }
+
+    /// Drains the changes of the connection since the last call
+    pub fn connection_events(&mut self) -> vec_deque::Drain<'_, ConnectionEvent> {
+        self.events.drain(..)
+    }
+
+    fn disconnect(&mut self, error: Error, now: Instant) {
+        log::warn!("lost connection to Discord: {}", error);
+
+        if let Some(mut discord) = self.discord.take() {
+            self.event_handler = discord.event_handler_mut().take();
+        }
+
+        self.retries = 0;
+        self.retry_at = Some(now + Self::RETRY_DELAY);
+        self.events.push_back(ConnectionEvent::Disconnected(error));
+    }
+
+    fn connect(&mut self, now: Instant) {
+        let mut discord = match (self.create)() {
+            Ok(discord) => discord,
+            Err(error) => {
+                self.retries += 1;
+
+                let retry_in = Self::retry_delay(self.retries);
+
+                log::debug!("could not reconnect to Discord: {}", error);
+
+                self.retry_at = Some(now + retry_in);
+                self.events
+                    .push_back(ConnectionEvent::ReconnectFailed { error, retry_in });
+                return;
+            }
+        };
+
+        *discord.event_handler_mut() = self.event_handler.take();
+
+        if let Some(command) = &self.launch_command {
+            if let Err(error) = discord.register_launch_command(command.as_str()) {
+                log::warn!("failed to register launch command: {}", error);
+            }
+        }
+
+        if let Some(steam_game_id) = self.steam_game_id {
+            if let Err(error) = discord.register_steam(steam_game_id) {
+                log::warn!("failed to register Steam game: {}", error);
+            }
+        }
+
+        // The new instance starts from scratch
+        self.updater = ActivityUpdater::new();
+
+        match &self.activity {
+            Some(Some(activity)) => self.updater.set(activity),
+            Some(None) => self.updater.clear(),
+            None => {}
+        }
+
+        self.discord = Some(discord);
+        self.retries = 0;
+        self.retry_at = None;
+        self.events.push_back(ConnectionEvent::Connected);
+    }
+
+    fn retry_delay(retries: u32) -> Duration {
+        let factor = 1_u32 << retries.saturating_sub(1).min(16);
+
+        (Self::RETRY_DELAY * factor).min(Self::MAX_RETRY_DELAY)
+    }
+}
+
+impl<E> fmt::Debug for DiscordSupervisor<'_, E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("DiscordSupervisor")
+            .field("connected", &self.is_connected())
+            .field("activity", &self.activity)
+            .field("launch_command", &self.launch_command)
+            .field("steam_game_id", &self.steam_game_id)
+            .field("retries", &self.retries)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{fixtures::faulty, MockFaults},
+        EventQueue,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn reconnects_and_restores_activity() {
+        let attempts = Rc::new(RefCell::new(0));
+        let create = {
+            let attempts = attempts.clone();
+
+            move || {
+                *attempts.borrow_mut() += 1;
+
+                // Discord is still starting on the second attempt
+                if *attempts.borrow() == 2 {
+                    return Err(Error::NotRunning);
+                }
+
+                let mut faults = MockFaults::new();
+                faults.with_not_running_after(3);
+
+                Ok(Discord::mock_with(&faulty(faults)))
+            }
+        };
+
+        let mut supervisor = DiscordSupervisor::<EventQueue>::with_create(create);
+        let start = Instant::now();
+        let state = |supervisor: &DiscordSupervisor<'_, EventQueue>| {
+            supervisor
+                .activity()
+                .map(|activity| activity.state().to_string())
+        };
+
+        *supervisor.event_handler_mut() = Some(EventQueue::default());
+        supervisor.set_activity(Activity::empty().with_state("In a match"));
+        supervisor.register_launch_command("game --join").unwrap();
+
+        for _ in 0..4 {
+            supervisor.run_callbacks_at(start);
+        }
+        assert!(supervisor.is_connected());
+        assert_eq!(state(&supervisor), Some("In a match".to_string()));
+        assert_eq!(
+            supervisor.connection_events().collect::<Vec<_>>(),
+            vec![ConnectionEvent::Connected]
+        );
+
+        supervisor.run_callbacks_at(start);
+        assert!(!supervisor.is_connected());
+        assert!(supervisor.event_handler_mut().is_some());
+
+        // Not due yet
+        supervisor.run_callbacks_at(start);
+        assert_eq!(*attempts.borrow(), 1);
+
+        supervisor.run_callbacks_at(start + Duration::from_secs(1));
+        supervisor.run_callbacks_at(start + Duration::from_secs(2));
+        assert!(supervisor.is_connected());
+        assert_eq!(*attempts.borrow(), 3);
+        assert_eq!(
+            supervisor.connection_events().collect::<Vec<_>>(),
+            vec![
+                ConnectionEvent::Disconnected(Error::NotRunning),
+                ConnectionEvent::ReconnectFailed {
+                    error: Error::NotRunning,
+                    retry_in: Duration::from_secs(1)
+                },
+                ConnectionEvent::Connected,
+            ]
+        );
+
+        assert_eq!(state(&supervisor), None);
+        supervisor.run_callbacks_at(start + Duration::from_secs(2));
+        assert_eq!(state(&supervisor), Some("In a match".to_string()));
+        assert!(supervisor.event_handler_mut().is_some());
+    }
+}
@@ -0,0 +1,463 @@
+use crate::{Discord, Error, Event, EventQueue, Result};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+type Command = Box<dyn FnOnce(&mut Discord<'static, EventQueue>) + Send>;
+
+enum Message {
+    Run(Command),
+    Stop,
+}
+
+/// Owns a [`Discord`](struct.Discord.html) instance on a dedicated thread
+///
+/// `Discord` is neither `Send` nor `Sync`: the instance is created on the thread, which calls
+/// [`run_callbacks`](struct.Discord.html#method.run_callbacks) at a fixed interval and runs the
+/// commands submitted through [`DiscordHandle`](struct.DiscordHandle.html)s in between.
+/// Events are collected with an [`EventQueue`](struct.EventQueue.html) and forwarded to the
+/// `DiscordThread`.
+///
+/// The thread stops when `run_callbacks` fails, when [`stop`](#method.stop) is called or when
+/// the `DiscordThread` is dropped. Commands submitted afterwards fail with
+/// `Error::NotRunning`.
+///
+/// ```rust,no_run
+/// # use discord_game_sdk::*;
+/// # use std::time::Duration;
+/// # const DISCORD_CLIENT_ID: ClientID = 0;
+/// # fn example() -> Result<()> {
+/// let discord = DiscordThread::spawn(Duration::from_millis(16), || {
+///     Discord::new(DISCORD_CLIENT_ID)
+/// })?;
+///
+/// let handle = discord.handle();
+///
+/// std::thread::spawn(move || {
+///     let update = handle.submit(|discord, responder| {
+///         discord.update_activity(
+///             Activity::empty().with_state("In a match"),
+///             move |_, res| responder.respond(res),
+///         )
+///     });
+///
+///     if let Ok(Err(error)) = update.wait() {
+///         eprintln!("failed to update activity: {}", error);
+///     }
+/// });
+///
+/// for event in discord.events() {
+///     // ...
+/// }
+/// # Ok(()) }
+/// ```
+pub struct DiscordThread {
+    handle: DiscordHandle,
+    events: Mutex<Receiver<Event>>,
+    thread: Option<JoinHandle<Result<()>>>,
+    // Cleared by the thread when it exits
+    running: Arc<AtomicBool>,
+}
+
+/// Submits commands to the instance owned by a [`DiscordThread`](struct.DiscordThread.html)
+///
+/// Can be cloned and sent to any thread.
+#[derive(Clone)]
+pub struct DiscordHandle {
+    // `Sender` is not `Sync`
+    sender: Arc<Mutex<Sender<Message>>>,
+}
+
+/// Result of a command submitted with [`DiscordHandle::submit`](struct.DiscordHandle.html#method.submit)
+///
+/// Resolves to `Err(NotRunning)` if the thread stops before a value is provided.
+#[must_use = "the value of the command is lost if this is dropped"]
+pub struct Pending<T>(Receiver<T>);
+
+/// Provides the value of a [`Pending`](struct.Pending.html), usually from within an SDK callback
+pub struct Responder<T>(Sender<T>);
+
+impl DiscordThread {
+    /// Spawns the thread and creates the instance on it with `create`
+    ///
+    /// `run_callbacks` is called every `interval`, commands are run as soon as they are
+    /// received. Commands still queued when `run_callbacks` is due wait until it returned.
+    ///
+    /// ## Errors
+    ///
+    /// The error returned by `create`.
+    pub fn spawn(
+        interval: Duration,
+        create: impl 'static + Send + FnOnce() -> Result<Discord<'static, EventQueue>>,
+    ) -> Result<Self> {
+        let (sender, commands) = mpsc::channel();
+        let (event_sender, events) = mpsc::channel();
+        let (created_sender, created) = mpsc::channel();
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = thread::Builder::new()
+            .name("discord".to_string())
+            .spawn({
+                let running = Stopped(running.clone());
+
+                move || {
+                    // Also cleared if a command panics
+                    let _running = running;
+
+                    let discord = match create() {
+                        Ok(discord) => discord,
+                        Err(error) => {
+                            let _ = created_sender.send(Err(error.clone()));
+                            return Err(error);
+                        }
+                    };
+
+                    let _ = created_sender.send(Ok(()));
+
+                    run(discord, interval, &commands, &event_sender)
+                }
+            })
+            .map_err(|error| {
+                log::error!("could not spawn the Discord thread: {}", error);
+                Error::Internal
+            })?;
+
+        let created = created.recv().unwrap_or(Err(Error::Internal));
+
+        let mut discord_thread = Self {
+            handle: DiscordHandle {
+                sender: Arc::new(Mutex::new(sender)),
+            },
+            events: Mutex::new(events),
+            thread: Some(thread),
+            running,
+        };
+
+        match created {
+            Ok(()) => Ok(discord_thread),
+            Err(error) => {
+                let _ = discord_thread.thread.take().map(JoinHandle::join);
+                Err(error)
+            }
+        }
+    }
+
+    /// Creates a handle to submit commands from other threads
+    pub fn handle(&self) -> DiscordHandle {
+        self.handle.clone()
+    }
+
+    /// Removes the oldest event, if any
+    pub fn try_event(&self) -> Option<Event> {
+        self.events.lock().unwrap().try_recv().ok()
+    }
+
+    /// Waits up to `timeout` for an event
+    ///
+    /// Returns `None` on timeout or once the thread stopped and every event was received.
+    pub fn event_timeout(&self, timeout: Duration) -> Option<Event> {
+        self.events.lock().unwrap().recv_timeout(timeout).ok()
+    }
+
+    /// Removes every event received so far, in order
+    pub fn events(&self) -> std::vec::IntoIter<Event> {
+        let events = self.events.lock().unwrap();
+
+        std::iter::from_fn(|| events.try_recv().ok())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Whether the thread is still running
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Stops the thread and destroys the instance, which aborts pending callbacks
+    ///
+    /// ## Errors
+    ///
+    /// The error returned by `run_callbacks` if the thread had stopped because of it.
+    pub fn stop(mut self) -> Result<()> {
+        self.join()
+    }
+
+    fn join(&mut self) -> Result<()> {
+        let _ = self.handle.send(Message::Stop);
+
+        match self.thread.take().map(JoinHandle::join) {
+            Some(Ok(res)) => res,
+            Some(Err(_)) => Err(Error::Internal),
+            None => Ok(()),
+        }
+    }
+}
+
+// Clears the flag it holds when dropped
+struct Stopped(Arc<AtomicBool>);
+
+impl Drop for Stopped {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+fn run(
+    mut discord: Discord<'static, EventQueue>,
+    interval: Duration,
+    commands: &Receiver<Message>,
+    events: &Sender<Event>,
+) -> Result<()> {
+    *discord.event_handler_mut() = Some(EventQueue::default());
+
+    let mut next_tick = Instant::now();
+
+    loop {
+        // Stops taking commands once the tick is due, a steady stream of them would otherwise
+        // hold back `run_callbacks` indefinitely
+        loop {
+            let timeout = next_tick.saturating_duration_since(Instant::now());
+
+            match commands.recv_timeout(timeout) {
+                Ok(Message::Run(command)) => command(&mut discord),
+                Ok(Message::Stop) | Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                Err(RecvTimeoutError::Timeout) => break,
+            }
+
+            if Instant::now() >= next_tick {
+                break;
+            }
+        }
+
+        next_tick = Instant::now() + interval;
+
+        if let Err(error) = discord.run_callbacks() {
+            log::error!("Discord thread stopped: {}", error);
+            return Err(error);
+        }
+
+        for event in discord.drain_events() {
+            let _ = events.send(event);
+        }
+    }
+}
+
+impl Drop for DiscordThread {
+    fn drop(&mut self) {
+        let _ = self.join();
+    }
+}
+
+impl DiscordHandle {
+    fn send(&self, message: Message) -> Result<()> {
+        self.sender
+            .lock()
+            .unwrap()
+            .send(message)
+            .map_err(|_| Error::NotRunning)
+    }
+
+    /// Runs `command` on the thread and waits for its result
+    ///
+    /// Must not be called from a command or an SDK callback: the thread would wait for itself
+    /// and never return. Use [`submit`](#method.submit) there instead.
+    ///
+    /// ## Errors
+    ///
+    /// `Error::NotRunning` if the thread stopped before running `command`.
+    pub fn run<T: 'static + Send>(
+        &self,
+        command: impl 'static + Send + FnOnce(&mut Discord<'static, EventQueue>) -> T,
+    ) -> Result<T> {
+        self.submit(move |discord, responder| {
+            let value = command(discord);
+            responder.respond(value)
+        })
+        .wait()
+    }
+
+    /// Runs `command` on the thread without waiting
+    ///
+    /// `command` receives a [`Responder`](struct.Responder.html), which can be moved into an SDK
+    /// callback to provide the value of the returned [`Pending`](struct.Pending.html).
+    pub fn submit<T: 'static + Send>(
+        &self,
+        command: impl 'static + Send + FnOnce(&mut Discord<'static, EventQueue>, Responder<T>),
+    ) -> Pending<T> {
+        let (sender, receiver) = mpsc::channel();
+        let responder = Responder(sender);
+
+        // On failure, the responder is dropped and `Pending` resolves to `Err(NotRunning)`
+        let _ = self.send(Message::Run(Box::new(move |discord| {
+            command(discord, responder)
+        })));
+
+        Pending(receiver)
+    }
+}
+
+impl<T> Pending<T> {
+    /// Waits for the value
+    pub fn wait(self) -> Result<T> {
+        self.0.recv().map_err(|_| Error::NotRunning)
+    }
+
+    /// Waits up to `timeout` for the value, `None` on timeout
+    pub fn wait_timeout(&self, timeout: Duration) -> Option<Result<T>> {
+        match self.0.recv_timeout(timeout) {
+            Ok(value) => Some(Ok(value)),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => Some(Err(Error::NotRunning)),
+        }
+    }
+
+    /// Returns the value if it was provided, without waiting
+    pub fn try_wait(&self) -> Option<Result<T>> {
+        self.wait_timeout(Duration::from_secs(0))
+    }
+}
+
+impl<T> Responder<T> {
+    /// Provides the value, ignored if the `Pending` was dropped
+    pub fn respond(self, value: T) {
+        let _ = self.0.send(value);
+    }
+}
+
+impl fmt::Debug for DiscordThread {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("DiscordThread")
+            .field("is_running", &self.is_running())
+            .finish()
+    }
+}
+
+impl fmt::Debug for DiscordHandle {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("DiscordHandle").finish()
+    }
+}
+
+impl<T> fmt::Debug for Pending<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Pending").finish()
+    }
+}
+
+impl<T> fmt::Debug for Responder<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Responder").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{fixtures::faulty, MockFaults},
+        Activity,
+    };
+
+    #[test]
+    fn runs_commands_from_other_threads() {
+        let discord =
+            DiscordThread::spawn(Duration::from_millis(1), || Ok(Discord::mock())).unwrap();
+
+        assert_eq!(
+            discord.event_timeout(Duration::from_secs(5)),
+            Some(Event::CurrentUserUpdate)
+        );
+
+        let handle = discord.handle();
+
+        let worker = std::thread::spawn(move || {
+            let user_id = handle.run(|discord| discord.current_user().map(|user| user.id()));
+
+            let update = handle.submit(|discord, responder| {
+                discord
+                    .update_activity(Activity::empty().with_state("In a match"), move |_, res| {
+                        responder.respond(res)
+                    })
+            });
+
+            (user_id, update.wait())
+        });
+
+        assert_eq!(worker.join().unwrap(), (Ok(Ok(1)), Ok(Ok(()))));
+
+        let handle = discord.handle();
+        assert!(discord.is_running());
+        assert_eq!(discord.stop(), Ok(()));
+
+        assert_eq!(handle.run(|_| ()), Err(Error::NotRunning));
+        assert_eq!(
+            DiscordThread::spawn(Duration::from_millis(1), || Err(Error::NotRunning)).err(),
+            Some(Error::NotRunning)
+        );
+    }
+
+    #[test]
+    fn ticks_under_a_stream_of_commands() {
+        // Every command queues the next one, the queue is never empty
+        fn resubmit(handle: DiscordHandle, done: Arc<AtomicBool>) {
+            if !done.load(Ordering::SeqCst) {
+                let next = handle.clone();
+                let _ = handle.submit(move |_, responder| {
+                    resubmit(next, done);
+                    responder.respond(())
+                });
+            }
+        }
+
+        let discord =
+            DiscordThread::spawn(Duration::from_millis(1), || Ok(Discord::mock())).unwrap();
+
+        let handle = discord.handle();
+        let done = Arc::new(AtomicBool::new(false));
+
+        resubmit(handle.clone(), done.clone());
+
+        let update = handle.submit(|discord, responder| {
+            discord.update_activity(Activity::empty().with_state("In a match"), move |_, res| {
+                responder.respond(res)
+            })
+        });
+
+        let res = update.wait_timeout(Duration::from_secs(5));
+        done.store(true, Ordering::SeqCst);
+
+        assert_eq!(res, Some(Ok(Ok(()))));
+        assert_eq!(discord.stop(), Ok(()));
+    }
+
+    #[test]
+    fn stops_when_run_callbacks_fails() {
+        let mut faults = MockFaults::new();
+        faults.with_not_running_after(3);
+        let config = faulty(faults);
+
+        let discord = DiscordThread::spawn(Duration::from_millis(1), move || {
+            Ok(Discord::mock_with(&config))
+        })
+        .unwrap();
+
+        let start = Instant::now();
+        while discord.is_running() && start.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        assert!(!discord.is_running());
+        assert_eq!(
+            format!("{:?}", discord),
+            "DiscordThread { is_running: false }"
+        );
+        assert_eq!(discord.stop(), Err(Error::NotRunning));
+    }
+}
@@ -4,6 +4,7 @@ use crate::sys;
 ///
 /// > [Enum in official docs](https://discordapp.com/developers/docs/game-sdk/store#data-models-entitlementtype-enum)
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EntitlementKind {
     /// Entitlement was gifted by a developer
     DeveloperGift,
@@ -37,3 +38,18 @@ impl From<sys::EDiscordEntitlementType> for EntitlementKind {
         }
     }
 }
+
+impl From<EntitlementKind> for sys::EDiscordEntitlementType {
+    fn from(source: EntitlementKind) -> Self {
+        match source {
+            EntitlementKind::DeveloperGift => sys::DiscordEntitlementType_DeveloperGift,
+            EntitlementKind::FreePurchase => sys::DiscordEntitlementType_FreePurchase,
+            EntitlementKind::PremiumPurchase => sys::DiscordEntitlementType_PremiumPurchase,
+            EntitlementKind::PremiumSubscription => sys::DiscordEntitlementType_PremiumSubscription,
+            EntitlementKind::Purchase => sys::DiscordEntitlementType_Purchase,
+            EntitlementKind::TestModePurchase => sys::DiscordEntitlementType_TestModePurchase,
+            EntitlementKind::UserGift => sys::DiscordEntitlementType_UserGift,
+            EntitlementKind::Undefined(n) => n,
+        }
+    }
+}
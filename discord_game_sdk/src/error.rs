@@ -1,4 +1,4 @@
-use crate::sys;
+use crate::{sys, ValidationError};
 use std::fmt;
 
 /// Alias for a `Result` with the error type [`discord_game_sdk::Error`]
@@ -9,7 +9,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// Discord Error
 ///
 /// > [Enum in official docs](https://discordapp.com/developers/docs/game-sdk/discord#data-models-result-enum)
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Error {
     /// Discord isn't working
     ServiceUnavailable,
@@ -140,6 +141,12 @@ pub enum Error {
     /// Transaction aborted
     TransactionAborted,
 
+    /// The SDK library could not be loaded (`dynamic` feature)
+    LibraryUnavailable,
+
+    /// A string does not fit the buffer the SDK keeps for it, nothing was sent
+    InvalidString(ValidationError),
+
     /// Safety net for missing definitions
     Undefined(sys::EDiscordResult),
 }
@@ -192,6 +199,8 @@ impl fmt::Display for Error {
             InvalidGiftCode => "invalid gift code",
             Purchase => "purchase error",
             TransactionAborted => "transaction aborted",
+            LibraryUnavailable => "library unavailable",
+            InvalidString(error) => return write!(f, "invalid string: {}", error),
             Undefined(n) => return write!(f, "undefined error {}", n),
         };
 
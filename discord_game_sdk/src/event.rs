@@ -0,0 +1,452 @@
+use crate::{
+    Action, Activity, Discord, Entitlement, EventHandler, LobbyID, NetworkChannelID, NetworkPeerID,
+    Relationship, User, UserAchievement, UserID,
+};
+use std::collections::{vec_deque, VecDeque};
+
+/// Owned copy of an SDK event
+///
+/// Each variant mirrors a method of [`EventHandler`](trait.EventHandler.html),
+/// see the methods for details.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// [`on_user_achievement_update`](trait.EventHandler.html#method.on_user_achievement_update)
+    UserAchievementUpdate {
+        /// The updated achievement
+        user_achievement: UserAchievement,
+    },
+
+    /// [`on_activity_join`](trait.EventHandler.html#method.on_activity_join)
+    ActivityJoin {
+        /// Join secret of the activity
+        secret: String,
+    },
+
+    /// [`on_activity_spectate`](trait.EventHandler.html#method.on_activity_spectate)
+    ActivitySpectate {
+        /// Spectate secret of the activity
+        secret: String,
+    },
+
+    /// [`on_activity_join_request`](trait.EventHandler.html#method.on_activity_join_request)
+    ActivityJoinRequest {
+        /// User asking to join
+        user: User,
+    },
+
+    /// [`on_activity_invite`](trait.EventHandler.html#method.on_activity_invite)
+    ActivityInvite {
+        /// Whether it is an invitation to join or to spectate
+        kind: Action,
+        /// User sending the invitation
+        user: User,
+        /// Activity the current user is invited to
+        activity: Activity,
+    },
+
+    /// [`on_lobby_update`](trait.EventHandler.html#method.on_lobby_update)
+    LobbyUpdate {
+        /// Updated lobby
+        lobby_id: LobbyID,
+    },
+
+    /// [`on_lobby_delete`](trait.EventHandler.html#method.on_lobby_delete)
+    LobbyDelete {
+        /// Deleted lobby
+        lobby_id: LobbyID,
+        /// Reason for deletion, as given by the SDK
+        reason: u32,
+    },
+
+    /// [`on_member_connect`](trait.EventHandler.html#method.on_member_connect)
+    MemberConnect {
+        /// Lobby that was joined
+        lobby_id: LobbyID,
+        /// New member
+        member_id: UserID,
+    },
+
+    /// [`on_member_update`](trait.EventHandler.html#method.on_member_update)
+    MemberUpdate {
+        /// Lobby of the member
+        lobby_id: LobbyID,
+        /// Updated member
+        member_id: UserID,
+    },
+
+    /// [`on_member_disconnect`](trait.EventHandler.html#method.on_member_disconnect)
+    MemberDisconnect {
+        /// Lobby that was left
+        lobby_id: LobbyID,
+        /// Former member
+        member_id: UserID,
+    },
+
+    /// [`on_lobby_message`](trait.EventHandler.html#method.on_lobby_message)
+    LobbyMessage {
+        /// Lobby the message was sent to
+        lobby_id: LobbyID,
+        /// Sender
+        member_id: UserID,
+        /// Contents of the message
+        data: Vec<u8>,
+    },
+
+    /// [`on_speaking`](trait.EventHandler.html#method.on_speaking)
+    Speaking {
+        /// Lobby of the voice channel
+        lobby_id: LobbyID,
+        /// Member whose state changed
+        member_id: UserID,
+        /// Whether the member started speaking
+        speaking: bool,
+    },
+
+    /// [`on_lobby_network_message`](trait.EventHandler.html#method.on_lobby_network_message)
+    LobbyNetworkMessage {
+        /// Lobby the message was sent through
+        lobby_id: LobbyID,
+        /// Sender
+        member_id: UserID,
+        /// Channel the message was sent on
+        channel_id: NetworkChannelID,
+        /// Contents of the message
+        data: Vec<u8>,
+    },
+
+    /// [`on_network_message`](trait.EventHandler.html#method.on_network_message)
+    NetworkMessage {
+        /// Sender
+        peer_id: NetworkPeerID,
+        /// Channel the message was sent on
+        channel_id: NetworkChannelID,
+        /// Contents of the message
+        data: Vec<u8>,
+    },
+
+    /// [`on_network_route_update`](trait.EventHandler.html#method.on_network_route_update)
+    NetworkRouteUpdate {
+        /// New route of the current user
+        route: String,
+    },
+
+    /// [`on_overlay_toggle`](trait.EventHandler.html#method.on_overlay_toggle)
+    OverlayToggle {
+        /// Whether the overlay was closed
+        closed: bool,
+    },
+
+    /// [`on_relationships_refresh`](trait.EventHandler.html#method.on_relationships_refresh)
+    RelationshipsRefresh,
+
+    /// [`on_relationship_update`](trait.EventHandler.html#method.on_relationship_update)
+    RelationshipUpdate {
+        /// Updated relationship
+        relationship: Relationship,
+    },
+
+    /// [`on_entitlement_create`](trait.EventHandler.html#method.on_entitlement_create)
+    EntitlementCreate {
+        /// New entitlement
+        entitlement: Entitlement,
+    },
+
+    /// [`on_entitlement_delete`](trait.EventHandler.html#method.on_entitlement_delete)
+    EntitlementDelete {
+        /// Lost entitlement
+        entitlement: Entitlement,
+    },
+
+    /// [`on_current_user_update`](trait.EventHandler.html#method.on_current_user_update)
+    CurrentUserUpdate,
+
+    /// [`on_voice_settings_update`](trait.EventHandler.html#method.on_voice_settings_update)
+    VoiceSettingsUpdate,
+}
+
+/// [`EventHandler`](trait.EventHandler.html) that stores every event as an owned
+/// [`Event`](enum.Event.html), to be drained after
+/// [`run_callbacks`](struct.Discord.html#method.run_callbacks).
+///
+/// This suits game loops that poll for events rather than react to them as they are received.
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # fn example(mut discord: Discord<'_, EventQueue>) -> Result<()> {
+/// *discord.event_handler_mut() = Some(EventQueue::default());
+///
+/// loop {
+///     discord.run_callbacks()?;
+///
+///     for event in discord.drain_events() {
+///         match event {
+///             Event::LobbyMessage { lobby_id, data, .. } => {
+///                 // ...
+///             }
+///             _ => {}
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EventQueue(VecDeque<Event>);
+
+impl EventQueue {
+    /// Removes all events in the order they were received
+    pub fn drain(&mut self) -> vec_deque::Drain<'_, Event> {
+        self.0.drain(..)
+    }
+
+    /// Removes the oldest event
+    pub fn pop(&mut self) -> Option<Event> {
+        self.0.pop_front()
+    }
+
+    /// Number of events waiting
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no events are waiting
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the waiting events without removing them
+    pub fn iter(&self) -> vec_deque::Iter<'_, Event> {
+        self.0.iter()
+    }
+
+    fn push(&mut self, event: Event) {
+        self.0.push_back(event)
+    }
+}
+
+impl EventHandler for EventQueue {
+    fn on_user_achievement_update(
+        &mut self,
+        _: &Discord<'_, Self>,
+        user_achievement: &UserAchievement,
+    ) {
+        self.push(Event::UserAchievementUpdate {
+            user_achievement: user_achievement.clone(),
+        })
+    }
+
+    fn on_activity_join(&mut self, _: &Discord<'_, Self>, secret: &str) {
+        self.push(Event::ActivityJoin {
+            secret: secret.to_string(),
+        })
+    }
+
+    fn on_activity_spectate(&mut self, _: &Discord<'_, Self>, secret: &str) {
+        self.push(Event::ActivitySpectate {
+            secret: secret.to_string(),
+        })
+    }
+
+    fn on_activity_join_request(&mut self, _: &Discord<'_, Self>, user: &User) {
+        self.push(Event::ActivityJoinRequest { user: user.clone() })
+    }
+
+    fn on_activity_invite(
+        &mut self,
+        _: &Discord<'_, Self>,
+        kind: Action,
+        user: &User,
+        activity: &Activity,
+    ) {
+        self.push(Event::ActivityInvite {
+            kind,
+            user: user.clone(),
+            activity: activity.clone(),
+        })
+    }
+
+    fn on_lobby_update(&mut self, _: &Discord<'_, Self>, lobby_id: LobbyID) {
+        self.push(Event::LobbyUpdate { lobby_id })
+    }
+
+    fn on_lobby_delete(&mut self, _: &Discord<'_, Self>, lobby_id: LobbyID, reason: u32) {
+        self.push(Event::LobbyDelete { lobby_id, reason })
+    }
+
+    fn on_member_connect(&mut self, _: &Discord<'_, Self>, lobby_id: LobbyID, member_id: UserID) {
+        self.push(Event::MemberConnect {
+            lobby_id,
+            member_id,
+        })
+    }
+
+    fn on_member_update(&mut self, _: &Discord<'_, Self>, lobby_id: LobbyID, member_id: UserID) {
+        self.push(Event::MemberUpdate {
+            lobby_id,
+            member_id,
+        })
+    }
+
+    fn on_member_disconnect(
+        &mut self,
+        _: &Discord<'_, Self>,
+        lobby_id: LobbyID,
+        member_id: UserID,
+    ) {
+        self.push(Event::MemberDisconnect {
+            lobby_id,
+            member_id,
+        })
+    }
+
+    fn on_lobby_message(
+        &mut self,
+        _: &Discord<'_, Self>,
+        lobby_id: LobbyID,
+        member_id: UserID,
+        data: &[u8],
+    ) {
+        self.push(Event::LobbyMessage {
+            lobby_id,
+            member_id,
+            data: data.to_vec(),
+        })
+    }
+
+    fn on_speaking(
+        &mut self,
+        _: &Discord<'_, Self>,
+        lobby_id: LobbyID,
+        member_id: UserID,
+        speaking: bool,
+    ) {
+        self.push(Event::Speaking {
+            lobby_id,
+            member_id,
+            speaking,
+        })
+    }
+
+    fn on_lobby_network_message(
+        &mut self,
+        _: &Discord<'_, Self>,
+        lobby_id: LobbyID,
+        member_id: UserID,
+        channel_id: NetworkChannelID,
+        data: &[u8],
+    ) {
+        self.push(Event::LobbyNetworkMessage {
+            lobby_id,
+            member_id,
+            channel_id,
+            data: data.to_vec(),
+        })
+    }
+
+    fn on_network_message(
+        &mut self,
+        _: &Discord<'_, Self>,
+        peer_id: NetworkPeerID,
+        channel_id: NetworkChannelID,
+        data: &[u8],
+    ) {
+        self.push(Event::NetworkMessage {
+            peer_id,
+            channel_id,
+            data: data.to_vec(),
+        })
+    }
+
+    fn on_network_route_update(&mut self, _: &Discord<'_, Self>, route: &str) {
+        self.push(Event::NetworkRouteUpdate {
+            route: route.to_string(),
+        })
+    }
+
+    fn on_overlay_toggle(&mut self, _: &Discord<'_, Self>, closed: bool) {
+        self.push(Event::OverlayToggle { closed })
+    }
+
+    fn on_relationships_refresh(&mut self, _: &Discord<'_, Self>) {
+        self.push(Event::RelationshipsRefresh)
+    }
+
+    fn on_relationship_update(&mut self, _: &Discord<'_, Self>, relationship: &Relationship) {
+        self.push(Event::RelationshipUpdate {
+            relationship: relationship.clone(),
+        })
+    }
+
+    fn on_entitlement_create(&mut self, _: &Discord<'_, Self>, entitlement: &Entitlement) {
+        self.push(Event::EntitlementCreate {
+            entitlement: entitlement.clone(),
+        })
+    }
+
+    fn on_entitlement_delete(&mut self, _: &Discord<'_, Self>, entitlement: &Entitlement) {
+        self.push(Event::EntitlementDelete {
+            entitlement: entitlement.clone(),
+        })
+    }
+
+    fn on_current_user_update(&mut self, _: &Discord<'_, Self>) {
+        self.push(Event::CurrentUserUpdate)
+    }
+
+    fn on_voice_settings_update(&mut self, _: &Discord<'_, Self>) {
+        self.push(Event::VoiceSettingsUpdate)
+    }
+}
+
+impl<'a> IntoIterator for &'a EventQueue {
+    type Item = &'a Event;
+    type IntoIter = vec_deque::Iter<'a, Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Discord<'_, EventQueue> {
+    /// Removes all events received so far, in order.
+    ///
+    /// Installs an empty [`EventQueue`](struct.EventQueue.html) if there was no event handler,
+    /// events that were fired before that are lost.
+    pub fn drain_events(&mut self) -> vec_deque::Drain<'_, Event> {
+        self.event_handler_mut()
+            .get_or_insert_with(EventQueue::default)
+            .drain()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{fixtures::queued, MockConfig},
+        SearchQuery,
+    };
+
+    #[test]
+    fn events_are_queued_until_drained() {
+        let mut discord = queued(&MockConfig::new());
+
+        discord.run_callbacks().unwrap();
+
+        let events: Vec<_> = discord.drain_events().collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::CurrentUserUpdate,
+                Event::RelationshipsRefresh,
+                Event::NetworkRouteUpdate {
+                    route: "mock-route-1".to_string()
+                },
+            ]
+        );
+
+        discord.lobby_search(&SearchQuery::new(), |_, _| {});
+        discord.run_callbacks().unwrap();
+
+        assert_eq!(discord.drain_events().count(), 0);
+    }
+}
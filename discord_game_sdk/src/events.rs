@@ -4,13 +4,18 @@ use crate::{
 };
 use std::{ffi::c_void, mem::ManuallyDrop};
 
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
 fn with_event_handler<E>(
     inner: *mut c_void,
+    name: &'static str,
     callback: impl FnOnce(&mut E, &Discord<'_, E>) + std::panic::UnwindSafe,
 ) {
     utils::abort_on_panic(|| {
         debug_assert!(!inner.is_null());
 
+        #[cfg(feature = "tracing")]
+        let _delivery = tracing::debug_span!("event", name).entered();
+
         let discord = &ManuallyDrop::new(Discord(inner as *mut DiscordInner<'_, E>));
 
         // SAFETY: Mutating through an immutable reference
@@ -37,11 +42,15 @@ pub(crate) fn achievement<E: EventHandler>() -> sys::IDiscordAchievementEvents {
                 inner: *mut c_void,
                 user_achievement: *mut sys::DiscordUserAchievement,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
-                    eh.on_user_achievement_update(discord, unsafe {
-                        &*(user_achievement as *const UserAchievement)
-                    })
-                })
+                with_event_handler(
+                    inner,
+                    "on_user_achievement_update",
+                    |eh: &mut E, discord| {
+                        eh.on_user_achievement_update(discord, unsafe {
+                            &*(user_achievement as *const UserAchievement)
+                        })
+                    },
+                )
             }
 
             Some(on_user_achievement_update::<E>)
@@ -53,7 +62,7 @@ pub(crate) fn activity<E: EventHandler>() -> sys::IDiscordActivityEvents {
     sys::IDiscordActivityEvents {
         on_activity_join: {
             extern "C" fn on_activity_join<E: EventHandler>(inner: *mut c_void, secret: *const u8) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_activity_join", |eh: &mut E, discord| {
                     eh.on_activity_join(discord, unsafe { utils::charptr_to_str(secret) })
                 })
             }
@@ -66,7 +75,7 @@ pub(crate) fn activity<E: EventHandler>() -> sys::IDiscordActivityEvents {
                 inner: *mut c_void,
                 secret: *const u8,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_activity_spectate", |eh: &mut E, discord| {
                     eh.on_activity_spectate(discord, unsafe { utils::charptr_to_str(secret) })
                 })
             }
@@ -79,7 +88,7 @@ pub(crate) fn activity<E: EventHandler>() -> sys::IDiscordActivityEvents {
                 inner: *mut c_void,
                 user: *mut sys::DiscordUser,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_activity_join_request", |eh: &mut E, discord| {
                     eh.on_activity_join_request(discord, unsafe { &*(user as *const User) })
                 })
             }
@@ -94,7 +103,7 @@ pub(crate) fn activity<E: EventHandler>() -> sys::IDiscordActivityEvents {
                 user: *mut sys::DiscordUser,
                 activity: *mut sys::DiscordActivity,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_activity_invite", |eh: &mut E, discord| {
                     eh.on_activity_invite(
                         discord,
                         kind.into(),
@@ -116,7 +125,7 @@ pub(crate) fn lobby<E: EventHandler>() -> sys::IDiscordLobbyEvents {
                 inner: *mut c_void,
                 lobby_id: sys::DiscordLobbyId,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_lobby_update", |eh: &mut E, discord| {
                     eh.on_lobby_update(discord, lobby_id)
                 })
             }
@@ -130,7 +139,7 @@ pub(crate) fn lobby<E: EventHandler>() -> sys::IDiscordLobbyEvents {
                 lobby_id: sys::DiscordLobbyId,
                 reason: u32,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_lobby_delete", |eh: &mut E, discord| {
                     eh.on_lobby_delete(discord, lobby_id, reason)
                 })
             }
@@ -144,7 +153,7 @@ pub(crate) fn lobby<E: EventHandler>() -> sys::IDiscordLobbyEvents {
                 lobby_id: sys::DiscordLobbyId,
                 member_id: sys::DiscordUserId,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_member_connect", |eh: &mut E, discord| {
                     eh.on_member_connect(discord, lobby_id, member_id)
                 })
             }
@@ -158,7 +167,7 @@ pub(crate) fn lobby<E: EventHandler>() -> sys::IDiscordLobbyEvents {
                 lobby_id: sys::DiscordLobbyId,
                 member_id: sys::DiscordUserId,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_member_update", |eh: &mut E, discord| {
                     eh.on_member_update(discord, lobby_id, member_id)
                 })
             }
@@ -172,7 +181,7 @@ pub(crate) fn lobby<E: EventHandler>() -> sys::IDiscordLobbyEvents {
                 lobby_id: sys::DiscordLobbyId,
                 member_id: sys::DiscordUserId,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_member_disconnect", |eh: &mut E, discord| {
                     eh.on_member_disconnect(discord, lobby_id, member_id)
                 })
             }
@@ -188,7 +197,7 @@ pub(crate) fn lobby<E: EventHandler>() -> sys::IDiscordLobbyEvents {
                 data: *mut u8,
                 data_len: u32,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_lobby_message", |eh: &mut E, discord| {
                     eh.on_lobby_message(discord, lobby_id, member_id, unsafe {
                         std::slice::from_raw_parts(data, data_len as usize)
                     })
@@ -205,7 +214,7 @@ pub(crate) fn lobby<E: EventHandler>() -> sys::IDiscordLobbyEvents {
                 member_id: sys::DiscordUserId,
                 speaking: bool,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_speaking", |eh: &mut E, discord| {
                     eh.on_speaking(discord, lobby_id, member_id, speaking)
                 })
             }
@@ -222,7 +231,7 @@ pub(crate) fn lobby<E: EventHandler>() -> sys::IDiscordLobbyEvents {
                 data: *mut u8,
                 data_len: u32,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_network_message", |eh: &mut E, discord| {
                     eh.on_lobby_network_message(discord, lobby_id, member_id, channel_id, unsafe {
                         std::slice::from_raw_parts(data, data_len as usize)
                     })
@@ -244,7 +253,7 @@ pub(crate) fn network<E: EventHandler>() -> sys::IDiscordNetworkEvents {
                 data: *mut u8,
                 data_len: u32,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_message", |eh: &mut E, discord| {
                     eh.on_network_message(discord, peer_id, channel_id, unsafe {
                         std::slice::from_raw_parts(data, data_len as usize)
                     })
@@ -256,7 +265,7 @@ pub(crate) fn network<E: EventHandler>() -> sys::IDiscordNetworkEvents {
 
         on_route_update: {
             extern "C" fn on_route_update<E: EventHandler>(inner: *mut c_void, route: *const u8) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_route_update", |eh: &mut E, discord| {
                     eh.on_network_route_update(discord, unsafe { utils::charptr_to_str(route) })
                 })
             }
@@ -270,7 +279,7 @@ pub(crate) fn overlay<E: EventHandler>() -> sys::IDiscordOverlayEvents {
     sys::IDiscordOverlayEvents {
         on_toggle: {
             extern "C" fn on_toggle<E: EventHandler>(inner: *mut c_void, locked: bool) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_toggle", |eh: &mut E, discord| {
                     eh.on_overlay_toggle(discord, !locked)
                 })
             }
@@ -284,7 +293,7 @@ pub(crate) fn relationship<E: EventHandler>() -> sys::IDiscordRelationshipEvents
     sys::IDiscordRelationshipEvents {
         on_refresh: {
             extern "C" fn on_refresh<E: EventHandler>(inner: *mut c_void) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_refresh", |eh: &mut E, discord| {
                     eh.on_relationships_refresh(discord)
                 })
             }
@@ -297,7 +306,7 @@ pub(crate) fn relationship<E: EventHandler>() -> sys::IDiscordRelationshipEvents
                 inner: *mut c_void,
                 relationship: *mut sys::DiscordRelationship,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_relationship_update", |eh: &mut E, discord| {
                     eh.on_relationship_update(discord, unsafe {
                         &*(relationship as *const Relationship)
                     })
@@ -316,7 +325,7 @@ pub(crate) fn store<E: EventHandler>() -> sys::IDiscordStoreEvents {
                 inner: *mut c_void,
                 entitlement: *mut sys::DiscordEntitlement,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_entitlement_create", |eh: &mut E, discord| {
                     eh.on_entitlement_create(discord, unsafe {
                         &*(entitlement as *const Entitlement)
                     })
@@ -331,7 +340,7 @@ pub(crate) fn store<E: EventHandler>() -> sys::IDiscordStoreEvents {
                 inner: *mut c_void,
                 entitlement: *mut sys::DiscordEntitlement,
             ) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_entitlement_delete", |eh: &mut E, discord| {
                     eh.on_entitlement_delete(discord, unsafe {
                         &*(entitlement as *const Entitlement)
                     })
@@ -347,7 +356,7 @@ pub(crate) fn user<E: EventHandler>() -> sys::IDiscordUserEvents {
     sys::IDiscordUserEvents {
         on_current_user_update: {
             extern "C" fn on_current_user_update<E: EventHandler>(inner: *mut c_void) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_current_user_update", |eh: &mut E, discord| {
                     eh.on_current_user_update(discord)
                 })
             }
@@ -361,7 +370,7 @@ pub(crate) fn voice<E: EventHandler>() -> sys::IDiscordVoiceEvents {
     sys::IDiscordVoiceEvents {
         on_settings_update: {
             extern "C" fn on_settings_update<E: EventHandler>(inner: *mut c_void) {
-                with_event_handler(inner, |eh: &mut E, discord| {
+                with_event_handler(inner, "on_settings_update", |eh: &mut E, discord| {
                     eh.on_voice_settings_update(discord)
                 })
             }
@@ -0,0 +1,567 @@
+use crate::{Discord, Error, LobbyID, NetworkChannelID, NetworkPeerID, Result, UserID};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    convert::TryFrom,
+};
+
+/// Identifies a message sent through a [`Framer`](struct.Framer.html)
+pub type MessageID = u32;
+
+const KIND_FRAGMENT: u8 = 0;
+const KIND_ACKNOWLEDGEMENT: u8 = 1;
+
+// kind + message ID
+const ACKNOWLEDGEMENT_LEN: usize = 1 + 4;
+// kind + message ID + fragment index + fragment count
+const HEADER_LEN: usize = ACKNOWLEDGEMENT_LEN + 4 + 4;
+
+// How many completed messages are remembered to recognize their duplicate fragments
+const RECENTLY_COMPLETED: usize = 256;
+
+/// Outcome of handing a received packet to a [`Framer`](struct.Framer.html)
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Frame {
+    /// A fragment was stored, the message it belongs to is not complete yet
+    Incomplete,
+
+    /// All fragments of a message were received
+    Message {
+        /// ID given by the sender
+        message_id: MessageID,
+        /// Reassembled contents
+        data: Vec<u8>,
+    },
+
+    /// The peer received all fragments of a message that was sent to it
+    Delivered {
+        /// ID returned when sending
+        message_id: MessageID,
+    },
+}
+
+/// Splits messages into fragments small enough for the SDK's networking, reassembles them on
+/// the receiving end and acknowledges complete messages back to the sender.
+///
+/// A `Framer` holds the state of one conversation: use one per peer (or lobby member) and
+/// channel, on both ends. Channels should be opened with
+/// [`Reliability::Reliable`](enum.Reliability.html#variant.Reliable), fragments lost on
+/// unreliable channels leave their message incomplete and it is never acknowledged.
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # const CHANNEL: NetworkChannelID = 0;
+/// # fn example(mut discord: Discord<'_, EventQueue>, peer_id: NetworkPeerID, snapshot: Vec<u8>) -> Result<()> {
+/// let mut framer = Framer::new();
+///
+/// let message_id = discord.send_framed_message(&mut framer, peer_id, CHANNEL, &snapshot)?;
+///
+/// loop {
+///     discord.run_callbacks()?;
+///
+///     let events: Vec<_> = discord.drain_events().collect();
+///     for event in events {
+///         if let Event::NetworkMessage { peer_id, channel_id: CHANNEL, data } = event {
+///             match discord.receive_framed_message(&mut framer, peer_id, CHANNEL, &data)? {
+///                 Frame::Message { data, .. } => { /* a whole snapshot was received */ }
+///                 Frame::Delivered { message_id } => { /* the peer has our snapshot */ }
+///                 Frame::Incomplete => {}
+///             }
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Framer {
+    fragment_size: usize,
+    next_message_id: MessageID,
+    // How many IDs were handed out, they wrap around after `MessageID::MAX`
+    sent: u64,
+    unacknowledged: BTreeSet<MessageID>,
+    incomplete: HashMap<MessageID, Reassembly>,
+    // Bytes of the packets held by `incomplete`
+    buffered: usize,
+    completed: VecDeque<MessageID>,
+}
+
+#[derive(Clone, Debug)]
+struct Reassembly {
+    count: u32,
+    fragments: BTreeMap<u32, Vec<u8>>,
+}
+
+impl Framer {
+    /// Default maximum length of a fragment's payload, in bytes
+    ///
+    /// Stays below common MTUs once headers are added.
+    pub const DEFAULT_FRAGMENT_SIZE: usize = 1024;
+
+    /// Most fragments a message can be split into
+    pub const MAX_FRAGMENTS: u32 = 1 << 16;
+
+    /// Most bytes of incomplete messages kept at once, headers included, fragments beyond it
+    /// are refused
+    pub const MAX_BUFFERED: usize = 1 << 26;
+
+    /// Creates a `Framer` with [`DEFAULT_FRAGMENT_SIZE`](#associatedconstant.DEFAULT_FRAGMENT_SIZE)
+    pub fn new() -> Self {
+        Self::with_fragment_size(Self::DEFAULT_FRAGMENT_SIZE)
+    }
+
+    /// Creates a `Framer` that produces fragments with payloads of at most `fragment_size` bytes.
+    ///
+    /// Both ends do not need to agree on this value.
+    ///
+    /// ## Panics
+    ///
+    /// If `fragment_size` is 0.
+    pub fn with_fragment_size(fragment_size: usize) -> Self {
+        assert!(fragment_size > 0, "fragment size must not be 0");
+
+        Self {
+            fragment_size,
+            next_message_id: 0,
+            sent: 0,
+            unacknowledged: BTreeSet::new(),
+            incomplete: HashMap::new(),
+            buffered: 0,
+            completed: VecDeque::with_capacity(RECENTLY_COMPLETED),
+        }
+    }
+
+    /// Splits `data` into packets to be sent in order, the message is then awaiting
+    /// acknowledgement.
+    ///
+    /// ## Errors
+    ///
+    /// If `data` requires more than [`MAX_FRAGMENTS`](#associatedconstant.MAX_FRAGMENTS)
+    /// fragments, [`Error::InvalidPayload`](enum.Error.html#variant.InvalidPayload) is returned.
+    pub fn fragment(&mut self, data: &[u8]) -> Result<(MessageID, Vec<Vec<u8>>)> {
+        let chunks: Vec<&[u8]> = if data.is_empty() {
+            vec![&[]]
+        } else {
+            data.chunks(self.fragment_size).collect()
+        };
+
+        let count = u32::try_from(chunks.len())
+            .ok()
+            .filter(|&count| count <= Self::MAX_FRAGMENTS)
+            .ok_or(Error::InvalidPayload)?;
+
+        let message_id = self.next_message_id;
+        self.next_message_id = self.next_message_id.wrapping_add(1);
+        self.sent = self.sent.saturating_add(1);
+
+        let packets = chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| {
+                let mut packet = Vec::with_capacity(HEADER_LEN + chunk.len());
+                packet.push(KIND_FRAGMENT);
+                packet.extend_from_slice(&message_id.to_le_bytes());
+                packet.extend_from_slice(&(index as u32).to_le_bytes());
+                packet.extend_from_slice(&count.to_le_bytes());
+                packet.extend_from_slice(chunk);
+                packet
+            })
+            .collect();
+
+        self.unacknowledged.insert(message_id);
+
+        Ok((message_id, packets))
+    }
+
+    /// Handles a packet received from the other end.
+    ///
+    /// When a message is complete, an acknowledgement packet is also returned, it must be sent
+    /// back for the other end to report the message as delivered. Duplicate fragments of a
+    /// recently completed message are acknowledged again and otherwise ignored.
+    ///
+    /// ## Errors
+    ///
+    /// If the packet was not produced by a `Framer`, or keeping the fragment would exceed
+    /// [`MAX_BUFFERED`](#associatedconstant.MAX_BUFFERED),
+    /// [`Error::InvalidPayload`](enum.Error.html#variant.InvalidPayload) is returned.
+    pub fn reassemble(&mut self, packet: &[u8]) -> Result<(Frame, Option<Vec<u8>>)> {
+        let read_u32 = |at: usize| {
+            packet
+                .get(at..at + 4)
+                .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .ok_or(Error::InvalidPayload)
+        };
+
+        let kind = *packet.first().ok_or(Error::InvalidPayload)?;
+        let message_id = read_u32(1)?;
+
+        match kind {
+            KIND_ACKNOWLEDGEMENT if packet.len() == ACKNOWLEDGEMENT_LEN => {
+                // Duplicates and unknown IDs are harmless, report them once at most
+                if self.unacknowledged.remove(&message_id) {
+                    Ok((Frame::Delivered { message_id }, None))
+                } else {
+                    Ok((Frame::Incomplete, None))
+                }
+            }
+
+            KIND_FRAGMENT if packet.len() >= HEADER_LEN => {
+                let index = read_u32(5)?;
+                let count = read_u32(9)?;
+
+                if index >= count || count > Self::MAX_FRAGMENTS {
+                    return Err(Error::InvalidPayload);
+                }
+
+                if self.completed.contains(&message_id) {
+                    return Ok((Frame::Incomplete, Some(acknowledgement(message_id))));
+                }
+
+                let payload = &packet[HEADER_LEN..];
+                let buffered = self.buffered + packet.len();
+
+                if buffered > Self::MAX_BUFFERED {
+                    return Err(Error::InvalidPayload);
+                }
+
+                let reassembly = self
+                    .incomplete
+                    .entry(message_id)
+                    .or_insert_with(|| Reassembly {
+                        count,
+                        fragments: BTreeMap::new(),
+                    });
+
+                if reassembly.count != count {
+                    return Err(Error::InvalidPayload);
+                }
+
+                self.buffered = buffered;
+
+                // A duplicate replaces the fragment it repeats
+                if let Some(previous) = reassembly.fragments.insert(index, payload.to_vec()) {
+                    self.buffered -= HEADER_LEN + previous.len();
+                }
+
+                if reassembly.fragments.len() as u64 != u64::from(count) {
+                    return Ok((Frame::Incomplete, None));
+                }
+
+                let data: Vec<u8> = self
+                    .incomplete
+                    .remove(&message_id)
+                    .into_iter()
+                    .flat_map(|reassembly| reassembly.fragments.into_iter())
+                    .flat_map(|(_, fragment)| fragment)
+                    .collect();
+
+                self.buffered -= count as usize * HEADER_LEN + data.len();
+
+                if self.completed.len() == RECENTLY_COMPLETED {
+                    self.completed.pop_front();
+                }
+                self.completed.push_back(message_id);
+
+                Ok((
+                    Frame::Message { message_id, data },
+                    Some(acknowledgement(message_id)),
+                ))
+            }
+
+            _ => Err(Error::InvalidPayload),
+        }
+    }
+
+    /// Whether a message sent through this `Framer` was acknowledged by the other end
+    pub fn is_delivered(&self, message_id: MessageID) -> bool {
+        // IDs are handed out in order, the ones behind the next ID were sent
+        let behind = self
+            .next_message_id
+            .wrapping_sub(message_id)
+            .wrapping_sub(1);
+
+        u64::from(behind) < self.sent && !self.unacknowledged.contains(&message_id)
+    }
+
+    /// Returns an `Iterator` over the IDs of sent messages that were not acknowledged yet
+    pub fn unacknowledged(&self) -> impl '_ + Iterator<Item = MessageID> {
+        self.unacknowledged.iter().copied()
+    }
+
+    /// Drops fragments of messages that are still incomplete, for example after the other end
+    /// disconnected
+    pub fn discard_incomplete(&mut self) {
+        self.incomplete.clear();
+        self.buffered = 0;
+    }
+}
+
+fn acknowledgement(message_id: MessageID) -> Vec<u8> {
+    let mut acknowledgement = Vec::with_capacity(ACKNOWLEDGEMENT_LEN);
+    acknowledgement.push(KIND_ACKNOWLEDGEMENT);
+    acknowledgement.extend_from_slice(&message_id.to_le_bytes());
+    acknowledgement
+}
+
+impl Default for Framer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # Framing
+///
+/// Sends messages of any size over the networking layers, see [`Framer`](struct.Framer.html).
+impl<E> Discord<'_, E> {
+    /// Sends `data` to a peer in as many messages as needed.
+    ///
+    /// The message is reported as delivered by
+    /// [`receive_framed_message`](#method.receive_framed_message) once the peer acknowledges it.
+    ///
+    /// ## Errors
+    ///
+    /// Fails like [`send_message`](#method.send_message), fragments that were already sent are
+    /// not recalled.
+    pub fn send_framed_message(
+        &self,
+        framer: &mut Framer,
+        peer_id: NetworkPeerID,
+        channel_id: NetworkChannelID,
+        data: &[u8],
+    ) -> Result<MessageID> {
+        let (message_id, packets) = framer.fragment(data)?;
+
+        for packet in packets {
+            self.send_message(peer_id, channel_id, packet)?;
+        }
+
+        Ok(message_id)
+    }
+
+    /// Handles data received from
+    /// [`EventHandler::on_network_message`](trait.EventHandler.html#method.on_network_message),
+    /// acknowledging complete messages to the peer.
+    pub fn receive_framed_message(
+        &self,
+        framer: &mut Framer,
+        peer_id: NetworkPeerID,
+        channel_id: NetworkChannelID,
+        packet: &[u8],
+    ) -> Result<Frame> {
+        let (frame, acknowledgement) = framer.reassemble(packet)?;
+
+        if let Some(acknowledgement) = acknowledgement {
+            self.send_message(peer_id, channel_id, acknowledgement)?;
+        }
+
+        Ok(frame)
+    }
+
+    /// Sends `data` to a lobby member in as many messages as needed.
+    ///
+    /// The message is reported as delivered by
+    /// [`receive_framed_lobby_network_message`](#method.receive_framed_lobby_network_message)
+    /// once the member acknowledges it.
+    ///
+    /// ## Errors
+    ///
+    /// Fails like [`send_lobby_network_message`](#method.send_lobby_network_message), fragments
+    /// that were already sent are not recalled.
+    pub fn send_framed_lobby_network_message(
+        &self,
+        framer: &mut Framer,
+        lobby_id: LobbyID,
+        user_id: UserID,
+        channel_id: NetworkChannelID,
+        data: &[u8],
+    ) -> Result<MessageID> {
+        let (message_id, packets) = framer.fragment(data)?;
+
+        for packet in packets {
+            self.send_lobby_network_message(lobby_id, user_id, channel_id, &packet)?;
+        }
+
+        Ok(message_id)
+    }
+
+    /// Handles data received from
+    /// [`EventHandler::on_lobby_network_message`](trait.EventHandler.html#method.on_lobby_network_message),
+    /// acknowledging complete messages to the member.
+    pub fn receive_framed_lobby_network_message(
+        &self,
+        framer: &mut Framer,
+        lobby_id: LobbyID,
+        member_id: UserID,
+        channel_id: NetworkChannelID,
+        packet: &[u8],
+    ) -> Result<Frame> {
+        let (frame, acknowledgement) = framer.reassemble(packet)?;
+
+        if let Some(acknowledgement) = acknowledgement {
+            self.send_lobby_network_message(lobby_id, member_id, channel_id, &acknowledgement)?;
+        }
+
+        Ok(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{fixtures::queued, MockConfig},
+        Event, Reliability,
+    };
+
+    #[test]
+    fn fragments_reassemble_in_any_order() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+        let mut sender = Framer::with_fragment_size(64);
+        let mut receiver = Framer::new();
+
+        let (message_id, mut packets) = sender.fragment(&data).unwrap();
+        assert_eq!(packets.len(), 16);
+        packets.reverse();
+
+        let last = packets.pop().unwrap();
+        for packet in &packets {
+            assert_eq!(
+                receiver.reassemble(packet).unwrap(),
+                (Frame::Incomplete, None)
+            );
+        }
+
+        let (frame, acknowledgement) = receiver.reassemble(&last).unwrap();
+        assert_eq!(frame, Frame::Message { message_id, data });
+
+        assert!(!sender.is_delivered(message_id));
+        assert_eq!(
+            sender.reassemble(&acknowledgement.unwrap()).unwrap(),
+            (Frame::Delivered { message_id }, None)
+        );
+        assert!(sender.is_delivered(message_id));
+        assert_eq!(sender.unacknowledged().count(), 0);
+    }
+
+    #[test]
+    fn malformed_packets_are_rejected() {
+        let mut framer = Framer::new();
+
+        assert_eq!(framer.reassemble(&[]), Err(Error::InvalidPayload));
+        assert_eq!(
+            framer.reassemble(&[7, 0, 0, 0, 0]),
+            Err(Error::InvalidPayload)
+        );
+        // index >= count
+        assert_eq!(
+            framer.reassemble(&[KIND_FRAGMENT, 0, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0]),
+            Err(Error::InvalidPayload)
+        );
+        // count > MAX_FRAGMENTS
+        assert_eq!(
+            framer.reassemble(&[KIND_FRAGMENT, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 2, 0]),
+            Err(Error::InvalidPayload)
+        );
+    }
+
+    #[test]
+    fn duplicates_and_oversized_messages_are_handled() {
+        let mut sender = Framer::with_fragment_size(4);
+        let mut receiver = Framer::new();
+
+        let (message_id, packets) = sender.fragment(b"hi").unwrap();
+        let (frame, acknowledgement) = receiver.reassemble(&packets[0]).unwrap();
+        assert_eq!(
+            frame,
+            Frame::Message {
+                message_id,
+                data: b"hi".to_vec()
+            }
+        );
+
+        // Acknowledged again, not delivered twice
+        assert_eq!(
+            receiver.reassemble(&packets[0]).unwrap(),
+            (Frame::Incomplete, acknowledgement)
+        );
+        assert_eq!(receiver.incomplete.len(), 0);
+
+        let (_, packets) = sender.fragment(&[0; 12]).unwrap();
+        receiver.reassemble(&packets[0]).unwrap();
+        receiver.reassemble(&packets[0]).unwrap();
+        assert_eq!(receiver.buffered, HEADER_LEN + 4);
+        receiver.reassemble(&packets[1]).unwrap();
+        receiver.reassemble(&packets[2]).unwrap();
+        assert_eq!(receiver.buffered, 0);
+
+        receiver.buffered = Framer::MAX_BUFFERED - 1;
+        let (_, packets) = sender.fragment(&[0; 8]).unwrap();
+        assert_eq!(receiver.reassemble(&packets[0]), Err(Error::InvalidPayload));
+    }
+
+    #[test]
+    fn delivery_survives_wrapping_ids() {
+        let mut framer = Framer::new();
+        framer.next_message_id = MessageID::MAX;
+
+        let (last, _) = framer.fragment(b"last").unwrap();
+        let (first, _) = framer.fragment(b"first").unwrap();
+        assert_eq!((last, first), (MessageID::MAX, 0));
+
+        framer.unacknowledged.clear();
+        assert!(framer.is_delivered(last));
+        assert!(framer.is_delivered(first));
+        assert!(!framer.is_delivered(1));
+        assert!(!framer.is_delivered(MessageID::MAX - 1));
+    }
+
+    #[test]
+    fn framed_messages_are_reassembled_and_acknowledged() {
+        let mut discord = queued(&MockConfig::new());
+        discord.run_callbacks().unwrap();
+        discord.drain_events().for_each(drop);
+
+        let peer_id = discord.peer_id();
+        discord.open_peer(peer_id, "mock-route-1").unwrap();
+        discord
+            .open_channel(peer_id, 0, Reliability::Reliable)
+            .unwrap();
+
+        let data: Vec<u8> = (0..=255).cycle().take(5000).collect();
+        let mut framer = Framer::new();
+        let message_id = discord
+            .send_framed_message(&mut framer, peer_id, 0, &data)
+            .unwrap();
+
+        let mut frames = Vec::new();
+        for _ in 0..2 {
+            discord.run_callbacks().unwrap();
+            let events: Vec<_> = discord.drain_events().collect();
+
+            for event in events {
+                if let Event::NetworkMessage {
+                    peer_id,
+                    channel_id,
+                    data,
+                } = event
+                {
+                    match discord
+                        .receive_framed_message(&mut framer, peer_id, channel_id, &data)
+                        .unwrap()
+                    {
+                        Frame::Incomplete => {}
+                        frame => frames.push(frame),
+                    }
+                }
+            }
+        }
+
+        assert_eq!(
+            frames,
+            vec![
+                Frame::Message { message_id, data },
+                Frame::Delivered { message_id },
+            ]
+        );
+        assert!(framer.is_delivered(message_id));
+    }
+}
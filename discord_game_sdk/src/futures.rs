@@ -0,0 +1,555 @@
+use crate::{
+    Action, Activity, Discord, FetchKind, ImageHandle, InputMode, Lobby, LobbyID,
+    LobbyMemberTransaction, LobbyTransaction, OAuth2Token, RequestReply, Result, SearchQuery,
+    Snowflake, User, UserID,
+};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+/// Value that will be provided by the SDK during a later call to
+/// [`Discord::run_callbacks`](struct.Discord.html#method.run_callbacks)
+///
+/// It does not borrow the [`Discord`](struct.Discord.html) instance it was created from, which
+/// leaves the instance available to `run_callbacks` while the future is pending.
+///
+/// Will resolve to `Err(TransactionAborted)` if the instance is dropped first.
+#[must_use = "futures do nothing unless polled"]
+pub struct DiscordFuture<T>(Rc<RefCell<State<T>>>);
+
+enum State<T> {
+    Pending(Option<Waker>),
+    Ready(T),
+    Taken,
+}
+
+struct Resolver<T>(Rc<RefCell<State<T>>>);
+
+impl<T> DiscordFuture<T> {
+    fn new() -> (Self, Resolver<T>) {
+        let state = Rc::new(RefCell::new(State::Pending(None)));
+
+        (Self(state.clone()), Resolver(state))
+    }
+
+    /// Whether the SDK has provided a value yet
+    pub fn is_ready(&self) -> bool {
+        matches!(*self.0.borrow(), State::Ready(_))
+    }
+}
+
+impl<T> Resolver<T> {
+    fn resolve(self, value: T) {
+        let previous = std::mem::replace(&mut *self.0.borrow_mut(), State::Ready(value));
+
+        if let State::Pending(Some(waker)) = previous {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Future for DiscordFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.0.borrow_mut();
+
+        match std::mem::replace(&mut *state, State::Taken) {
+            State::Ready(value) => Poll::Ready(value),
+            State::Pending(_) => {
+                *state = State::Pending(Some(cx.waker().clone()));
+                Poll::Pending
+            }
+            State::Taken => panic!("`DiscordFuture` polled after completion"),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for DiscordFuture<T> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("DiscordFuture")
+            .field("is_ready", &self.is_ready())
+            .finish()
+    }
+}
+
+/// Asynchronous methods of [`Discord`](struct.Discord.html), returning
+/// [`DiscordFuture`](struct.DiscordFuture.html)s instead of taking callbacks
+///
+/// Obtained through [`Discord::futures`](struct.Discord.html#method.futures).
+/// Each method has the same name and arguments as its callback-based counterpart, its
+/// documentation applies here too.
+///
+/// The futures are only woken from within `run_callbacks`, they should be polled by an executor
+/// running on the same thread, in between calls to `run_callbacks`:
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # use std::cell::RefCell;
+/// # async fn example(discord: &RefCell<Discord<'_, ()>>) -> Result<()> {
+/// let create = discord.borrow().futures().create_lobby(&LobbyTransaction::new());
+/// let lobby = create.await?;
+///
+/// let connect = discord.borrow().futures().connect_lobby_voice(lobby.id());
+/// connect.await?;
+///
+/// let update = discord
+///     .borrow()
+///     .futures()
+///     .update_activity(Activity::empty().with_state("In a lobby"));
+/// update.await?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct Futures<'a, 'd, E>(&'a Discord<'d, E>);
+
+impl<'d, E> Discord<'d, E> {
+    /// Provides methods returning [`DiscordFuture`](struct.DiscordFuture.html)s
+    ///
+    /// See [`Futures`](struct.Futures.html).
+    pub fn futures(&self) -> Futures<'_, 'd, E> {
+        Futures(self)
+    }
+}
+
+impl<E> Futures<'_, '_, E> {
+    /// See [`Discord::set_user_achievement`](struct.Discord.html#method.set_user_achievement)
+    pub fn set_user_achievement(
+        &self,
+        achievement_id: Snowflake,
+        percent_complete: u8,
+    ) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .set_user_achievement(achievement_id, percent_complete, move |_, res| {
+                resolver.resolve(res)
+            });
+
+        future
+    }
+
+    /// See [`Discord::fetch_user_achievements`](struct.Discord.html#method.fetch_user_achievements)
+    pub fn fetch_user_achievements(&self) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .fetch_user_achievements(move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::update_activity`](struct.Discord.html#method.update_activity)
+    pub fn update_activity(&self, activity: &Activity) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .update_activity(activity, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::clear_activity`](struct.Discord.html#method.clear_activity)
+    pub fn clear_activity(&self) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0.clear_activity(move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::send_request_reply`](struct.Discord.html#method.send_request_reply)
+    pub fn send_request_reply(
+        &self,
+        user_id: UserID,
+        reply: RequestReply,
+    ) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .send_request_reply(user_id, reply, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::send_invite`](struct.Discord.html#method.send_invite)
+    pub fn send_invite<'s>(
+        &self,
+        user_id: UserID,
+        action: Action,
+        content: impl Into<Cow<'s, str>>,
+    ) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0.send_invite(user_id, action, content, move |_, res| {
+            resolver.resolve(res)
+        });
+
+        future
+    }
+
+    /// See [`Discord::accept_invite`](struct.Discord.html#method.accept_invite)
+    pub fn accept_invite(&self, user_id: UserID) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .accept_invite(user_id, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::validate_or_exit`](struct.Discord.html#method.validate_or_exit)
+    pub fn validate_or_exit(&self) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0.validate_or_exit(move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::oauth2_token`](struct.Discord.html#method.oauth2_token)
+    pub fn oauth2_token(&self) -> DiscordFuture<Result<OAuth2Token>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .oauth2_token(move |_, res| resolver.resolve(res.map(OAuth2Token::to_owned)));
+
+        future
+    }
+
+    /// See [`Discord::app_ticket`](struct.Discord.html#method.app_ticket)
+    pub fn app_ticket(&self) -> DiscordFuture<Result<String>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .app_ticket(move |_, res| resolver.resolve(res.map(str::to_string)));
+
+        future
+    }
+
+    /// See [`Discord::fetch_image`](struct.Discord.html#method.fetch_image)
+    pub fn fetch_image(
+        &self,
+        handle: ImageHandle,
+        refresh: FetchKind,
+    ) -> DiscordFuture<Result<ImageHandle>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .fetch_image(handle, refresh, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::create_lobby`](struct.Discord.html#method.create_lobby)
+    pub fn create_lobby(&self, transaction: &LobbyTransaction) -> DiscordFuture<Result<Lobby>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0.create_lobby(transaction, move |_, res| {
+            resolver.resolve(res.map(Lobby::to_owned))
+        });
+
+        future
+    }
+
+    /// See [`Discord::update_lobby`](struct.Discord.html#method.update_lobby)
+    pub fn update_lobby(
+        &self,
+        lobby_id: LobbyID,
+        transaction: &LobbyTransaction,
+    ) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .update_lobby(lobby_id, transaction, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::delete_lobby`](struct.Discord.html#method.delete_lobby)
+    pub fn delete_lobby(&self, lobby_id: LobbyID) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .delete_lobby(lobby_id, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::connect_lobby`](struct.Discord.html#method.connect_lobby)
+    pub fn connect_lobby<'s>(
+        &self,
+        lobby_id: LobbyID,
+        secret: impl Into<Cow<'s, str>>,
+    ) -> DiscordFuture<Result<Lobby>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0.connect_lobby(lobby_id, secret, move |_, res| {
+            resolver.resolve(res.map(Lobby::to_owned))
+        });
+
+        future
+    }
+
+    /// See [`Discord::connect_lobby_with_activity_secret`](struct.Discord.html#method.connect_lobby_with_activity_secret)
+    pub fn connect_lobby_with_activity_secret<'s>(
+        &self,
+        activity_secret: impl Into<Cow<'s, str>>,
+    ) -> DiscordFuture<Result<Lobby>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .connect_lobby_with_activity_secret(activity_secret, move |_, res| {
+                resolver.resolve(res.map(Lobby::to_owned))
+            });
+
+        future
+    }
+
+    /// See [`Discord::disconnect_lobby`](struct.Discord.html#method.disconnect_lobby)
+    pub fn disconnect_lobby(&self, lobby_id: LobbyID) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .disconnect_lobby(lobby_id, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::update_member`](struct.Discord.html#method.update_member)
+    pub fn update_member(
+        &self,
+        lobby_id: LobbyID,
+        user_id: UserID,
+        transaction: &LobbyMemberTransaction,
+    ) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .update_member(lobby_id, user_id, transaction, move |_, res| {
+                resolver.resolve(res)
+            });
+
+        future
+    }
+
+    /// See [`Discord::send_lobby_message`](struct.Discord.html#method.send_lobby_message)
+    pub fn send_lobby_message(
+        &self,
+        lobby_id: LobbyID,
+        buffer: impl AsRef<[u8]>,
+    ) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .send_lobby_message(lobby_id, buffer, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::lobby_search`](struct.Discord.html#method.lobby_search)
+    pub fn lobby_search(&self, search: &SearchQuery) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .lobby_search(search, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::connect_lobby_voice`](struct.Discord.html#method.connect_lobby_voice)
+    pub fn connect_lobby_voice(&self, lobby_id: LobbyID) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .connect_lobby_voice(lobby_id, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::disconnect_lobby_voice`](struct.Discord.html#method.disconnect_lobby_voice)
+    pub fn disconnect_lobby_voice(&self, lobby_id: LobbyID) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .disconnect_lobby_voice(lobby_id, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::set_overlay_opened`](struct.Discord.html#method.set_overlay_opened)
+    pub fn set_overlay_opened(&self, opened: bool) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .set_overlay_opened(opened, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::open_invite_overlay`](struct.Discord.html#method.open_invite_overlay)
+    pub fn open_invite_overlay(&self, action: Action) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .open_invite_overlay(action, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::open_guild_invite_overlay`](struct.Discord.html#method.open_guild_invite_overlay)
+    pub fn open_guild_invite_overlay<'s>(
+        &self,
+        code: impl Into<Cow<'s, str>>,
+    ) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .open_guild_invite_overlay(code, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::open_voice_settings`](struct.Discord.html#method.open_voice_settings)
+    pub fn open_voice_settings(&self) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .open_voice_settings(move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::read_file_async`](struct.Discord.html#method.read_file_async)
+    pub fn read_file_async<'s>(
+        &self,
+        filename: impl Into<Cow<'s, str>>,
+    ) -> DiscordFuture<Result<Vec<u8>>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0.read_file_async(filename, move |_, res| {
+            resolver.resolve(res.map(<[u8]>::to_vec))
+        });
+
+        future
+    }
+
+    /// See [`Discord::read_file_async_partial`](struct.Discord.html#method.read_file_async_partial)
+    pub fn read_file_async_partial<'s>(
+        &self,
+        filename: impl Into<Cow<'s, str>>,
+        offset: u64,
+        length: u64,
+    ) -> DiscordFuture<Result<Vec<u8>>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .read_file_async_partial(filename, offset, length, move |_, res| {
+                resolver.resolve(res.map(<[u8]>::to_vec))
+            });
+
+        future
+    }
+
+    /// See [`Discord::write_file_async`](struct.Discord.html#method.write_file_async)
+    pub fn write_file_async<'s>(
+        &self,
+        filename: impl Into<Cow<'s, str>>,
+        buffer: impl AsRef<[u8]>,
+    ) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .write_file_async(filename, buffer, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::fetch_skus`](struct.Discord.html#method.fetch_skus)
+    pub fn fetch_skus(&self) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0.fetch_skus(move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::fetch_entitlements`](struct.Discord.html#method.fetch_entitlements)
+    pub fn fetch_entitlements(&self) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .fetch_entitlements(move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::start_purchase`](struct.Discord.html#method.start_purchase)
+    pub fn start_purchase(&self, sku_id: Snowflake) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .start_purchase(sku_id, move |_, res| resolver.resolve(res));
+
+        future
+    }
+
+    /// See [`Discord::user`](struct.Discord.html#method.user)
+    pub fn user(&self, user_id: UserID) -> DiscordFuture<Result<User>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0.user(user_id, move |_, res| {
+            resolver.resolve(res.map(User::to_owned))
+        });
+
+        future
+    }
+
+    /// See [`Discord::set_input_mode`](struct.Discord.html#method.set_input_mode)
+    pub fn set_input_mode(&self, input_mode: InputMode) -> DiscordFuture<Result<()>> {
+        let (future, resolver) = DiscordFuture::new();
+
+        self.0
+            .set_input_mode(input_mode, move |_, res| resolver.resolve(res));
+
+        future
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::task::{RawWaker, RawWakerVTable};
+
+    #[test]
+    fn resolve_during_run_callbacks() {
+        fn noop_raw_waker() -> RawWaker {
+            fn clone(_: *const ()) -> RawWaker {
+                noop_raw_waker()
+            }
+            fn noop(_: *const ()) {}
+
+            static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+
+        let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+        let mut cx = Context::from_waker(&waker);
+
+        let mut discord = Discord::<()>::mock();
+
+        let mut fetch = discord.futures().fetch_user_achievements();
+        assert!(!fetch.is_ready());
+        assert!(Pin::new(&mut fetch).poll(&mut cx).is_pending());
+
+        discord.run_callbacks().unwrap();
+
+        assert!(fetch.is_ready());
+        assert_eq!(Pin::new(&mut fetch).poll(&mut cx), Poll::Ready(Ok(())));
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{Error, UserID};
+
+/// Change of the authority over a lobby, see
+/// [`HostMigration::drain_changes`](struct.HostMigration.html#method.drain_changes)
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HostChange {
+    /// The owner left and every member elected the same successor, which is the authority from
+    /// now on even though the SDK may not show it as the owner yet
+    Elected {
+        /// New authority
+        owner_id: UserID,
+    },
+
+    /// The SDK shows a new owner for the lobby, either the elected one or one the previous owner
+    /// handed the lobby to
+    Transferred {
+        /// New owner
+        owner_id: UserID,
+    },
+
+    /// The current user was the owner according to the SDK and failed to hand the lobby over to
+    /// the elected member, this is retried on the next lobby update
+    TransferFailed {
+        /// Member the lobby was handed to
+        owner_id: UserID,
+        /// Error of `update_lobby`
+        error: Error,
+    },
+}
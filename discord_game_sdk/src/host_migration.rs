@@ -0,0 +1,329 @@
+use crate::{Discord, Error, Event, HostChange, LobbyID, LobbyTransaction, Result, UserID};
+use std::{
+    cell::RefCell,
+    cmp::Reverse,
+    collections::{vec_deque, VecDeque},
+    rc::Rc,
+};
+
+/// Elects and installs a new owner when the owner of a lobby leaves
+///
+/// Every member runs the same election over the remaining members when the owner's departure
+/// is applied: the highest number under the [priority key](#method.with_priority_key) of their
+/// metadata wins, members without one come last and ties go to the lowest user ID. The elected
+/// member is the authority right away, even though the SDK may have handed the lobby to someone
+/// else.
+///
+/// Only the member the SDK shows as the owner may call
+/// [`update_lobby`](struct.Discord.html#method.update_lobby), so that member hands the lobby to
+/// the elected one. When several members attempt it, the ones that are no longer the owner fail
+/// with `Error::InvalidPermissions`, which is ignored.
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # fn example(mut discord: Discord<'_, EventQueue>, lobby_id: LobbyID) -> Result<()> {
+/// let mut migration = HostMigration::new(&discord, lobby_id)?;
+/// migration.with_priority_key("bandwidth");
+///
+/// loop {
+///     discord.run_callbacks()?;
+///
+///     let events: Vec<_> = discord.drain_events().collect();
+///     for event in &events {
+///         migration.apply(&discord, event)?;
+///     }
+///
+///     for change in migration.drain_changes() {
+///         if let HostChange::Elected { owner_id } = change {
+///             // Simulate the game on `owner_id` from now on
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct HostMigration {
+    lobby_id: LobbyID,
+    current_user_id: UserID,
+    priority_key: Option<String>,
+    // The authority, which may not be the owner according to the SDK yet
+    owner_id: UserID,
+    sdk_owner_id: UserID,
+    pending: bool,
+    shared: Rc<RefCell<Shared>>,
+    changes: VecDeque<HostChange>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    transferring: bool,
+    failed: Option<(UserID, Error)>,
+}
+
+impl HostMigration {
+    /// Starts following the owner of a lobby the current user is connected to
+    ///
+    /// ## Errors
+    ///
+    /// Those of [`Discord::lobby`](struct.Discord.html#method.lobby) and
+    /// [`Discord::current_user`](struct.Discord.html#method.current_user).
+    pub fn new<E>(discord: &Discord<'_, E>, lobby_id: LobbyID) -> Result<Self> {
+        let owner_id = discord.lobby(lobby_id)?.owner_id();
+
+        Ok(Self {
+            lobby_id,
+            current_user_id: discord.current_user()?.id(),
+            priority_key: None,
+            owner_id,
+            sdk_owner_id: owner_id,
+            pending: false,
+            shared: Rc::new(RefCell::new(Shared::default())),
+            changes: VecDeque::new(),
+        })
+    }
+
+    /// The member metadata key holding the priority of members during elections
+    ///
+    /// Values are parsed as integers, members whose value is missing or invalid come last.
+    pub fn with_priority_key(&mut self, key: &str) -> &mut Self {
+        self.priority_key = Some(key.to_string());
+        self
+    }
+
+    /// The followed lobby
+    pub fn lobby_id(&self) -> LobbyID {
+        self.lobby_id
+    }
+
+    /// The authority over the lobby, the elected member while the transfer is pending
+    pub fn owner_id(&self) -> UserID {
+        self.owner_id
+    }
+
+    /// Whether the current user is the authority over the lobby
+    pub fn is_host(&self) -> bool {
+        self.owner_id == self.current_user_id
+    }
+
+    /// Whether the SDK does not show the elected member as the owner yet
+    pub fn is_pending(&self) -> bool {
+        self.pending
+    }
+
+    /// Follows the lobby's owner from a lobby event, other events are ignored
+    ///
+    /// ## Errors
+    ///
+    /// Those of the methods reading the lobby and its members.
+    pub fn apply<E>(&mut self, discord: &Discord<'_, E>, event: &Event) -> Result<()> {
+        match *event {
+            Event::MemberDisconnect {
+                lobby_id,
+                member_id,
+            } if lobby_id == self.lobby_id && member_id == self.owner_id => {
+                self.elect(discord, member_id)?;
+                self.reconcile(discord)
+            }
+
+            Event::LobbyUpdate { lobby_id } if lobby_id == self.lobby_id => self.reconcile(discord),
+
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes the changes since the last call, in the order they happened
+    pub fn drain_changes(&mut self) -> vec_deque::Drain<'_, HostChange> {
+        if let Some((owner_id, error)) = self.shared.borrow_mut().failed.take() {
+            self.changes
+                .push_back(HostChange::TransferFailed { owner_id, error });
+        }
+
+        self.changes.drain(..)
+    }
+
+    fn elect<E>(&mut self, discord: &Discord<'_, E>, departed: UserID) -> Result<()> {
+        let mut elected = None;
+
+        for member_id in discord.iter_lobby_member_ids(self.lobby_id)? {
+            let member_id = member_id?;
+
+            if member_id == departed {
+                continue;
+            }
+
+            let priority = self.priority_key.as_deref().and_then(|key| {
+                discord
+                    .lobby_member_metadata(self.lobby_id, member_id, key)
+                    .ok()
+                    .and_then(|value| value.parse::<i64>().ok())
+            });
+
+            let rank = (priority, Reverse(member_id));
+
+            elected = match elected {
+                Some(best) if best > rank => Some(best),
+                _ => Some(rank),
+            };
+        }
+
+        // None when nobody is left
+        if let Some((_, Reverse(owner_id))) = elected {
+            self.owner_id = owner_id;
+            self.pending = true;
+            self.changes.push_back(HostChange::Elected { owner_id });
+        }
+
+        Ok(())
+    }
+
+    fn reconcile<E>(&mut self, discord: &Discord<'_, E>) -> Result<()> {
+        let sdk_owner_id = discord.lobby(self.lobby_id)?.owner_id();
+        let changed = sdk_owner_id != self.sdk_owner_id;
+
+        self.sdk_owner_id = sdk_owner_id;
+
+        if !self.pending {
+            if !changed || sdk_owner_id == self.owner_id {
+                return Ok(());
+            }
+
+            let previous = self.owner_id;
+            let stayed = discord
+                .iter_lobby_member_ids(self.lobby_id)?
+                .any(|member_id| member_id == Ok(previous));
+
+            // Handed over on purpose, rather than because the owner left
+            if stayed {
+                self.owner_id = sdk_owner_id;
+                self.changes.push_back(HostChange::Transferred {
+                    owner_id: sdk_owner_id,
+                });
+                return Ok(());
+            }
+
+            self.elect(discord, previous)?;
+        }
+
+        if sdk_owner_id == self.owner_id {
+            self.pending = false;
+            self.changes.push_back(HostChange::Transferred {
+                owner_id: sdk_owner_id,
+            });
+        } else if sdk_owner_id == self.current_user_id {
+            self.transfer(discord);
+        }
+
+        Ok(())
+    }
+
+    fn transfer<E>(&mut self, discord: &Discord<'_, E>) {
+        if self.shared.borrow().transferring {
+            return;
+        }
+
+        self.shared.borrow_mut().transferring = true;
+
+        let owner_id = self.owner_id;
+        let shared = self.shared.clone();
+
+        discord.update_lobby(
+            self.lobby_id,
+            LobbyTransaction::new().owner(owner_id),
+            move |_, res| {
+                let mut shared = shared.borrow_mut();
+                shared.transferring = false;
+
+                match res {
+                    Ok(()) => {}
+                    // Someone else owns the lobby now and takes care of it
+                    Err(Error::InvalidPermissions) => {
+                        log::debug!("lobby was handed over by another member")
+                    }
+                    Err(error) => {
+                        log::warn!("failed to hand lobby over: {}", error);
+                        shared.failed = Some((owner_id, error));
+                    }
+                }
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{
+            fixtures::{create_lobby, queued, run, service_user},
+            MockService,
+        },
+        EventQueue, LobbyKind, LobbyMemberTransaction,
+    };
+
+    #[test]
+    fn elects_the_same_owner_everywhere() {
+        fn tick(
+            discord: &mut Discord<'_, EventQueue>,
+            migration: &mut HostMigration,
+        ) -> Vec<HostChange> {
+            discord.run_callbacks().unwrap();
+
+            let events: Vec<_> = discord.drain_events().collect();
+            for event in &events {
+                migration.apply(discord, event).unwrap();
+            }
+
+            migration.drain_changes().collect()
+        }
+
+        let service = MockService::new();
+        let mut host = Discord::<()>::mock_with(&service_user(&service, 1, "host"));
+        let lobby = create_lobby(&mut host, LobbyTransaction::new().kind(LobbyKind::Public));
+        let lobby_id = lobby.id();
+
+        let mut guests = Vec::new();
+        for &(user_id, priority) in &[(2, "5"), (3, "9")] {
+            let mut guest = queued(&service_user(&service, user_id, "guest"));
+
+            guest.connect_lobby(lobby_id, lobby.secret(), |_, res| assert!(res.is_ok()));
+            run(&mut guest);
+            guest.update_member(
+                lobby_id,
+                user_id,
+                LobbyMemberTransaction::new()
+                    .add_metadata("priority".to_string(), priority.to_string()),
+                |_, res| res.unwrap(),
+            );
+            run(&mut guest);
+
+            let mut migration = HostMigration::new(&guest, lobby_id).unwrap();
+            migration.with_priority_key("priority");
+            assert_eq!(migration.owner_id(), 1);
+
+            guests.push((guest, migration, Vec::new()));
+        }
+
+        // The SDK hands the lobby to the first member left, who hands it to the elected one
+        drop(host);
+
+        for _ in 0..4 {
+            for (guest, migration, changes) in &mut guests {
+                changes.extend(tick(guest, migration));
+            }
+        }
+
+        for (guest, migration, changes) in &guests {
+            assert_eq!(
+                *changes,
+                vec![
+                    HostChange::Elected { owner_id: 3 },
+                    HostChange::Transferred { owner_id: 3 }
+                ]
+            );
+            assert_eq!(guest.lobby(lobby_id).unwrap().owner_id(), 3);
+            assert!(!migration.is_pending());
+        }
+        assert!(!guests[0].1.is_host());
+        assert!(guests[1].1.is_host());
+    }
+}
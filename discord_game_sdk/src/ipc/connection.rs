@@ -0,0 +1,184 @@
+use super::json::Value;
+use std::{
+    convert::TryInto,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError},
+    thread,
+    time::Duration,
+};
+
+pub(crate) const HANDSHAKE: u32 = 0;
+pub(crate) const FRAME: u32 = 1;
+pub(crate) const CLOSE: u32 = 2;
+pub(crate) const PING: u32 = 3;
+pub(crate) const PONG: u32 = 4;
+
+/// Upper bound for a payload, the client never sends anything close to that
+const MAX_PAYLOAD_LEN: usize = 64 * 1024 * 1024;
+
+/// A socket to the Discord client, packets are read by a background thread
+pub(crate) struct Connection {
+    writer: Box<dyn Write>,
+    incoming: Receiver<(u32, Value)>,
+    // Unblocks the background thread
+    shutdown: Box<dyn Fn()>,
+}
+
+impl Connection {
+    /// Connects to the first socket the client listens on
+    pub(crate) fn discover() -> io::Result<Self> {
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, "no Discord IPC socket found");
+
+        for path in socket_paths() {
+            match Self::open(&path) {
+                Ok(connection) => {
+                    log::debug!("connected to {:?}", path);
+                    return Ok(connection);
+                }
+                Err(error) => last_error = error,
+            }
+        }
+
+        Err(last_error)
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn open(path: &Path) -> io::Result<Self> {
+        Self::from_stream(std::os::unix::net::UnixStream::connect(path)?)
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn from_stream(stream: std::os::unix::net::UnixStream) -> io::Result<Self> {
+        let reader = stream.try_clone()?;
+        let shutdown = stream.try_clone()?;
+
+        Ok(Self::new(Box::new(stream), reader, move || {
+            let _ = shutdown.shutdown(std::net::Shutdown::Both);
+        }))
+    }
+
+    // Synchronous named pipes block writes while a read is pending, which a background reader
+    // would always be doing
+    #[cfg(not(unix))]
+    pub(crate) fn open(_path: &Path) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "Discord IPC is only supported over Unix domain sockets",
+        ))
+    }
+
+    #[cfg_attr(not(unix), allow(dead_code))]
+    fn new(
+        writer: Box<dyn Write>,
+        mut reader: impl 'static + Read + Send,
+        shutdown: impl 'static + Fn(),
+    ) -> Self {
+        let (sender, incoming) = mpsc::channel();
+
+        thread::spawn(move || loop {
+            match read_packet(&mut reader) {
+                Ok(packet) => {
+                    if sender.send(packet).is_err() {
+                        break;
+                    }
+                }
+                Err(error) => {
+                    log::debug!("IPC connection closed: {}", error);
+                    break;
+                }
+            }
+        });
+
+        Self {
+            writer,
+            incoming,
+            shutdown: Box::new(shutdown),
+        }
+    }
+
+    pub(crate) fn send(&mut self, opcode: u32, payload: &Value) -> io::Result<()> {
+        let payload = payload.to_string();
+        let len: u32 = payload
+            .len()
+            .try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "payload too large"))?;
+
+        let mut packet = Vec::with_capacity(8 + payload.len());
+        packet.extend_from_slice(&opcode.to_le_bytes());
+        packet.extend_from_slice(&len.to_le_bytes());
+        packet.extend_from_slice(payload.as_bytes());
+
+        self.writer.write_all(&packet)?;
+        self.writer.flush()
+    }
+
+    /// Returns the next packet if one was received, `Err` once the connection is closed
+    pub(crate) fn try_recv(&self) -> Result<Option<(u32, Value)>, ()> {
+        match self.incoming.try_recv() {
+            Ok(packet) => Ok(Some(packet)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(()),
+        }
+    }
+
+    /// Waits for the next packet, `None` on timeout or if the connection is closed
+    pub(crate) fn recv_timeout(&self, timeout: Duration) -> Option<(u32, Value)> {
+        match self.incoming.recv_timeout(timeout) {
+            Ok(packet) => Some(packet),
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        (self.shutdown)()
+    }
+}
+
+fn read_packet(reader: &mut impl Read) -> io::Result<(u32, Value)> {
+    let mut header = [0; 8];
+    reader.read_exact(&mut header)?;
+
+    let opcode = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+
+    if len > MAX_PAYLOAD_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "payload too large",
+        ));
+    }
+
+    let mut payload = vec![0; len];
+    reader.read_exact(&mut payload)?;
+
+    let payload = std::str::from_utf8(&payload)
+        .ok()
+        .and_then(Value::parse)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed payload"))?;
+
+    Ok((opcode, payload))
+}
+
+/// Locations of the sockets, in the order the official library tries them
+fn socket_paths() -> Vec<PathBuf> {
+    let directory = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .find_map(std::env::var_os)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/tmp"));
+
+    // Sandboxed clients listen in a sub-directory
+    let directories = [
+        directory.clone(),
+        directory.join("app/com.discordapp.Discord"),
+        directory.join("snap.discord"),
+    ];
+
+    directories
+        .iter()
+        .flat_map(|directory| (0..10).map(move |i| directory.join(format!("discord-ipc-{}", i))))
+        .collect()
+}
@@ -0,0 +1,748 @@
+use super::{
+    connection::{self, Connection},
+    json::{object, Value},
+};
+use crate::{
+    backend::{self, Backend, Interface, LogHook, Managers},
+    sys,
+    tables::Pointer,
+    utils::charbuf_to_str,
+    Error, Result,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+    ffi::c_void,
+    time::Duration,
+};
+
+// SAFETY: `interface` must point to the `vtable` of an `Interface<T, Ipc>` owned by a live `Ipc`
+unsafe fn ipc<'a, T>(interface: *mut T) -> &'a Ipc {
+    backend::backend(interface)
+}
+
+type Callback = Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>;
+
+type Deferred = Box<dyn FnOnce(sys::EDiscordResult)>;
+
+struct Ipc {
+    core: Interface<sys::IDiscordCore, Ipc>,
+    managers: Managers<Ipc>,
+
+    params: sys::DiscordCreateParams,
+    log_hook: Cell<Option<(*mut c_void, LogHook)>>,
+    connection: RefCell<Option<Connection>>,
+    state: RefCell<State>,
+}
+
+#[derive(Default)]
+struct State {
+    next_nonce: u64,
+    current_user: Option<sys::DiscordUser>,
+    // Requests awaiting a response, by nonce
+    pending: HashMap<String, (*mut c_void, Callback)>,
+    // Responses that did not come from the client
+    deferred: VecDeque<(Deferred, sys::EDiscordResult)>,
+    // Packets received before the instance was created
+    received: VecDeque<Value>,
+}
+
+/// How long to wait for the client to accept the handshake
+pub(crate) const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// The client only sends activity events to subscribers
+const SUBSCRIPTIONS: &[&str] = &[
+    "ACTIVITY_JOIN",
+    "ACTIVITY_SPECTATE",
+    "ACTIVITY_JOIN_REQUEST",
+];
+
+impl Ipc {
+    fn log(&self, level: sys::EDiscordLogLevel, message: &str) {
+        if let Some((hook_data, hook)) = self.log_hook.get() {
+            let message = format!("{}\0", message);
+            unsafe { hook(hook_data, level, message.as_ptr()) }
+        }
+    }
+
+    fn send(&self, opcode: u32, payload: &Value) -> sys::EDiscordResult {
+        let mut connection = self.connection.borrow_mut();
+
+        let sent = match connection.as_mut() {
+            Some(connection) => connection.send(opcode, payload),
+            None => return sys::DiscordResult_NotRunning,
+        };
+
+        match sent {
+            Ok(()) => sys::DiscordResult_Ok,
+            Err(error) => {
+                log::warn!("could not write to the Discord client: {}", error);
+                *connection = None;
+                sys::DiscordResult_NotRunning
+            }
+        }
+    }
+
+    /// Sends a command, the callback is called when the client responds
+    fn command(&self, command: &str, args: Value, callback_data: *mut c_void, callback: Callback) {
+        let nonce = {
+            let mut state = self.state.borrow_mut();
+            state.next_nonce += 1;
+            state.next_nonce.to_string()
+        };
+
+        let payload = object(vec![
+            ("cmd", command.into()),
+            ("args", args),
+            ("nonce", nonce.clone().into()),
+        ]);
+
+        match self.send(connection::FRAME, &payload) {
+            sys::DiscordResult_Ok => {
+                self.state
+                    .borrow_mut()
+                    .pending
+                    .insert(nonce, (callback_data, callback));
+            }
+            res => self.respond(callback_data, callback, res),
+        }
+    }
+
+    /// Calls back with `res` during the next call to `run_callbacks`
+    fn respond(&self, callback_data: *mut c_void, callback: Callback, res: sys::EDiscordResult) {
+        self.defer(res, move |res| unsafe {
+            callback.unwrap()(callback_data, res)
+        })
+    }
+
+    /// Calls `callback` with `res` during the next call to `run_callbacks`, or with
+    /// `TransactionAborted` if the instance is destroyed first
+    fn defer(
+        &self,
+        res: sys::EDiscordResult,
+        callback: impl 'static + FnOnce(sys::EDiscordResult),
+    ) {
+        self.state
+            .borrow_mut()
+            .deferred
+            .push_back((Box::new(callback), res));
+    }
+
+    unsafe fn tick(&self) -> sys::EDiscordResult {
+        let (deferred, received) = {
+            let mut state = self.state.borrow_mut();
+            (
+                std::mem::take(&mut state.deferred),
+                std::mem::take(&mut state.received),
+            )
+        };
+
+        for (callback, res) in deferred {
+            callback(res);
+        }
+
+        for payload in received {
+            self.handle(&payload);
+        }
+
+        loop {
+            let packet = match self.connection.borrow().as_ref().map(Connection::try_recv) {
+                Some(Ok(Some(packet))) => packet,
+                Some(Ok(None)) => return sys::DiscordResult_Ok,
+                Some(Err(())) | None => break,
+            };
+
+            match packet {
+                (connection::FRAME, payload) => self.handle(&payload),
+                (connection::PING, payload) => {
+                    self.send(connection::PONG, &payload);
+                }
+                (connection::CLOSE, payload) => {
+                    log::warn!("Discord client closed the connection: {}", payload);
+                    break;
+                }
+                (opcode, _) => log::debug!("ignoring IPC packet with opcode {}", opcode),
+            }
+        }
+
+        self.disconnect();
+        sys::DiscordResult_NotRunning
+    }
+
+    unsafe fn handle(&self, payload: &Value) {
+        let data = payload.get("data");
+
+        if payload.get("cmd").as_str() == Some("DISPATCH") {
+            return self.dispatch(payload.get("evt").as_str().unwrap_or(""), data);
+        }
+
+        let nonce = payload.get("nonce").as_str().unwrap_or("");
+        let pending = self.state.borrow_mut().pending.remove(nonce);
+
+        if let Some((callback_data, callback)) = pending {
+            let res = if payload.get("evt").as_str() == Some("ERROR") {
+                let message = data.get("message").as_str().unwrap_or("");
+                self.log(sys::DiscordLogLevel_Error, message);
+
+                match data.get("code").as_f64() {
+                    Some(code) if code as i64 == 4000 => sys::DiscordResult_InvalidPayload,
+                    _ => sys::DiscordResult_InternalError,
+                }
+            } else {
+                sys::DiscordResult_Ok
+            };
+
+            callback.unwrap()(callback_data, res);
+        }
+    }
+
+    unsafe fn dispatch(&self, event: &str, data: &Value) {
+        let events = &self.params;
+
+        match event {
+            "READY" => {
+                self.state.borrow_mut().current_user = Some(user_from_json(data.get("user")));
+                self.log(sys::DiscordLogLevel_Info, "connected to Discord client");
+
+                for subscription in SUBSCRIPTIONS {
+                    self.send(
+                        connection::FRAME,
+                        &object(vec![
+                            ("cmd", "SUBSCRIBE".into()),
+                            ("evt", (*subscription).into()),
+                            ("nonce", format!("subscribe-{}", subscription).into()),
+                        ]),
+                    );
+                }
+
+                (*events.user_events).on_current_user_update.unwrap()(events.event_data)
+            }
+
+            "ACTIVITY_JOIN" | "ACTIVITY_SPECTATE" => {
+                let secret = format!("{}\0", data.get("secret").as_str().unwrap_or(""));
+
+                let handler = if event == "ACTIVITY_JOIN" {
+                    (*events.activity_events).on_activity_join
+                } else {
+                    (*events.activity_events).on_activity_spectate
+                };
+
+                handler.unwrap()(events.event_data, secret.as_ptr())
+            }
+
+            "ACTIVITY_JOIN_REQUEST" => {
+                let mut user = user_from_json(data.get("user"));
+
+                (*events.activity_events).on_activity_join_request.unwrap()(
+                    events.event_data,
+                    &mut user,
+                )
+            }
+
+            _ => log::debug!("ignoring IPC event {:?}", event),
+        }
+    }
+
+    unsafe fn disconnect(&self) {
+        *self.connection.borrow_mut() = None;
+
+        let pending = std::mem::take(&mut self.state.borrow_mut().pending);
+
+        for (_, (callback_data, callback)) in pending {
+            callback.unwrap()(callback_data, sys::DiscordResult_NotRunning);
+        }
+    }
+
+    unsafe fn abort_all(&self) {
+        let (pending, deferred) = {
+            let mut state = self.state.borrow_mut();
+            (
+                std::mem::take(&mut state.pending),
+                std::mem::take(&mut state.deferred),
+            )
+        };
+
+        for (_, (callback_data, callback)) in pending {
+            callback.unwrap()(callback_data, sys::DiscordResult_TransactionAborted);
+        }
+
+        for (callback, _) in deferred {
+            callback(sys::DiscordResult_TransactionAborted);
+        }
+    }
+}
+
+// Truncates on a character boundary, keeping room for the nul byte
+fn write_charbuf(charbuf: &mut [u8], value: &str) {
+    let mut len = value.len().min(charbuf.len() - 1);
+
+    while !value.is_char_boundary(len) {
+        len -= 1;
+    }
+
+    charbuf[..len].copy_from_slice(&value.as_bytes()[..len]);
+    charbuf[len] = 0;
+}
+
+fn user_from_json(json: &Value) -> sys::DiscordUser {
+    let mut user = sys::DiscordUser::default();
+    let string = |key| json.get(key).as_str().unwrap_or("");
+
+    // Snowflakes are sent as strings
+    user.id = string("id").parse().unwrap_or(0);
+    user.bot = json.get("bot") == &Value::Bool(true);
+    write_charbuf(&mut user.username, string("username"));
+    write_charbuf(&mut user.discriminator, string("discriminator"));
+    write_charbuf(&mut user.avatar, string("avatar"));
+
+    user
+}
+
+fn activity_to_json(activity: &sys::DiscordActivity) -> Value {
+    // The client rejects empty strings, they are left out instead
+    fn strings(fields: &[(&str, &[u8])]) -> Value {
+        Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key, charbuf_to_str(value)))
+                .filter(|(_, value)| !value.is_empty())
+                .map(|(key, value)| (key.to_string(), value.into()))
+                .collect(),
+        )
+    }
+
+    let mut fields = match strings(&[("state", &activity.state), ("details", &activity.details)]) {
+        Value::Object(fields) => fields,
+        _ => unreachable!(),
+    };
+
+    let timestamps = &activity.timestamps;
+    let mut timestamp_fields = Vec::new();
+    if timestamps.start != 0 {
+        timestamp_fields.push(("start".to_string(), timestamps.start.into()));
+    }
+    if timestamps.end != 0 {
+        timestamp_fields.push(("end".to_string(), timestamps.end.into()));
+    }
+
+    let assets = &activity.assets;
+    let secrets = &activity.secrets;
+    let party = &activity.party;
+
+    let mut party_fields = match strings(&[("id", &party.id)]) {
+        Value::Object(fields) => fields,
+        _ => unreachable!(),
+    };
+    if party.size.max_size > 0 {
+        party_fields.push((
+            "size".to_string(),
+            Value::Array(vec![
+                i64::from(party.size.current_size).into(),
+                i64::from(party.size.max_size).into(),
+            ]),
+        ));
+    }
+
+    let nested = vec![
+        ("timestamps", Value::Object(timestamp_fields)),
+        (
+            "assets",
+            strings(&[
+                ("large_image", &assets.large_image),
+                ("large_text", &assets.large_text),
+                ("small_image", &assets.small_image),
+                ("small_text", &assets.small_text),
+            ]),
+        ),
+        ("party", Value::Object(party_fields)),
+        (
+            "secrets",
+            strings(&[
+                ("match", &secrets.match_),
+                ("join", &secrets.join),
+                ("spectate", &secrets.spectate),
+            ]),
+        ),
+    ];
+
+    for (key, value) in nested {
+        if value != Value::Object(Vec::new()) {
+            fields.push((key.to_string(), value));
+        }
+    }
+
+    fields.push(("instance".to_string(), activity.instance.into()));
+
+    Value::Object(fields)
+}
+
+impl Backend for Ipc {
+    fn managers(&self) -> &Managers<Self> {
+        &self.managers
+    }
+
+    unsafe fn destroy(ipc: *mut Self) {
+        (*ipc).abort_all();
+
+        drop(Box::from_raw(ipc));
+    }
+
+    unsafe fn run_callbacks(&self) -> sys::EDiscordResult {
+        self.tick()
+    }
+
+    unsafe fn set_log_hook(
+        &self,
+        _min_level: sys::EDiscordLogLevel,
+        hook: Option<(*mut c_void, LogHook)>,
+    ) {
+        self.log_hook.set(hook);
+    }
+}
+
+const ACTIVITIES: sys::IDiscordActivityManager = sys::IDiscordActivityManager {
+    // Registration is done by the official library itself, not the client
+    register_command: {
+        unsafe extern "C" fn register_command(
+            manager: *mut sys::IDiscordActivityManager,
+            _command: *const u8,
+        ) -> sys::EDiscordResult {
+            ipc(manager).log(
+                sys::DiscordLogLevel_Warn,
+                "launch commands cannot be registered over IPC",
+            );
+            sys::DiscordResult_InvalidCommand
+        }
+
+        Some(register_command)
+    },
+
+    register_steam: {
+        unsafe extern "C" fn register_steam(
+            manager: *mut sys::IDiscordActivityManager,
+            _steam_id: u32,
+        ) -> sys::EDiscordResult {
+            ipc(manager).log(
+                sys::DiscordLogLevel_Warn,
+                "Steam games cannot be registered over IPC",
+            );
+            sys::DiscordResult_InvalidCommand
+        }
+
+        Some(register_steam)
+    },
+
+    update_activity: {
+        unsafe extern "C" fn update_activity(
+            manager: *mut sys::IDiscordActivityManager,
+            activity: *mut sys::DiscordActivity,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            let args = object(vec![
+                ("pid", i64::from(std::process::id()).into()),
+                ("activity", activity_to_json(&*activity)),
+            ]);
+
+            ipc(manager).command("SET_ACTIVITY", args, callback_data, callback)
+        }
+
+        Some(update_activity)
+    },
+
+    clear_activity: {
+        unsafe extern "C" fn clear_activity(
+            manager: *mut sys::IDiscordActivityManager,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            let args = object(vec![("pid", i64::from(std::process::id()).into())]);
+
+            ipc(manager).command("SET_ACTIVITY", args, callback_data, callback)
+        }
+
+        Some(clear_activity)
+    },
+
+    send_request_reply: {
+        unsafe extern "C" fn send_request_reply(
+            manager: *mut sys::IDiscordActivityManager,
+            user_id: sys::DiscordUserId,
+            reply: sys::EDiscordActivityJoinRequestReply,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            let command = if reply == sys::DiscordActivityJoinRequestReply_Yes {
+                "SEND_ACTIVITY_JOIN_INVITE"
+            } else {
+                "CLOSE_ACTIVITY_REQUEST"
+            };
+
+            let args = object(vec![("user_id", user_id.to_string().into())]);
+
+            ipc(manager).command(command, args, callback_data, callback)
+        }
+
+        Some(send_request_reply)
+    },
+
+    send_invite: {
+        unsafe extern "C" fn send_invite(
+            manager: *mut sys::IDiscordActivityManager,
+            _user_id: sys::DiscordUserId,
+            _type: sys::EDiscordActivityActionType,
+            _content: *const u8,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            ipc(manager).respond(callback_data, callback, sys::DiscordResult_InvalidCommand)
+        }
+
+        Some(send_invite)
+    },
+
+    accept_invite: {
+        unsafe extern "C" fn accept_invite(
+            manager: *mut sys::IDiscordActivityManager,
+            _user_id: sys::DiscordUserId,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            ipc(manager).respond(callback_data, callback, sys::DiscordResult_InvalidCommand)
+        }
+
+        Some(accept_invite)
+    },
+};
+
+const USERS: sys::IDiscordUserManager = sys::IDiscordUserManager {
+    get_current_user: {
+        unsafe extern "C" fn get_current_user(
+            manager: *mut sys::IDiscordUserManager,
+            current_user: *mut sys::DiscordUser,
+        ) -> sys::EDiscordResult {
+            match ipc(manager).state.borrow().current_user {
+                Some(user) => {
+                    *current_user = user;
+                    sys::DiscordResult_Ok
+                }
+                // Not available until `on_current_user_update` has fired
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(get_current_user)
+    },
+
+    get_user: <sys::IDiscordUserManager as Unsupported>::UNSUPPORTED.get_user,
+    get_current_user_premium_type: <sys::IDiscordUserManager as Unsupported>::UNSUPPORTED
+        .get_current_user_premium_type,
+    current_user_has_flag: <sys::IDiscordUserManager as Unsupported>::UNSUPPORTED
+        .current_user_has_flag,
+};
+
+/// Table of a manager the IPC protocol does not provide
+trait Unsupported {
+    const UNSUPPORTED: Self;
+}
+
+/// What a function returns when it is not available
+trait Unavailable {
+    fn unavailable() -> Self;
+}
+
+impl Unavailable for sys::EDiscordResult {
+    fn unavailable() -> Self {
+        sys::DiscordResult_InvalidCommand
+    }
+}
+
+impl Unavailable for () {
+    fn unavailable() -> Self {}
+}
+
+// Callbacks of unavailable functions get nothing to read
+macro_rules! blank_arg {
+    (val $arg:ident: $ty:ty) => {
+        let $arg: $ty = std::mem::zeroed();
+    };
+    (ptr $arg:ident: $ty:ty) => {
+        let mut $arg = <<$ty as Pointer>::Target as Default>::default();
+        let $arg: $ty = &mut $arg;
+    };
+    (str $arg:ident: $ty:ty) => {
+        let $arg: $ty = b"\0".as_ptr();
+    };
+    (buf $arg:ident [$len:ident]: $ty:ty) => {
+        let $arg: $ty = std::ptr::null_mut();
+    };
+    (len $arg:ident: $ty:ty) => {
+        let $arg: $ty = 0;
+    };
+}
+
+macro_rules! unsupported_methods {
+    ($(
+        $field:ident $iface:ident {
+            $(
+                fn $name:ident (
+                    $( $kind:ident $arg:ident $([$len:ident])? : $ty:ty ),*
+                )
+                $( => $callback:ident (
+                    $( $ckind:ident $carg:ident $([$clen:ident])? : $cty:ty ),*
+                ) )?
+                $( -> $ret:ty )?;
+            )*
+        } manual { $( $manual:ident ),* }
+    )*) => {
+        $(
+            impl Unsupported for sys::$iface {
+                const UNSUPPORTED: Self = sys::$iface {
+                    $(
+                        $name: {
+                            #[allow(unused_variables)]
+                            unsafe extern "C" fn $name(
+                                interface: *mut sys::$iface,
+                                $( $arg: $ty, )*
+                                $(
+                                    callback_data: *mut c_void,
+                                    $callback: Option<
+                                        unsafe extern "C" fn(
+                                            *mut c_void,
+                                            sys::EDiscordResult
+                                            $(, $cty)*
+                                        )
+                                    >,
+                                )?
+                            ) -> ret!($($ret)?) {
+                                let ipc = ipc(interface);
+
+                                ipc.log(
+                                    sys::DiscordLogLevel_Warn,
+                                    concat!(
+                                        stringify!($field),
+                                        ".",
+                                        stringify!($name),
+                                        " is not available over IPC"
+                                    ),
+                                );
+
+                                $(
+                                    ipc.defer(sys::DiscordResult_InvalidCommand, move |res| {
+                                        $( blank_arg!($ckind $carg $([$clen])?: $cty); )*
+                                        $callback.unwrap()(callback_data, res $(, $carg)*)
+                                    });
+                                )?
+
+                                <ret!($($ret)?) as Unavailable>::unavailable()
+                            }
+
+                            Some($name)
+                        },
+                    )*
+                    $( $manual: Some(manual::$manual), )*
+                };
+            }
+        )*
+    };
+}
+
+sdk_methods!(unsupported_methods);
+
+mod manual {
+    use super::*;
+
+    // There are no relationships to go through
+    pub(super) unsafe extern "C" fn filter(
+        interface: *mut sys::IDiscordRelationshipManager,
+        _filter_data: *mut c_void,
+        _filter: Option<unsafe extern "C" fn(*mut c_void, *mut sys::DiscordRelationship) -> bool>,
+    ) {
+        ipc(interface).log(
+            sys::DiscordLogLevel_Warn,
+            "relationships.filter is not available over IPC",
+        );
+    }
+}
+
+fn unsupported<T: Unsupported>() -> Interface<T, Ipc> {
+    Interface::new(T::UNSUPPORTED)
+}
+
+/// Connects and performs the handshake, `socket` is discovered when `None`
+pub(crate) unsafe fn create_ipc(
+    params: sys::DiscordCreateParams,
+    socket: Option<&std::path::Path>,
+) -> Result<*mut sys::IDiscordCore> {
+    let connection = match socket {
+        Some(path) => Connection::open(path),
+        None => Connection::discover(),
+    };
+
+    let mut connection = connection.map_err(|error| {
+        log::error!("could not connect to the Discord client: {}", error);
+        Error::NotRunning
+    })?;
+
+    let handshake = object(vec![
+        ("v", 1_i64.into()),
+        ("client_id", params.client_id.to_string().into()),
+    ]);
+
+    connection
+        .send(connection::HANDSHAKE, &handshake)
+        .map_err(|error| {
+            log::error!("could not send handshake: {}", error);
+            Error::NotRunning
+        })?;
+
+    // The client answers with a READY event, or closes the connection with a reason
+    let ready = match connection.recv_timeout(HANDSHAKE_TIMEOUT) {
+        Some((connection::FRAME, ready))
+            if ready.get("evt").as_str() == Some("READY") && !ready.get("data").is_null() =>
+        {
+            ready
+        }
+        Some((_, payload)) => {
+            log::error!("Discord client refused the handshake: {}", payload);
+            return Err(Error::ServiceUnavailable);
+        }
+        None => {
+            log::error!("Discord client did not answer the handshake");
+            return Err(Error::ServiceUnavailable);
+        }
+    };
+
+    let ipc = Box::into_raw(Box::new(Ipc {
+        core: backend::core(),
+        managers: Managers {
+            activities: Interface::new(ACTIVITIES),
+            users: Interface::new(USERS),
+
+            // Not provided by the IPC protocol, every function fails with `InvalidCommand`
+            achievements: unsupported(),
+            applications: unsupported(),
+            images: unsupported(),
+            lobbies: unsupported(),
+            networking: unsupported(),
+            overlay: unsupported(),
+            relationships: unsupported(),
+            storage: unsupported(),
+            store: unsupported(),
+            voice: unsupported(),
+        },
+
+        params,
+        log_hook: Cell::new(None),
+        connection: RefCell::new(Some(connection)),
+        state: RefCell::new(State::default()),
+    }));
+
+    (*ipc).core.backend = ipc;
+    (*ipc).managers.attach(ipc);
+
+    // Handled like any other event, during the first call to `run_callbacks`
+    (*ipc).state.borrow_mut().received.push_back(ready);
+
+    Ok((*ipc).core.as_ptr())
+}
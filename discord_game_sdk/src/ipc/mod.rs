@@ -0,0 +1,328 @@
+use crate::{
+    discord::{Discord, DiscordInner},
+    events, ClientID, CreateFlags, EventHandler, LogLevel, Result,
+};
+use std::{
+    cell::{Cell, UnsafeCell},
+    marker::PhantomData,
+    path::Path,
+};
+
+mod connection;
+mod ffi;
+mod json;
+
+/// # IPC
+///
+/// An instance can talk to the Discord client directly, over the same local socket the native
+/// library uses, which allows Rich Presence on platforms the library does not support.
+///
+/// Only activities are available: activity methods, `current_user`, activity events and
+/// `on_current_user_update`. Launch commands cannot be registered and invitations can neither
+/// be sent nor accepted. These, and every other method, fail with `Error::InvalidCommand`, or
+/// return nothing when they cannot fail.
+///
+/// Requires the `ipc` feature.
+impl<E> Discord<'_, E> {
+    /// Connects to the running Discord client and creates an instance backed by it.
+    ///
+    /// The current user becomes available after the first call to
+    /// [`run_callbacks`](#method.run_callbacks), as with the native library.
+    ///
+    /// ## Errors
+    ///
+    /// [`Error::NotRunning`](enum.Error.html#variant.NotRunning) if the client could not be
+    /// reached, [`Error::ServiceUnavailable`](enum.Error.html#variant.ServiceUnavailable) if it
+    /// refused or did not answer the handshake. The reason is logged.
+    ///
+    /// ```rust,no_run
+    /// # use discord_game_sdk::*;
+    /// # const DISCORD_CLIENT_ID: ClientID = 0;
+    /// # fn example() -> Result<()> {
+    /// let mut discord = Discord::<()>::with_ipc(DISCORD_CLIENT_ID)?;
+    ///
+    /// discord.update_activity(Activity::empty().with_state("In the menus"), |_, res| {
+    ///     if let Err(error) = res {
+    ///         eprintln!("failed to update activity: {}", error);
+    ///     }
+    /// });
+    ///
+    /// loop {
+    ///     discord.run_callbacks()?;
+    /// }
+    /// # }
+    /// ```
+    pub fn with_ipc(client_id: ClientID) -> Result<Self>
+    where
+        E: EventHandler,
+    {
+        Self::connect(client_id, None)
+    }
+
+    /// Same as [`with_ipc`](#method.with_ipc), connecting to the socket at `path` instead of
+    /// looking for it in the usual locations.
+    pub fn with_ipc_socket(path: impl AsRef<Path>, client_id: ClientID) -> Result<Self>
+    where
+        E: EventHandler,
+    {
+        Self::connect(client_id, Some(path.as_ref()))
+    }
+
+    fn connect(client_id: ClientID, socket: Option<&Path>) -> Result<Self>
+    where
+        E: EventHandler,
+    {
+        log::debug!("connecting over IPC with client ID {}", client_id);
+
+        let mut instance = Discord(Box::into_raw(Box::new(DiscordInner {
+            _invariant_lifetime: PhantomData,
+
+            core: std::ptr::null_mut(),
+            client_id,
+            event_handler: UnsafeCell::new(None),
+            log_level: Cell::new(LogLevel::Debug),
+            log_sink: UnsafeCell::new(None),
+            log_sink_generation: Cell::new(0),
+
+            #[cfg(feature = "dynamic")]
+            library: None,
+
+            achievement_events: events::achievement::<E>(),
+            activity_events: events::activity::<E>(),
+            lobby_events: events::lobby::<E>(),
+            network_events: events::network::<E>(),
+            overlay_events: events::overlay::<E>(),
+            relationship_events: events::relationship::<E>(),
+            store_events: events::store::<E>(),
+            user_events: events::user::<E>(),
+            voice_events: events::voice::<E>(),
+        })));
+
+        let params = instance.create_params(CreateFlags::Default.into());
+
+        instance.inner_mut().core = unsafe { ffi::create_ipc(params, socket)? };
+
+        instance.set_log_hook();
+        instance.kickstart_managers(crate::Managers::all());
+
+        Ok(instance)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{
+        connection::{self, Connection},
+        json::{object, Value},
+    };
+    use crate::{Activity, Discord, Error, Event, EventQueue};
+    use std::{cell::RefCell, os::unix::net::UnixListener, path::PathBuf, rc::Rc, thread};
+
+    fn socket_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn dispatch(event: &str, data: Value) -> Value {
+        object(vec![
+            ("cmd", "DISPATCH".into()),
+            ("evt", event.into()),
+            ("data", data),
+        ])
+    }
+
+    /// Accepts one connection and plays the part of the Discord client
+    fn serve(listener: UnixListener, handshake: Option<Value>) -> thread::JoinHandle<Vec<Value>> {
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut client = Connection::from_stream(stream).unwrap();
+            let mut commands = Vec::new();
+
+            let (opcode, hello) = client.recv_timeout(super::ffi::HANDSHAKE_TIMEOUT).unwrap();
+            assert_eq!(opcode, connection::HANDSHAKE);
+            assert_eq!(hello.get("client_id").as_str(), Some("42"));
+
+            let ready = handshake.unwrap_or_else(|| {
+                dispatch(
+                    "READY",
+                    object(vec![(
+                        "user",
+                        object(vec![
+                            ("id", "53908232506183680".into()),
+                            ("username", "Mason".into()),
+                            ("discriminator", "1337".into()),
+                        ]),
+                    )]),
+                )
+            });
+
+            if client.send(connection::FRAME, &ready).is_err() {
+                return commands;
+            }
+
+            while let Some((connection::FRAME, command)) =
+                client.recv_timeout(super::ffi::HANDSHAKE_TIMEOUT)
+            {
+                let response = match command.get("cmd").as_str() {
+                    Some("SET_ACTIVITY") if command.get("args").get("activity").is_null() => {
+                        object(vec![
+                            ("cmd", "SET_ACTIVITY".into()),
+                            ("evt", "ERROR".into()),
+                            (
+                                "data",
+                                object(vec![
+                                    ("code", 4000_i64.into()),
+                                    ("message", "nothing to clear".into()),
+                                ]),
+                            ),
+                            ("nonce", command.get("nonce").clone()),
+                        ])
+                    }
+                    Some(cmd) => object(vec![
+                        ("cmd", cmd.into()),
+                        ("data", Value::Null),
+                        ("nonce", command.get("nonce").clone()),
+                    ]),
+                    None => break,
+                };
+
+                let is_set_activity = command.get("cmd").as_str() == Some("SET_ACTIVITY");
+                commands.push(command);
+
+                // The instance may be dropped as soon as it has its responses
+                if client.send(connection::FRAME, &response).is_err() {
+                    break;
+                }
+
+                if is_set_activity {
+                    let join = dispatch(
+                        "ACTIVITY_JOIN",
+                        object(vec![("secret", "join-secret".into())]),
+                    );
+
+                    if client.send(connection::FRAME, &join).is_err() {
+                        break;
+                    }
+                }
+            }
+
+            commands
+        })
+    }
+
+    #[test]
+    fn activities_are_set_over_ipc() {
+        let path = socket_path("discord-ipc-test");
+        let server = serve(UnixListener::bind(&path).unwrap(), None);
+
+        let mut discord = Discord::<EventQueue>::with_ipc_socket(&path, 42).unwrap();
+        *discord.event_handler_mut() = Some(EventQueue::default());
+
+        assert_eq!(discord.current_user().unwrap_err(), Error::NotFound);
+
+        discord.run_callbacks().unwrap();
+
+        let user = discord.current_user().unwrap();
+        assert_eq!(user.id(), 53_908_232_506_183_680);
+        assert_eq!(user.username(), "Mason");
+        assert_eq!(user.discriminator(), "1337");
+
+        assert_eq!(
+            discord.current_user_premium_kind(),
+            Err(Error::InvalidCommand)
+        );
+        assert_eq!(
+            discord.register_launch_command("game --join"),
+            Err(Error::InvalidCommand)
+        );
+        assert_eq!(discord.iter_lobbies().count(), 0);
+
+        let results = Rc::new(RefCell::new(Vec::new()));
+
+        let r = results.clone();
+        discord.update_activity(
+            Activity::empty()
+                .with_state("In a match")
+                .with_party_id("party")
+                .with_party_amount(2)
+                .with_party_capacity(4)
+                .with_join_secret("join-secret"),
+            move |_, res| r.borrow_mut().push(res),
+        );
+
+        let r = results.clone();
+        discord.clear_activity(move |_, res| r.borrow_mut().push(res));
+
+        let r = results.clone();
+        discord.accept_invite(1, move |_, res| r.borrow_mut().push(res));
+
+        let r = results.clone();
+        discord.user(1, move |_, res| r.borrow_mut().push(res.map(|_| ())));
+
+        while results.borrow().len() < 4 {
+            discord.run_callbacks().unwrap();
+        }
+
+        assert_eq!(
+            *results.borrow(),
+            vec![
+                Err(Error::InvalidCommand),
+                Err(Error::InvalidCommand),
+                Ok(()),
+                Err(Error::InvalidPayload)
+            ]
+        );
+
+        let events: Vec<_> = discord.drain_events().collect();
+        assert_eq!(events[0], Event::CurrentUserUpdate);
+        assert!(events.contains(&Event::ActivityJoin {
+            secret: "join-secret".to_string()
+        }));
+
+        drop(discord);
+
+        let commands = server.join().unwrap();
+        let activity = commands
+            .iter()
+            .find(|command| !command.get("args").get("activity").is_null())
+            .unwrap()
+            .get("args")
+            .get("activity");
+
+        assert_eq!(activity.get("state").as_str(), Some("In a match"));
+        assert_eq!(activity.get("details"), &Value::Null);
+        assert_eq!(
+            activity.get("party").get("size"),
+            &Value::Array(vec![2_i64.into(), 4_i64.into()])
+        );
+        assert_eq!(
+            activity.get("secrets").get("join").as_str(),
+            Some("join-secret")
+        );
+        assert!(commands
+            .iter()
+            .any(|command| command.get("evt").as_str() == Some("ACTIVITY_JOIN_REQUEST")));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn refused_handshakes_are_reported() {
+        let path = socket_path("discord-ipc-refused");
+        let refusal = object(vec![
+            ("code", 4000_i64.into()),
+            ("message", "Invalid Client ID".into()),
+        ]);
+        let server = serve(UnixListener::bind(&path).unwrap(), Some(refusal));
+
+        let result = Discord::<()>::with_ipc_socket(&path, 42);
+        assert_eq!(result.err(), Some(Error::ServiceUnavailable));
+
+        server.join().unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let result = Discord::<()>::with_ipc_socket(&path, 42);
+        assert_eq!(result.err(), Some(Error::NotRunning));
+    }
+}
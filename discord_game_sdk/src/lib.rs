@@ -63,6 +63,25 @@
 //! This allows for `cargo run` to function.
 //!
 //!
+//! ### `dynamic`
+//!
+//! Delegates to `discord_game_sdk_sys/dynamic`, disable default features to use it instead of `link`.
+//!
+//! The SDK library is loaded at runtime, from the platform's library search locations or from
+//! a given path with `Discord::with_library`. It can be used as found in the SDK archive.
+//! If it cannot be loaded, creating an instance fails with `Error::LibraryUnavailable`, which
+//! allows the game to run without Discord features.
+//!
+//!
+//! ### `pregenerated`
+//!
+//! Delegates to `discord_game_sdk_sys/pregenerated`.
+//!
+//! Skips `bindgen` and the SDK download by using bindings generated from the header vendored in
+//! `discord_game_sdk_sys`. If `DISCORD_GAME_SDK_PATH` is set, the build verifies that its header
+//! matches.
+//!
+//!
 //! ### [`image`](https://docs.rs/image)
 //!
 //! Optional crate.
@@ -70,6 +89,71 @@
 //! Provides a conversion from our `Image` to `image::RgbaImage`.
 //!
 //!
+//! ### [`serde`](https://docs.rs/serde)
+//!
+//! Optional crate.
+//!
+//! Implements `Serialize` and `Deserialize` for data types such as `Activity`, `User`, `Lobby`
+//! or `Relationship`, and for enums, which are written by name. Strings are written as text;
+//! deserializing fails if one does not fit the buffer the SDK reserves for it.
+//!
+//!
+//! ### [`tracing`](https://docs.rs/tracing)
+//!
+//! Optional crate.
+//!
+//! Every asynchronous operation, such as `update_activity` or `connect_lobby`, gets a span from
+//! the request until its callback fires, named after the method, with the IDs it was given and
+//! its result; callbacks run within it. Events run within an `event` span named after the
+//! handler method.
+//!
+//! SDK log messages are emitted as `tracing` events instead of `log` records, along with the
+//! level the SDK gave them.
+//!
+//!
+//! ### `derive`
+//!
+//! Provides `#[derive(LobbyMetadata)]`, which maps the named fields of a struct to lobby or
+//! member metadata keys.
+//!
+//!
+//! ### `ipc`
+//!
+//! Provides `Discord::with_ipc` and `Discord::with_ipc_socket`, which create an instance that
+//! talks to the Discord client over its local socket instead of going through the native library.
+//!
+//! Only Rich Presence is supported: activities, the current user and their events. It works on
+//! targets the library is not available for, such as Linux on ARM; use it with
+//! `--no-default-features --features ipc,pregenerated`. Unix domain sockets are the only transport,
+//! Windows named pipes are not supported.
+//!
+//!
+//! ### `replay`
+//!
+//! Provides `Discord::with_recording`, which records everything that crosses the SDK boundary
+//! to a file, and `Discord::replay`, which plays such a recording back without the SDK.
+//!
+//! Calls, their results, callbacks and events are recorded in order with timestamps, so a
+//! session from a player's machine can be reproduced under a debugger or in a test.
+//!
+//!
+//! ### `mock`
+//!
+//! Provides `Discord::mock` and `Discord::mock_with`, which create an instance backed by an
+//! in-process imitation of the SDK instead of the native library.
+//!
+//! Every manager is implemented, state is kept in memory and callbacks complete on the next call
+//! to `run_callbacks`. This is meant for testing code that uses this crate without a Discord
+//! client; use it with `--no-default-features` to avoid linking altogether.
+//!
+//! `MockFaults` makes the imitation misbehave on purpose, to exercise error handling: calls
+//! failing with a given error, callbacks arriving late, network messages getting lost, events
+//! out of order or Discord closing mid-session.
+//!
+//! Instances connected to the same `MockService` are different users of one simulated Discord:
+//! they share lobbies, messages and voice events, and reach each other over the network.
+//!
+//!
 //! # Safety
 //!
 //! This crate relies on the SDK to provide correct data and behavior:
@@ -107,19 +191,28 @@
 mod action;
 mod activity;
 mod activity_kind;
+mod activity_updater;
 mod aliases;
 mod cast;
 mod comparison;
 mod create_flags;
 mod discord;
+mod discord_builder;
+mod discord_supervisor;
+mod discord_thread;
 mod distance;
 mod entitlement;
 mod entitlement_kind;
 mod error;
+mod event;
 mod event_handler;
 pub(crate) mod events;
 mod fetch_kind;
 mod file_stat;
+mod framing;
+mod futures;
+mod host_change;
+mod host_migration;
 mod image;
 mod image_handle;
 mod image_kind;
@@ -127,9 +220,17 @@ mod input_mode;
 mod input_mode_kind;
 pub(crate) mod iter;
 mod lobby;
+mod lobby_cache;
+mod lobby_change;
 mod lobby_kind;
 mod lobby_member_transaction;
+mod lobby_state;
 mod lobby_transaction;
+mod log_level;
+mod managers;
+mod matchmaker;
+mod matchmaker_status;
+mod metadata;
 mod oauth2_token;
 mod premium_kind;
 mod presence;
@@ -141,11 +242,17 @@ mod search_query;
 mod sku;
 mod sku_kind;
 mod status;
+mod storage_file;
 mod to_result;
 mod user;
 mod user_achievement;
 mod user_flags;
 pub(crate) mod utils;
+mod validation_error;
+
+// Declared before the methods, which create operations
+#[macro_use]
+mod operation;
 
 mod methods {
     mod core;
@@ -166,36 +273,70 @@ mod methods {
     mod callback;
 }
 
-#[cfg(test)]
+// Declared first, the macros are used by the modules below
+#[cfg(any(test, feature = "ipc", feature = "mock", feature = "replay"))]
+#[macro_use]
+mod tables;
+
+#[cfg(any(test, feature = "ipc", feature = "mock", feature = "replay"))]
+mod backend;
+
+#[cfg(feature = "ipc")]
+mod ipc;
+
+#[cfg(any(test, feature = "mock"))]
 mod mock;
 
+#[cfg(feature = "replay")]
+mod replay;
+
+#[cfg(feature = "serde")]
+mod serialization;
+
 pub(crate) use discord_game_sdk_sys as sys;
 
 pub use self::{
     action::Action,
     activity::Activity,
     activity_kind::ActivityKind,
+    activity_updater::ActivityUpdater,
     aliases::*,
     cast::Cast,
     comparison::Comparison,
     create_flags::CreateFlags,
     discord::Discord,
+    discord_builder::DiscordBuilder,
+    discord_supervisor::{ConnectionEvent, DiscordSupervisor},
+    discord_thread::{DiscordHandle, DiscordThread, Pending, Responder},
     distance::Distance,
     entitlement::Entitlement,
     entitlement_kind::EntitlementKind,
     error::{Error, Result},
+    event::{Event, EventQueue},
     event_handler::EventHandler,
     fetch_kind::FetchKind,
     file_stat::FileStat,
+    framing::{Frame, Framer, MessageID},
+    futures::{DiscordFuture, Futures},
+    host_change::HostChange,
+    host_migration::HostMigration,
     image::Image,
     image_handle::ImageHandle,
     image_kind::ImageKind,
     input_mode::InputMode,
     input_mode_kind::InputModeKind,
     lobby::Lobby,
+    lobby_cache::{CachedLobby, CachedMember, LobbyCache},
+    lobby_change::LobbyChange,
     lobby_kind::LobbyKind,
     lobby_member_transaction::LobbyMemberTransaction,
+    lobby_state::LobbyState,
     lobby_transaction::LobbyTransaction,
+    log_level::LogLevel,
+    managers::Managers,
+    matchmaker::Matchmaker,
+    matchmaker_status::MatchmakerStatus,
+    metadata::{LobbyMetadata, MetadataError, MetadataValue},
     oauth2_token::OAuth2Token,
     premium_kind::PremiumKind,
     presence::Presence,
@@ -207,7 +348,19 @@ pub use self::{
     sku::Sku,
     sku_kind::SkuKind,
     status::Status,
+    storage_file::StorageFile,
     user::User,
     user_achievement::UserAchievement,
     user_flags::UserFlags,
+    validation_error::ValidationError,
 };
+
+#[cfg(feature = "mock")]
+pub use self::mock::{MockConfig, MockFaults, MockService};
+
+#[cfg(feature = "derive")]
+pub use discord_game_sdk_derive::LobbyMetadata;
+
+// Lets derived code refer to `::discord_game_sdk` in this crate's tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as discord_game_sdk;
@@ -0,0 +1,477 @@
+use crate::{
+    Discord, Event, Lobby, LobbyChange, LobbyID, LobbyMetadata, MetadataError, Result, User, UserID,
+};
+use std::collections::{btree_map, vec_deque, BTreeMap, HashMap, VecDeque};
+
+/// Owned mirror of the lobbies the current user is connected to, kept in sync by lobby events
+///
+/// A lobby is mirrored once [`track`](#method.track) is called, usually after connecting to it,
+/// or when one of its events is first applied. [`apply`](#method.apply) takes the events drained
+/// from an [`EventQueue`](struct.EventQueue.html), or built by an
+/// [`EventHandler`](trait.EventHandler.html), and reads only what the event changed. The
+/// differences are then drained with [`drain_changes`](#method.drain_changes), once per frame.
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # fn example(mut discord: Discord<'_, EventQueue>, lobby_id: LobbyID) -> Result<()> {
+/// let mut lobbies = LobbyCache::new();
+///
+/// // Once connected
+/// lobbies.track(&discord, lobby_id)?;
+///
+/// loop {
+///     discord.run_callbacks()?;
+///
+///     let events: Vec<_> = discord.drain_events().collect();
+///     for event in &events {
+///         lobbies.apply(&discord, event)?;
+///     }
+///
+///     let changes: Vec<_> = lobbies.drain_changes().collect();
+///     for change in changes {
+///         if let LobbyChange::MemberConnected { lobby_id, member_id } = change {
+///             let member = lobbies.lobby(lobby_id).and_then(|lobby| lobby.member(member_id));
+///             // ...
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LobbyCache {
+    lobbies: BTreeMap<LobbyID, CachedLobby>,
+    changes: VecDeque<LobbyChange>,
+}
+
+/// Snapshot of a lobby, its metadata and its members, see
+/// [`LobbyCache`](struct.LobbyCache.html)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CachedLobby {
+    pub(crate) lobby: Lobby,
+    pub(crate) metadata: HashMap<String, String>,
+    pub(crate) members: BTreeMap<UserID, CachedMember>,
+}
+
+/// Snapshot of a lobby member and its metadata, see [`LobbyCache`](struct.LobbyCache.html)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CachedMember {
+    pub(crate) user: User,
+    pub(crate) metadata: HashMap<String, String>,
+}
+
+impl LobbyCache {
+    /// Creates an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the whole lobby and mirrors it
+    ///
+    /// A lobby that was already mirrored is read again, differences are recorded as changes.
+    ///
+    /// ## Errors
+    ///
+    /// Those of [`Discord::lobby`](struct.Discord.html#method.lobby) and of the iterators over
+    /// the metadata and members of the lobby.
+    pub fn track<E>(&mut self, discord: &Discord<'_, E>, lobby_id: LobbyID) -> Result<()> {
+        let snapshot = CachedLobby::read(discord, lobby_id)?;
+
+        let cached = match self.lobbies.entry(lobby_id) {
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(snapshot);
+                self.changes.push_back(LobbyChange::Added { lobby_id });
+                return Ok(());
+            }
+            btree_map::Entry::Occupied(entry) => entry.into_mut(),
+        };
+
+        let properties = cached.lobby != snapshot.lobby;
+        let keys = changed_keys(&cached.metadata, &snapshot.metadata);
+
+        if properties || !keys.is_empty() {
+            self.changes.push_back(LobbyChange::Updated {
+                lobby_id,
+                properties,
+                keys,
+            });
+        }
+
+        for &member_id in cached.members.keys() {
+            if !snapshot.members.contains_key(&member_id) {
+                self.changes.push_back(LobbyChange::MemberDisconnected {
+                    lobby_id,
+                    member_id,
+                });
+            }
+        }
+
+        for (&member_id, member) in &snapshot.members {
+            match cached.members.get(&member_id) {
+                None => self.changes.push_back(LobbyChange::MemberConnected {
+                    lobby_id,
+                    member_id,
+                }),
+
+                Some(previous) => {
+                    let keys = changed_keys(&previous.metadata, &member.metadata);
+
+                    if !keys.is_empty() {
+                        self.changes.push_back(LobbyChange::MemberUpdated {
+                            lobby_id,
+                            member_id,
+                            keys,
+                        });
+                    }
+                }
+            }
+        }
+
+        *cached = snapshot;
+
+        Ok(())
+    }
+
+    /// Stops mirroring a lobby, usually after disconnecting from it
+    ///
+    /// No change is recorded.
+    pub fn forget(&mut self, lobby_id: LobbyID) -> Option<CachedLobby> {
+        self.lobbies.remove(&lobby_id)
+    }
+
+    /// Updates the mirror from a lobby event, other events are ignored
+    ///
+    /// Events of lobbies that are not mirrored yet read the whole lobby, as
+    /// [`track`](#method.track) does.
+    ///
+    /// ## Errors
+    ///
+    /// Those of the methods reading what the event changed, the mirror is left untouched.
+    pub fn apply<E>(&mut self, discord: &Discord<'_, E>, event: &Event) -> Result<()> {
+        match *event {
+            Event::LobbyUpdate { lobby_id } => self.update_lobby(discord, lobby_id),
+
+            Event::LobbyDelete { lobby_id, reason } => {
+                if self.lobbies.remove(&lobby_id).is_some() {
+                    self.changes
+                        .push_back(LobbyChange::Deleted { lobby_id, reason });
+                }
+
+                Ok(())
+            }
+
+            Event::MemberConnect {
+                lobby_id,
+                member_id,
+            }
+            | Event::MemberUpdate {
+                lobby_id,
+                member_id,
+            } => self.update_member(discord, lobby_id, member_id),
+
+            Event::MemberDisconnect {
+                lobby_id,
+                member_id,
+            } => {
+                let removed = self
+                    .lobbies
+                    .get_mut(&lobby_id)
+                    .and_then(|cached| cached.members.remove(&member_id));
+
+                if removed.is_some() {
+                    self.changes.push_back(LobbyChange::MemberDisconnected {
+                        lobby_id,
+                        member_id,
+                    });
+                }
+
+                Ok(())
+            }
+
+            _ => Ok(()),
+        }
+    }
+
+    /// The snapshot of a mirrored lobby
+    pub fn lobby(&self, lobby_id: LobbyID) -> Option<&CachedLobby> {
+        self.lobbies.get(&lobby_id)
+    }
+
+    /// Iterates over the mirrored lobbies, by ID
+    pub fn iter(&self) -> btree_map::Values<'_, LobbyID, CachedLobby> {
+        self.lobbies.values()
+    }
+
+    /// Removes the changes recorded since the last call, in the order they were applied
+    pub fn drain_changes(&mut self) -> vec_deque::Drain<'_, LobbyChange> {
+        self.changes.drain(..)
+    }
+
+    fn update_lobby<E>(&mut self, discord: &Discord<'_, E>, lobby_id: LobbyID) -> Result<()> {
+        let cached = match self.lobbies.get_mut(&lobby_id) {
+            Some(cached) => cached,
+            None => return self.track(discord, lobby_id),
+        };
+
+        let lobby = discord.lobby(lobby_id)?;
+        let metadata = discord
+            .iter_lobby_metadata(lobby_id)?
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        let properties = cached.lobby != lobby;
+        let keys = changed_keys(&cached.metadata, &metadata);
+
+        cached.lobby = lobby;
+        cached.metadata = metadata;
+
+        if properties || !keys.is_empty() {
+            self.changes.push_back(LobbyChange::Updated {
+                lobby_id,
+                properties,
+                keys,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn update_member<E>(
+        &mut self,
+        discord: &Discord<'_, E>,
+        lobby_id: LobbyID,
+        member_id: UserID,
+    ) -> Result<()> {
+        let cached = match self.lobbies.get_mut(&lobby_id) {
+            Some(cached) => cached,
+            None => return self.track(discord, lobby_id),
+        };
+
+        let member = CachedMember::read(discord, lobby_id, member_id)?;
+
+        match cached.members.insert(member_id, member) {
+            None => self.changes.push_back(LobbyChange::MemberConnected {
+                lobby_id,
+                member_id,
+            }),
+
+            Some(previous) => {
+                let keys = changed_keys(&previous.metadata, &cached.members[&member_id].metadata);
+
+                if !keys.is_empty() {
+                    self.changes.push_back(LobbyChange::MemberUpdated {
+                        lobby_id,
+                        member_id,
+                        keys,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl CachedLobby {
+    fn read<E>(discord: &Discord<'_, E>, lobby_id: LobbyID) -> Result<Self> {
+        let members = discord
+            .iter_lobby_member_ids(lobby_id)?
+            .map(|member_id| {
+                let member_id = member_id?;
+
+                Ok((member_id, CachedMember::read(discord, lobby_id, member_id)?))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            lobby: discord.lobby(lobby_id)?,
+            metadata: discord
+                .iter_lobby_metadata(lobby_id)?
+                .collect::<Result<_>>()?,
+            members,
+        })
+    }
+
+    /// The lobby's properties
+    pub fn lobby(&self) -> &Lobby {
+        &self.lobby
+    }
+
+    /// The lobby's metadata
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
+    /// Decodes the lobby's metadata, as
+    /// [`Discord::lobby_metadata_as`](struct.Discord.html#method.lobby_metadata_as) does
+    pub fn metadata_as<T: LobbyMetadata>(&self) -> std::result::Result<T, MetadataError> {
+        T::from_metadata(&self.metadata)
+    }
+
+    /// A member of the lobby
+    pub fn member(&self, member_id: UserID) -> Option<&CachedMember> {
+        self.members.get(&member_id)
+    }
+
+    /// Iterates over the members of the lobby, by ID
+    pub fn members(&self) -> btree_map::Iter<'_, UserID, CachedMember> {
+        self.members.iter()
+    }
+}
+
+impl CachedMember {
+    fn read<E>(discord: &Discord<'_, E>, lobby_id: LobbyID, member_id: UserID) -> Result<Self> {
+        Ok(Self {
+            user: discord.lobby_member_user(lobby_id, member_id)?,
+            metadata: discord
+                .iter_lobby_member_metadata(lobby_id, member_id)?
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// The member's user information
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    /// The member's metadata
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
+    /// Decodes the member's metadata, as
+    /// [`Discord::lobby_member_metadata_as`](struct.Discord.html#method.lobby_member_metadata_as)
+    /// does
+    pub fn metadata_as<T: LobbyMetadata>(&self) -> std::result::Result<T, MetadataError> {
+        T::from_metadata(&self.metadata)
+    }
+}
+
+// Keys that were added, modified or deleted, in order
+fn changed_keys(old: &HashMap<String, String>, new: &HashMap<String, String>) -> Vec<String> {
+    let mut keys: Vec<String> = old
+        .keys()
+        .filter(|key| old.get(*key) != new.get(*key))
+        .chain(new.keys().filter(|key| !old.contains_key(*key)))
+        .cloned()
+        .collect();
+
+    keys.sort();
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{
+            fixtures::{create_lobby, queued, service_user},
+            MockService,
+        },
+        EventQueue, LobbyKind, LobbyMemberTransaction, LobbyTransaction,
+    };
+
+    #[test]
+    fn follows_lobby_events() {
+        fn tick(
+            cache: &mut LobbyCache,
+            host: &mut Discord<'_, EventQueue>,
+            guest: Option<&mut Discord<'_, EventQueue>>,
+        ) -> Vec<LobbyChange> {
+            if let Some(guest) = guest {
+                guest.run_callbacks().unwrap();
+            }
+            host.run_callbacks().unwrap();
+
+            let events: Vec<_> = host.drain_events().collect();
+            for event in &events {
+                cache.apply(host, event).unwrap();
+            }
+
+            cache.drain_changes().collect()
+        }
+
+        let service = MockService::new();
+        let mut host = queued(&service_user(&service, 1, "host"));
+        let mut guest = Discord::<EventQueue>::mock_with(&service_user(&service, 2, "guest"));
+
+        let lobby = create_lobby(
+            &mut host,
+            LobbyTransaction::new()
+                .kind(LobbyKind::Public)
+                .add_metadata("mode".to_string(), "ranked".to_string()),
+        );
+        let lobby_id = lobby.id();
+
+        let mut cache = LobbyCache::new();
+        cache.track(&host, lobby_id).unwrap();
+        assert_eq!(
+            cache.drain_changes().collect::<Vec<_>>(),
+            vec![LobbyChange::Added { lobby_id }]
+        );
+        assert_eq!(cache.lobby(lobby_id).unwrap().metadata()["mode"], "ranked");
+
+        guest.connect_lobby(lobby_id, lobby.secret(), |_, res| assert!(res.is_ok()));
+        assert_eq!(
+            tick(&mut cache, &mut host, Some(&mut guest)),
+            vec![LobbyChange::MemberConnected {
+                lobby_id,
+                member_id: 2
+            }]
+        );
+
+        guest.update_member(
+            lobby_id,
+            2,
+            LobbyMemberTransaction::new().add_metadata("team".to_string(), "red".to_string()),
+            |_, res| res.unwrap(),
+        );
+        assert_eq!(
+            tick(&mut cache, &mut host, Some(&mut guest)),
+            vec![LobbyChange::MemberUpdated {
+                lobby_id,
+                member_id: 2,
+                keys: vec!["team".to_string()]
+            }]
+        );
+
+        let member = cache.lobby(lobby_id).unwrap().member(2).unwrap();
+        assert_eq!(member.user().username(), "guest");
+        assert_eq!(member.metadata()["team"], "red");
+
+        host.update_lobby(
+            lobby_id,
+            LobbyTransaction::new()
+                .capacity(8)
+                .add_metadata("map".to_string(), "dust".to_string())
+                .add_metadata("mode".to_string(), "ranked".to_string()),
+            |_, res| res.unwrap(),
+        );
+        assert_eq!(
+            tick(&mut cache, &mut host, Some(&mut guest)),
+            vec![LobbyChange::Updated {
+                lobby_id,
+                properties: true,
+                keys: vec!["map".to_string()]
+            }]
+        );
+        assert_eq!(cache.lobby(lobby_id).unwrap().lobby().capacity(), 8);
+
+        drop(guest);
+        assert_eq!(
+            tick(&mut cache, &mut host, None),
+            vec![LobbyChange::MemberDisconnected {
+                lobby_id,
+                member_id: 2
+            }]
+        );
+
+        host.delete_lobby(lobby_id, |_, res| res.unwrap());
+        assert_eq!(
+            tick(&mut cache, &mut host, None),
+            vec![LobbyChange::Deleted {
+                lobby_id,
+                reason: 0
+            }]
+        );
+        assert!(cache.iter().next().is_none());
+    }
+}
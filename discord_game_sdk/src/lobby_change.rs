@@ -0,0 +1,59 @@
+use crate::{LobbyID, UserID};
+
+/// Difference applied to a [`LobbyCache`](struct.LobbyCache.html), see
+/// [`LobbyCache::drain_changes`](struct.LobbyCache.html#method.drain_changes)
+///
+/// Metadata keys are listed in order, whether they were added, modified or deleted.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LobbyChange {
+    /// The lobby is now mirrored, with the members it had then
+    Added {
+        /// New lobby
+        lobby_id: LobbyID,
+    },
+
+    /// The lobby's properties or metadata changed
+    Updated {
+        /// Updated lobby
+        lobby_id: LobbyID,
+        /// Whether the kind, owner, secret, capacity or lock changed
+        properties: bool,
+        /// Metadata keys that changed
+        keys: Vec<String>,
+    },
+
+    /// The lobby was deleted and is no longer mirrored
+    Deleted {
+        /// Deleted lobby
+        lobby_id: LobbyID,
+        /// Reason for deletion, as given by the SDK
+        reason: u32,
+    },
+
+    /// A member joined the lobby
+    MemberConnected {
+        /// Lobby that was joined
+        lobby_id: LobbyID,
+        /// New member
+        member_id: UserID,
+    },
+
+    /// The metadata of a member changed
+    MemberUpdated {
+        /// Lobby of the member
+        lobby_id: LobbyID,
+        /// Updated member
+        member_id: UserID,
+        /// Metadata keys that changed
+        keys: Vec<String>,
+    },
+
+    /// A member left the lobby
+    MemberDisconnected {
+        /// Lobby that was left
+        lobby_id: LobbyID,
+        /// Former member
+        member_id: UserID,
+    },
+}
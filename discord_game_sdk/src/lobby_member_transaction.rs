@@ -1,4 +1,10 @@
-use crate::{sys, to_result::ToResult, Result};
+use crate::{
+    sys,
+    to_result::ToResult,
+    utils::metadata_entry,
+    validation_error::{validate_metadata, validate_metadata_map, ValidationError},
+    Error, LobbyMetadata, MetadataError, Result,
+};
 use std::collections::HashMap;
 
 /// Lobby Member Transaction
@@ -24,18 +30,26 @@ impl LobbyMemberTransaction {
     /// A nul byte will be appended to `key` and `value` if one is not present.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#lobbymembertransactionsetmetadata)
-    pub fn add_metadata(&mut self, mut key: String, mut value: String) -> &mut Self {
-        if !key.ends_with('\0') {
-            key.push('\0')
-        }
-
-        if !value.ends_with('\0') {
-            value.push('\0')
-        }
+    pub fn add_metadata(&mut self, key: String, value: String) -> &mut Self {
+        let (key, value) = metadata_entry(key, Some(value));
+        let _ = self.metadata.insert(key, value);
+        self
+    }
 
-        let _ = self.metadata.insert(key, Some(value));
+    /// Sets metadata value under a given key for the user, checking that both fit
+    ///
+    /// ## Errors
+    ///
+    /// If `key` or `value` is longer than the SDK allows or contains a nul byte, the transaction
+    /// is left untouched.
+    pub fn try_add_metadata(
+        &mut self,
+        key: String,
+        value: String,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        validate_metadata(&key, Some(&value))?;
 
-        self
+        Ok(self.add_metadata(key, value))
     }
 
     /// Deletes metadata value under a given key for the user
@@ -45,19 +59,61 @@ impl LobbyMemberTransaction {
     /// A nul byte will be appended to `key` if one is not present.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#lobbymembertransactiondeletemetadata)
-    pub fn delete_metadata<S>(&mut self, mut key: String) -> &mut Self {
-        if !key.ends_with('\0') {
-            key.push('\0')
+    pub fn delete_metadata<S>(&mut self, key: String) -> &mut Self {
+        let (key, value) = metadata_entry(key, None);
+        let _ = self.metadata.insert(key, value);
+        self
+    }
+
+    /// Sets or deletes the metadata of the user described by `metadata`
+    ///
+    /// ## Errors
+    ///
+    /// If a field of `metadata` cannot be encoded or does not fit, the transaction is left
+    /// untouched.
+    pub fn add_typed_metadata(
+        &mut self,
+        metadata: &impl LobbyMetadata,
+    ) -> std::result::Result<&mut Self, MetadataError> {
+        let metadata = metadata.to_metadata()?;
+
+        for (key, value) in &metadata {
+            validate_metadata(key, value.as_deref())?;
         }
 
-        let _ = self.metadata.insert(key, None);
-        self
+        self.metadata.extend(
+            metadata
+                .into_iter()
+                .map(|(key, value)| metadata_entry(key, value)),
+        );
+
+        Ok(self)
+    }
+
+    /// Whether the transaction changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.metadata.is_empty()
+    }
+
+    /// Checks that every metadata key and value fits in the buffers of the SDK
+    ///
+    /// The SDK would truncate strings that do not fit without reporting an error, so sending the
+    /// transaction checks them too and fails with `Error::InvalidString`. This and
+    /// [`try_add_metadata`](#method.try_add_metadata) find out before sending.
+    ///
+    /// ## Errors
+    ///
+    /// The key or value that is too long or contains a nul byte, checking keys in order.
+    pub fn validate(&self) -> std::result::Result<(), ValidationError> {
+        validate_metadata_map(&self.metadata)
     }
 
     pub(crate) unsafe fn process(
         &self,
         tx: *mut sys::IDiscordLobbyMemberTransaction,
     ) -> Result<()> {
+        self.validate().map_err(Error::InvalidString)?;
+
         for (key, value) in &self.metadata {
             match value {
                 Some(value) => {
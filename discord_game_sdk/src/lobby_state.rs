@@ -0,0 +1,287 @@
+use crate::{
+    utils::metadata_entry, Discord, Lobby, LobbyID, LobbyKind, LobbyMemberTransaction,
+    LobbyMetadata, LobbyTransaction, MetadataError, Result, UserID,
+};
+use std::collections::HashMap;
+
+/// Desired state of a lobby, from which the transaction reaching it is computed
+///
+/// Properties that are not set are left as they are, while the metadata is complete: keys that
+/// are not part of it are deleted.
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) {
+/// discord.update_lobby_to(
+///     lobby_id,
+///     LobbyState::new()
+///         .capacity(8)
+///         .add_metadata("mode".into(), "ranked".into()),
+///     |discord, result| {
+///         if let Err(error) = result {
+///             eprintln!("failed to update lobby: {}", error);
+///         }
+///     },
+/// );
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LobbyState {
+    pub(crate) kind: Option<LobbyKind>,
+    pub(crate) capacity: Option<u32>,
+    pub(crate) locked: Option<bool>,
+    pub(crate) metadata: HashMap<String, String>,
+}
+
+impl LobbyState {
+    /// Creates a state that leaves the properties as they are and has no metadata
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the lobby should be private or public
+    pub fn kind(&mut self, kind: LobbyKind) -> &mut Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// The maximum amount of players that should be able to join
+    pub fn capacity(&mut self, capacity: u32) -> &mut Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Whether the lobby should be locked
+    pub fn locked(&mut self, locked: bool) -> &mut Self {
+        self.locked = Some(locked);
+        self
+    }
+
+    /// Adds a metadata value under a given key
+    pub fn add_metadata(&mut self, key: String, value: String) -> &mut Self {
+        let _ = self.metadata.insert(key, value);
+        self
+    }
+
+    /// Adds the metadata described by `metadata`, the fields that would be deleted are removed
+    ///
+    /// ## Errors
+    ///
+    /// If a field of `metadata` cannot be encoded, the state is left untouched.
+    pub fn add_typed_metadata(
+        &mut self,
+        metadata: &impl LobbyMetadata,
+    ) -> std::result::Result<&mut Self, MetadataError> {
+        for (key, value) in metadata.to_metadata()? {
+            match value {
+                Some(value) => {
+                    self.metadata.insert(key, value);
+                }
+                None => {
+                    self.metadata.remove(&key);
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// The smallest transaction that takes a lobby from `lobby` and `metadata` to this state
+    pub fn diff(&self, lobby: &Lobby, metadata: &HashMap<String, String>) -> LobbyTransaction {
+        let mut transaction = LobbyTransaction::new();
+
+        if let Some(kind) = self.kind.filter(|&kind| kind != lobby.kind()) {
+            transaction.kind(kind);
+        }
+
+        if let Some(capacity) = self
+            .capacity
+            .filter(|&capacity| capacity != lobby.capacity())
+        {
+            transaction.capacity(capacity);
+        }
+
+        if let Some(locked) = self.locked.filter(|&locked| locked != lobby.locked()) {
+            transaction.locked(locked);
+        }
+
+        transaction.metadata = metadata_diff(metadata, &self.metadata);
+        transaction
+    }
+}
+
+impl<'d, E> Discord<'d, E> {
+    /// Updates a lobby so that it matches `desired`, sending only what differs
+    ///
+    /// The current state is read with [`lobby`](#method.lobby) and
+    /// [`iter_lobby_metadata`](#method.iter_lobby_metadata). Returns `false` when the lobby
+    /// already matches: nothing is sent and `callback` is dropped without being called.
+    pub fn update_lobby_to(
+        &self,
+        lobby_id: LobbyID,
+        desired: &LobbyState,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) -> bool {
+        let current = self.lobby(lobby_id).and_then(|lobby| {
+            let metadata = self
+                .iter_lobby_metadata(lobby_id)?
+                .collect::<Result<HashMap<_, _>>>()?;
+
+            Ok(desired.diff(&lobby, &metadata))
+        });
+
+        match current {
+            Ok(transaction) if transaction.is_empty() => return false,
+            Ok(transaction) => self.update_lobby(lobby_id, &transaction, callback),
+            Err(error) => callback(self, Err(error)),
+        }
+
+        true
+    }
+
+    /// Updates the metadata of a lobby member so that it is `desired`, sending only what differs
+    ///
+    /// Keys that are not part of `desired` are deleted. The current metadata is read with
+    /// [`iter_lobby_member_metadata`](#method.iter_lobby_member_metadata). Returns `false` when
+    /// it already matches: nothing is sent and `callback` is dropped without being called.
+    pub fn update_member_to(
+        &self,
+        lobby_id: LobbyID,
+        user_id: UserID,
+        desired: &HashMap<String, String>,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
+    ) -> bool {
+        let current = self
+            .iter_lobby_member_metadata(lobby_id, user_id)
+            .and_then(|metadata| metadata.collect::<Result<HashMap<_, _>>>());
+
+        let metadata = match current {
+            Ok(metadata) => metadata_diff(&metadata, desired),
+            Err(error) => {
+                callback(self, Err(error));
+                return true;
+            }
+        };
+
+        if metadata.is_empty() {
+            return false;
+        }
+
+        let mut transaction = LobbyMemberTransaction::new();
+        transaction.metadata = metadata;
+
+        self.update_member(lobby_id, user_id, &transaction, callback);
+        true
+    }
+}
+
+// Changes as stored by transactions
+fn metadata_diff(
+    current: &HashMap<String, String>,
+    desired: &HashMap<String, String>,
+) -> HashMap<String, Option<String>> {
+    let changed = desired
+        .iter()
+        .filter(|(key, value)| current.get(*key) != Some(*value))
+        .map(|(key, value)| metadata_entry(key.clone(), Some(value.clone())));
+
+    let deleted = current
+        .keys()
+        .filter(|key| !desired.contains_key(*key))
+        .map(|key| metadata_entry(key.clone(), None));
+
+    changed.chain(deleted).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::fixtures::{create_lobby, run};
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn sends_the_smallest_transaction() {
+        fn metadata(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        }
+
+        let mut discord = Discord::<()>::mock();
+        let lobby = create_lobby(
+            &mut discord,
+            LobbyTransaction::new()
+                .capacity(4)
+                .add_metadata("a".to_string(), "1".to_string())
+                .add_metadata("b".to_string(), "2".to_string()),
+        );
+
+        let mut desired = LobbyState::new();
+        desired
+            .kind(lobby.kind())
+            .capacity(8)
+            .add_metadata("a".to_string(), "1".to_string())
+            .add_metadata("c".to_string(), "3".to_string());
+
+        let transaction = desired.diff(&lobby, &metadata(&[("a", "1"), ("b", "2")]));
+        assert_eq!(transaction.kind, None);
+        assert_eq!(transaction.capacity, Some(8));
+        assert_eq!(
+            transaction.metadata,
+            vec![
+                ("b\0".to_string(), None),
+                ("c\0".to_string(), Some("3\0".to_string())),
+            ]
+            .into_iter()
+            .collect()
+        );
+
+        let updated = Rc::new(RefCell::new(0));
+        for &sent in &[true, false] {
+            let update = discord.update_lobby_to(lobby.id(), &desired, {
+                let updated = updated.clone();
+                move |_, res| {
+                    res.unwrap();
+                    *updated.borrow_mut() += 1;
+                }
+            });
+            assert_eq!(update, sent);
+            run(&mut discord);
+        }
+        assert_eq!(*updated.borrow(), 1);
+        assert!(desired
+            .diff(
+                &discord.lobby(lobby.id()).unwrap(),
+                &metadata(&[("a", "1"), ("c", "3")])
+            )
+            .is_empty());
+        assert_eq!(
+            discord
+                .iter_lobby_metadata(lobby.id())
+                .unwrap()
+                .collect::<Result<HashMap<_, _>>>(),
+            Ok(metadata(&[("a", "1"), ("c", "3")]))
+        );
+        assert_eq!(discord.lobby(lobby.id()).unwrap().capacity(), 8);
+
+        let user_id = discord.current_user().unwrap().id();
+        for desired in &[metadata(&[("team", "red")]), metadata(&[("role", "x")])] {
+            assert!(discord.update_member_to(lobby.id(), user_id, desired, |_, res| res.unwrap()));
+            run(&mut discord);
+        }
+        assert!(!discord.update_member_to(
+            lobby.id(),
+            user_id,
+            &metadata(&[("role", "x")]),
+            |_, _| panic!("nothing was sent")
+        ));
+        assert_eq!(
+            discord
+                .iter_lobby_member_metadata(lobby.id(), user_id)
+                .unwrap()
+                .collect::<Result<HashMap<_, _>>>(),
+            Ok(metadata(&[("role", "x")]))
+        );
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{sys, to_result::ToResult, LobbyKind, Result, UserID};
+use crate::{
+    sys,
+    to_result::ToResult,
+    utils::metadata_entry,
+    validation_error::{validate_metadata, validate_metadata_map, ValidationError},
+    Error, LobbyKind, LobbyMetadata, MetadataError, Result, UserID,
+};
 use std::collections::HashMap;
 
 /// Lobby Transaction
@@ -53,19 +59,28 @@ impl LobbyTransaction {
     /// A nul byte will be appended to `key` and `value` if one is not present.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#lobbytransactionsetmetadata)
-    pub fn add_metadata(&mut self, mut key: String, mut value: String) -> &mut Self {
-        if !key.ends_with('\0') {
-            key.push('\0')
-        }
-
-        if !value.ends_with('\0') {
-            value.push('\0')
-        }
-
-        let _ = self.metadata.insert(key, Some(value));
+    pub fn add_metadata(&mut self, key: String, value: String) -> &mut Self {
+        let (key, value) = metadata_entry(key, Some(value));
+        let _ = self.metadata.insert(key, value);
         self
     }
 
+    /// Sets metadata value under a given key for the lobby, checking that both fit
+    ///
+    /// ## Errors
+    ///
+    /// If `key` or `value` is longer than the SDK allows or contains a nul byte, the transaction
+    /// is left untouched.
+    pub fn try_add_metadata(
+        &mut self,
+        key: String,
+        value: String,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        validate_metadata(&key, Some(&value))?;
+
+        Ok(self.add_metadata(key, value))
+    }
+
     /// Deletes metadata value under a given key for the lobby
     ///
     /// ## Performance
@@ -73,13 +88,35 @@ impl LobbyTransaction {
     /// A nul byte will be appended to `key` if one is not present.
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#lobbytransactiondeletemetadata)
-    pub fn delete_metadata<S>(&mut self, mut key: String) -> &mut Self {
-        if !key.ends_with('\0') {
-            key.push('\0')
+    pub fn delete_metadata<S>(&mut self, key: String) -> &mut Self {
+        let (key, value) = metadata_entry(key, None);
+        let _ = self.metadata.insert(key, value);
+        self
+    }
+
+    /// Sets or deletes the metadata of the lobby described by `metadata`
+    ///
+    /// ## Errors
+    ///
+    /// If a field of `metadata` cannot be encoded or does not fit, the transaction is left
+    /// untouched.
+    pub fn add_typed_metadata(
+        &mut self,
+        metadata: &impl LobbyMetadata,
+    ) -> std::result::Result<&mut Self, MetadataError> {
+        let metadata = metadata.to_metadata()?;
+
+        for (key, value) in &metadata {
+            validate_metadata(key, value.as_deref())?;
         }
 
-        let _ = self.metadata.insert(key, None);
-        self
+        self.metadata.extend(
+            metadata
+                .into_iter()
+                .map(|(key, value)| metadata_entry(key, value)),
+        );
+
+        Ok(self)
     }
 
     /// Sets whether the lobby is locked or not. When locked, new users cannot join
@@ -90,7 +127,31 @@ impl LobbyTransaction {
         self
     }
 
+    /// Whether the transaction changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.kind.is_none()
+            && self.owner.is_none()
+            && self.capacity.is_none()
+            && self.locked.is_none()
+            && self.metadata.is_empty()
+    }
+
+    /// Checks that every metadata key and value fits in the buffers of the SDK
+    ///
+    /// The SDK would truncate strings that do not fit without reporting an error, so sending the
+    /// transaction checks them too and fails with `Error::InvalidString`. This and
+    /// [`try_add_metadata`](#method.try_add_metadata) find out before sending.
+    ///
+    /// ## Errors
+    ///
+    /// The key or value that is too long or contains a nul byte, checking keys in order.
+    pub fn validate(&self) -> std::result::Result<(), ValidationError> {
+        validate_metadata_map(&self.metadata)
+    }
+
     pub(crate) unsafe fn process(&self, tx: *mut sys::IDiscordLobbyTransaction) -> Result<()> {
+        self.validate().map_err(Error::InvalidString)?;
+
         if let Some(kind) = self.kind {
             (*tx).set_type.unwrap()(tx, kind.into()).to_result()?;
         }
@@ -0,0 +1,54 @@
+use crate::sys;
+
+/// Log Level, ordered from the most to the least severe
+///
+/// > [Enum in official docs](https://discordapp.com/developers/docs/game-sdk/discord#data-models-loglevel-enum)
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LogLevel {
+    /// Errors only
+    Error,
+    /// Warnings and errors
+    Warn,
+    /// Informational messages and above
+    Info,
+    /// Everything, including debugging messages
+    Debug,
+}
+
+impl From<sys::EDiscordLogLevel> for LogLevel {
+    fn from(source: sys::EDiscordLogLevel) -> Self {
+        match source {
+            sys::DiscordLogLevel_Error => Self::Error,
+            sys::DiscordLogLevel_Warn => Self::Warn,
+            sys::DiscordLogLevel_Info => Self::Info,
+            _ => Self::Debug,
+        }
+    }
+}
+
+impl From<LogLevel> for sys::EDiscordLogLevel {
+    fn from(source: LogLevel) -> Self {
+        match source {
+            LogLevel::Error => sys::DiscordLogLevel_Error,
+            LogLevel::Warn => sys::DiscordLogLevel_Warn,
+            LogLevel::Info => sys::DiscordLogLevel_Info,
+            LogLevel::Debug => sys::DiscordLogLevel_Debug,
+        }
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            fmt,
+            "{}",
+            match self {
+                Self::Error => "error",
+                Self::Warn => "warn",
+                Self::Info => "info",
+                Self::Debug => "debug",
+            }
+        )
+    }
+}
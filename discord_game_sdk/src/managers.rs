@@ -0,0 +1,27 @@
+bitflags::bitflags! {
+    /// Managers that produce events, see
+    /// [`DiscordBuilder::with_managers`](struct.DiscordBuilder.html#method.with_managers)
+    ///
+    /// The SDK only sends the events of a manager once it was initialized, which happens the
+    /// first time one of its methods is used.
+    pub struct Managers: u32 {
+        /// Achievements, for `on_user_achievement_update`
+        const ACHIEVEMENTS = 1 << 0;
+        /// Activities, for joins, spectates, requests and invites
+        const ACTIVITIES = 1 << 1;
+        /// Lobbies, for lobby, member, message and speaking events
+        const LOBBIES = 1 << 2;
+        /// Networking, for messages and route updates
+        const NETWORKING = 1 << 3;
+        /// Overlay, for `on_overlay_toggle`
+        const OVERLAY = 1 << 4;
+        /// Relationships, for `on_relationships_refresh` and `on_relationship_update`
+        const RELATIONSHIPS = 1 << 5;
+        /// Store, for entitlement events
+        const STORE = 1 << 6;
+        /// Users, for `on_current_user_update`
+        const USERS = 1 << 7;
+        /// Voice, for `on_voice_settings_update`
+        const VOICE = 1 << 8;
+    }
+}
@@ -0,0 +1,415 @@
+use crate::{Discord, Distance, Lobby, LobbyTransaction, MatchmakerStatus, Result, SearchQuery};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    fmt,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+type Scorer = Box<dyn FnMut(&Lobby, &HashMap<String, String>) -> Option<i64>>;
+
+/// Finds a lobby to join with searches that widen over time, or creates one
+///
+/// Stages are searched in order, each one is given some time before the next, usually wider,
+/// query is sent, and the last one is repeated. The lobbies found are ranked by the scorer and
+/// joined with [`connect_lobby`](struct.Discord.html#method.connect_lobby), best first, until
+/// one accepts the current user. If no lobby was joined before the deadline, the fallback lobby
+/// is created with [`create_lobby`](struct.Discord.html#method.create_lobby) instead.
+///
+/// [`update`](#method.update) must be called regularly, along with
+/// [`Discord::run_callbacks`](struct.Discord.html#method.run_callbacks).
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # use std::time::Duration;
+/// # fn example(mut discord: Discord<'_, ()>) -> Result<()> {
+/// let mut query = SearchQuery::new();
+/// query.filter("mode".into(), Comparison::Equal, "ranked".into(), Cast::String);
+///
+/// let mut matchmaker = Matchmaker::new(
+///     LobbyTransaction::new()
+///         .kind(LobbyKind::Public)
+///         .capacity(4)
+///         .add_metadata("mode".into(), "ranked".into()),
+/// );
+///
+/// matchmaker
+///     .with_distance_stages(&query, Duration::from_secs(5))
+///     .with_scorer(|lobby, _| Some(i64::from(lobby.capacity())))
+///     .with_deadline(Duration::from_secs(30));
+///
+/// while !matchmaker.is_done() {
+///     matchmaker.update(&discord);
+///     discord.run_callbacks()?;
+/// }
+///
+/// if let MatchmakerStatus::Joined(lobby) | MatchmakerStatus::Created(lobby) = matchmaker.status() {
+///     // ...
+/// }
+/// # Ok(()) }
+/// ```
+pub struct Matchmaker {
+    stages: Vec<(SearchQuery, Duration)>,
+    scorer: Option<Scorer>,
+    fallback: LobbyTransaction,
+    deadline: Duration,
+    status: MatchmakerStatus,
+    started_at: Option<Instant>,
+    // Last stage that was searched
+    stage: Option<usize>,
+    next_search_at: Option<Instant>,
+    candidates: VecDeque<Lobby>,
+    in_flight: bool,
+    shared: Rc<RefCell<Shared>>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    searched: Option<Result<()>>,
+    connected: Option<Result<Lobby>>,
+    created: Option<Result<Lobby>>,
+}
+
+impl Matchmaker {
+    /// Deadline used unless [`with_deadline`](#method.with_deadline) is called
+    pub const DEFAULT_DEADLINE: Duration = Duration::from_secs(30);
+
+    /// Creates a matchmaker that creates `fallback` when it finds nothing to join
+    ///
+    /// Without stages, the fallback lobby is created right away.
+    pub fn new(fallback: &LobbyTransaction) -> Self {
+        Self {
+            stages: Vec::new(),
+            scorer: None,
+            fallback: fallback.clone(),
+            deadline: Self::DEFAULT_DEADLINE,
+            status: MatchmakerStatus::Searching { stage: 0 },
+            started_at: None,
+            stage: None,
+            next_search_at: None,
+            candidates: VecDeque::new(),
+            in_flight: false,
+            shared: Rc::new(RefCell::new(Shared::default())),
+        }
+    }
+
+    /// Adds a stage searching with `query`, the next stage is searched `wait` later
+    pub fn with_stage(&mut self, query: &SearchQuery, wait: Duration) -> &mut Self {
+        self.stages.push((query.clone(), wait));
+        self
+    }
+
+    /// Adds a stage per [`Distance`](enum.Distance.html), from `Local` to `Global`, searching
+    /// with `query`
+    pub fn with_distance_stages(&mut self, query: &SearchQuery, wait: Duration) -> &mut Self {
+        for &distance in &[
+            Distance::Local,
+            Distance::Default,
+            Distance::Extended,
+            Distance::Global,
+        ] {
+            self.with_stage(query.clone().distance(distance), wait);
+        }
+
+        self
+    }
+
+    /// Ranks the lobbies found by a search, given their properties and metadata
+    ///
+    /// Higher scores are joined first and `None` rejects the lobby. Lobbies with equal scores
+    /// keep the order of the search, which is the only ranking without a scorer.
+    pub fn with_scorer(
+        &mut self,
+        scorer: impl 'static + FnMut(&Lobby, &HashMap<String, String>) -> Option<i64>,
+    ) -> &mut Self {
+        self.scorer = Some(Box::new(scorer));
+        self
+    }
+
+    /// How long after the first update the fallback lobby is created if nothing was joined,
+    /// [`DEFAULT_DEADLINE`](#associatedconstant.DEFAULT_DEADLINE) by default
+    pub fn with_deadline(&mut self, deadline: Duration) -> &mut Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// The progress so far
+    pub fn status(&self) -> &MatchmakerStatus {
+        &self.status
+    }
+
+    /// Whether a lobby was joined or created, or creating one failed
+    pub fn is_done(&self) -> bool {
+        matches!(
+            self.status,
+            MatchmakerStatus::Joined(_)
+                | MatchmakerStatus::Created(_)
+                | MatchmakerStatus::Failed(_)
+        )
+    }
+
+    /// Sends the next search, connection or creation when due
+    ///
+    /// The first call starts the deadline. Results of previous requests are received during
+    /// `run_callbacks`.
+    pub fn update<E>(&mut self, discord: &Discord<'_, E>) {
+        self.update_at(discord, Instant::now())
+    }
+
+    pub(crate) fn update_at<E>(&mut self, discord: &Discord<'_, E>, now: Instant) {
+        if self.is_done() {
+            return;
+        }
+
+        let started_at = *self.started_at.get_or_insert(now);
+        let (searched, connected, created) = {
+            let mut shared = self.shared.borrow_mut();
+
+            (
+                shared.searched.take(),
+                shared.connected.take(),
+                shared.created.take(),
+            )
+        };
+
+        if let Some(res) = searched {
+            self.in_flight = false;
+
+            match res {
+                Ok(()) => self.candidates = self.rank(discord),
+                Err(error) => log::warn!("lobby search failed: {}", error),
+            }
+        }
+
+        if let Some(res) = connected {
+            self.in_flight = false;
+
+            match res {
+                Ok(lobby) => {
+                    self.status = MatchmakerStatus::Joined(lobby);
+                    return;
+                }
+                Err(error) => log::debug!("could not join lobby: {}", error),
+            }
+        }
+
+        if let Some(res) = created {
+            self.in_flight = false;
+            self.status = match res {
+                Ok(lobby) => MatchmakerStatus::Created(lobby),
+                Err(error) => MatchmakerStatus::Failed(error),
+            };
+            return;
+        }
+
+        if self.in_flight {
+            return;
+        }
+
+        if let Some(lobby) = self.candidates.pop_front() {
+            return self.join(discord, lobby);
+        }
+
+        if self.stages.is_empty() || now.duration_since(started_at) >= self.deadline {
+            return self.create(discord);
+        }
+
+        self.status = MatchmakerStatus::Searching {
+            stage: self.stage.unwrap_or(0),
+        };
+
+        if !matches!(self.next_search_at, Some(at) if now < at) {
+            self.search(discord, now);
+        }
+    }
+
+    fn search<E>(&mut self, discord: &Discord<'_, E>, now: Instant) {
+        let stage = self
+            .stage
+            .map_or(0, |stage| stage + 1)
+            .min(self.stages.len() - 1);
+        let (query, wait) = &self.stages[stage];
+
+        self.stage = Some(stage);
+        self.next_search_at = Some(now + *wait);
+        self.status = MatchmakerStatus::Searching { stage };
+        self.in_flight = true;
+
+        let shared = self.shared.clone();
+        discord.lobby_search(query, move |_, res| {
+            shared.borrow_mut().searched = Some(res);
+        });
+    }
+
+    // Candidates from the last search, best first
+    fn rank<E>(&mut self, discord: &Discord<'_, E>) -> VecDeque<Lobby> {
+        let mut ranked = Vec::new();
+
+        for lobby_id in discord.iter_lobbies() {
+            let read = lobby_id.and_then(|lobby_id| {
+                Ok((
+                    discord.lobby(lobby_id)?,
+                    discord
+                        .iter_lobby_metadata(lobby_id)?
+                        .collect::<Result<HashMap<_, _>>>()?,
+                ))
+            });
+
+            let (lobby, metadata) = match read {
+                Ok(read) => read,
+                Err(error) => {
+                    log::debug!("skipping lobby found by search: {}", error);
+                    continue;
+                }
+            };
+
+            let score = match &mut self.scorer {
+                Some(scorer) => scorer(&lobby, &metadata),
+                None => Some(0),
+            };
+
+            if let Some(score) = score {
+                ranked.push((score, lobby));
+            }
+        }
+
+        // Stable, equal scores keep the order of the search
+        ranked.sort_by(|(a, _), (b, _)| b.cmp(a));
+        ranked.into_iter().map(|(_, lobby)| lobby).collect()
+    }
+
+    fn join<E>(&mut self, discord: &Discord<'_, E>, lobby: Lobby) {
+        self.status = MatchmakerStatus::Joining {
+            lobby_id: lobby.id(),
+        };
+        self.in_flight = true;
+
+        let shared = self.shared.clone();
+        discord.connect_lobby(lobby.id(), lobby.secret().to_string(), move |_, res| {
+            shared.borrow_mut().connected = Some(res.map(ToOwned::to_owned));
+        });
+    }
+
+    fn create<E>(&mut self, discord: &Discord<'_, E>) {
+        self.status = MatchmakerStatus::Creating;
+        self.candidates.clear();
+        self.in_flight = true;
+
+        let shared = self.shared.clone();
+        discord.create_lobby(&self.fallback, move |_, res| {
+            shared.borrow_mut().created = Some(res.map(ToOwned::to_owned));
+        });
+    }
+}
+
+impl fmt::Debug for Matchmaker {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Matchmaker")
+            .field("stages", &self.stages)
+            .field("fallback", &self.fallback)
+            .field("deadline", &self.deadline)
+            .field("status", &self.status)
+            .field("candidates", &self.candidates)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{
+            fixtures::{create_lobby, service_user},
+            MockService,
+        },
+        Cast, Comparison, LobbyKind,
+    };
+
+    #[test]
+    fn widens_then_falls_back() {
+        fn step(matchmaker: &mut Matchmaker, discord: &mut Discord<'_, ()>, now: Instant) {
+            matchmaker.update_at(discord, now);
+            discord.run_callbacks().unwrap();
+            matchmaker.update_at(discord, now);
+        }
+
+        let service = MockService::new();
+        let mut host = Discord::<()>::mock_with(&service_user(&service, 1, "host"));
+        let lobby_ids: Vec<_> = ["1500", "1200"]
+            .iter()
+            .map(|skill| {
+                create_lobby(
+                    &mut host,
+                    LobbyTransaction::new()
+                        .kind(LobbyKind::Public)
+                        .add_metadata("skill".to_string(), skill.to_string()),
+                )
+                .id()
+            })
+            .collect();
+
+        let mut guest = Discord::<()>::mock_with(&service_user(&service, 2, "guest"));
+
+        let query = |comparison| {
+            SearchQuery::new()
+                .filter(
+                    "skill".to_string(),
+                    comparison,
+                    "1000".to_string(),
+                    Cast::Number,
+                )
+                .clone()
+        };
+        let wait = Duration::from_secs(5);
+        let fallback = LobbyTransaction::new().kind(LobbyKind::Public).clone();
+
+        let mut matchmaker = Matchmaker::new(&fallback);
+        matchmaker
+            .with_stage(&query(Comparison::Equal), wait)
+            .with_stage(&query(Comparison::GreaterThan), wait)
+            .with_scorer(|_, metadata| {
+                let skill: i64 = metadata["skill"].parse().unwrap();
+                Some(-(skill - 1000).abs())
+            });
+
+        let start = Instant::now();
+        step(&mut matchmaker, &mut guest, start);
+        assert_eq!(
+            *matchmaker.status(),
+            MatchmakerStatus::Searching { stage: 0 }
+        );
+
+        step(&mut matchmaker, &mut guest, start + wait);
+        assert_eq!(
+            *matchmaker.status(),
+            MatchmakerStatus::Joining {
+                lobby_id: lobby_ids[1]
+            }
+        );
+
+        step(&mut matchmaker, &mut guest, start + wait);
+        match matchmaker.status() {
+            MatchmakerStatus::Joined(lobby) => assert_eq!(lobby.id(), lobby_ids[1]),
+            status => panic!("{:?}", status),
+        }
+        assert_eq!(guest.lobby_member_count(lobby_ids[1]).unwrap(), 2);
+
+        let mut matchmaker = Matchmaker::new(&fallback);
+        matchmaker
+            .with_stage(&query(Comparison::LessThan), wait)
+            .with_deadline(wait * 2);
+
+        step(&mut matchmaker, &mut guest, start);
+        step(&mut matchmaker, &mut guest, start + wait);
+        matchmaker.update_at(&guest, start + wait * 2);
+        assert_eq!(*matchmaker.status(), MatchmakerStatus::Creating);
+
+        step(&mut matchmaker, &mut guest, start + wait * 2);
+        match matchmaker.status() {
+            MatchmakerStatus::Created(lobby) => assert_eq!(lobby.owner_id(), 2),
+            status => panic!("{:?}", status),
+        }
+        assert!(matchmaker.is_done());
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{Error, Lobby, LobbyID};
+
+/// Progress of a [`Matchmaker`](struct.Matchmaker.html)
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MatchmakerStatus {
+    /// Searching with the query of this stage, or waiting for the next one
+    Searching {
+        /// Index of the stage, in the order stages were added
+        stage: usize,
+    },
+
+    /// Connecting to the best candidate found
+    Joining {
+        /// Candidate lobby
+        lobby_id: LobbyID,
+    },
+
+    /// Nothing was joined before the deadline, creating the fallback lobby
+    Creating,
+
+    /// Connected to a lobby that was found
+    Joined(Lobby),
+
+    /// Connected to the fallback lobby, owned by the current user
+    Created(Lobby),
+
+    /// Creating the fallback lobby failed
+    Failed(Error),
+}
@@ -0,0 +1,435 @@
+use crate::{Cast, Comparison, Discord, Error, LobbyID, SearchQuery, UserID, ValidationError};
+use std::{collections::HashMap, fmt};
+
+/// Lobby or member metadata described by a Rust type
+///
+/// Implement it with `#[derive(LobbyMetadata)]` when the `derive` feature is enabled, each field
+/// is stored under its name (or the one given with `#[lobby_metadata(rename = "key")]`) and
+/// encoded with [`MetadataValue`](trait.MetadataValue.html).
+///
+/// ```rust
+/// use discord_game_sdk::{LobbyMetadata, MetadataError, MetadataValue};
+/// # use discord_game_sdk::{Discord, LobbyID, LobbyTransaction};
+///
+/// struct Match {
+///     mode: String,
+///     skill: u32,
+///     password: Option<String>,
+/// }
+///
+/// impl LobbyMetadata for Match {
+///     fn to_metadata(&self) -> Result<Vec<(String, Option<String>)>, MetadataError> {
+///         Ok(vec![
+///             ("mode".into(), self.mode.encode("mode")?),
+///             ("skill".into(), self.skill.encode("skill")?),
+///             ("password".into(), self.password.encode("password")?),
+///         ])
+///     }
+///
+///     fn from_metadata(metadata: &std::collections::HashMap<String, String>) -> Result<Self, MetadataError> {
+///         let get = |key: &str| metadata.get(key).map(String::as_str);
+///
+///         Ok(Self {
+///             mode: MetadataValue::decode("mode", get("mode"))?,
+///             skill: MetadataValue::decode("skill", get("skill"))?,
+///             password: MetadataValue::decode("password", get("password"))?,
+///         })
+///     }
+/// }
+///
+/// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<(), MetadataError> {
+/// discord.create_lobby(
+///     LobbyTransaction::new().capacity(4).add_typed_metadata(&Match {
+///         mode: "ranked".into(),
+///         skill: 1500,
+///         password: None,
+///     })?,
+///     |discord, lobby| {},
+/// );
+///
+/// let current: Match = discord.lobby_metadata_as(lobby_id)?;
+/// # Ok(()) }
+/// ```
+pub trait LobbyMetadata: Sized {
+    /// Encodes every field as a key and a value, `None` deletes the key
+    fn to_metadata(&self) -> Result<Vec<(String, Option<String>)>, MetadataError>;
+
+    /// Decodes the fields from the metadata of a lobby or member
+    fn from_metadata(metadata: &HashMap<String, String>) -> Result<Self, MetadataError>;
+}
+
+/// Value that can be stored in lobby or member metadata
+///
+/// Numbers are written in plain decimal notation, without exponents, so that searches using
+/// [`Cast::Number`](enum.Cast.html#variant.Number) compare and sort them by value.
+/// Discord may compare them as floating point numbers: 64-bit integers above 2<sup>53</sup> can
+/// be considered equal to their neighbors.
+///
+/// `bool`s are stored as `0` and `1` and `Option`s as a missing key when `None`.
+pub trait MetadataValue: Sized {
+    /// Cast to use when searching for this value
+    const CAST: Cast;
+
+    /// Encodes the value stored under `key`, `None` when the key should not exist.
+    ///
+    /// ## Errors
+    ///
+    /// [`MetadataError::Unrepresentable`](enum.MetadataError.html#variant.Unrepresentable) if
+    /// the value has no textual form that compares correctly, such as `NaN`.
+    fn encode(&self, key: &str) -> Result<Option<String>, MetadataError>;
+
+    /// Decodes the value stored under `key`, `value` is `None` when the key does not exist.
+    ///
+    /// ## Errors
+    ///
+    /// [`MetadataError::Missing`](enum.MetadataError.html#variant.Missing) or
+    /// [`MetadataError::Malformed`](enum.MetadataError.html#variant.Malformed).
+    fn decode(key: &str, value: Option<&str>) -> Result<Self, MetadataError>;
+}
+
+/// Error encoding or decoding [`LobbyMetadata`](trait.LobbyMetadata.html)
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum MetadataError {
+    /// The metadata could not be read
+    Discord(Error),
+
+    /// No value is stored under `key`
+    Missing {
+        /// Key of the field
+        key: String,
+    },
+
+    /// The value stored under `key` could not be decoded
+    Malformed {
+        /// Key of the field
+        key: String,
+        /// Value that was found
+        value: String,
+    },
+
+    /// The value of `key` cannot be stored
+    Unrepresentable {
+        /// Key of the field
+        key: String,
+    },
+
+    /// A key or value does not fit in the buffers of the SDK
+    Invalid(ValidationError),
+}
+
+impl fmt::Display for MetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MetadataError::Discord(error) => error.fmt(f),
+            MetadataError::Missing { key } => write!(f, "missing metadata {:?}", key),
+            MetadataError::Malformed { key, value } => {
+                write!(f, "malformed metadata {:?}: {:?}", key, value)
+            }
+            MetadataError::Unrepresentable { key } => {
+                write!(f, "metadata {:?} cannot be represented", key)
+            }
+            MetadataError::Invalid(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for MetadataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MetadataError::Discord(error) => Some(error),
+            MetadataError::Invalid(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for MetadataError {
+    fn from(error: Error) -> Self {
+        MetadataError::Discord(error)
+    }
+}
+
+impl From<ValidationError> for MetadataError {
+    fn from(error: ValidationError) -> Self {
+        MetadataError::Invalid(error)
+    }
+}
+
+fn required<'v>(key: &str, value: Option<&'v str>) -> Result<&'v str, MetadataError> {
+    value.ok_or_else(|| MetadataError::Missing {
+        key: key.to_string(),
+    })
+}
+
+fn malformed(key: &str, value: &str) -> MetadataError {
+    MetadataError::Malformed {
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+impl MetadataValue for String {
+    const CAST: Cast = Cast::String;
+
+    fn encode(&self, _: &str) -> Result<Option<String>, MetadataError> {
+        Ok(Some(self.clone()))
+    }
+
+    fn decode(key: &str, value: Option<&str>) -> Result<Self, MetadataError> {
+        required(key, value).map(str::to_string)
+    }
+}
+
+impl MetadataValue for bool {
+    const CAST: Cast = Cast::Number;
+
+    fn encode(&self, _: &str) -> Result<Option<String>, MetadataError> {
+        Ok(Some(if *self { "1" } else { "0" }.to_string()))
+    }
+
+    fn decode(key: &str, value: Option<&str>) -> Result<Self, MetadataError> {
+        match required(key, value)? {
+            "0" => Ok(false),
+            "1" => Ok(true),
+            value => Err(malformed(key, value)),
+        }
+    }
+}
+
+macro_rules! integer {
+    ($($ty:ty),*) => {$(
+        impl MetadataValue for $ty {
+            const CAST: Cast = Cast::Number;
+
+            fn encode(&self, _: &str) -> Result<Option<String>, MetadataError> {
+                Ok(Some(self.to_string()))
+            }
+
+            fn decode(key: &str, value: Option<&str>) -> Result<Self, MetadataError> {
+                let value = required(key, value)?;
+                value.parse().map_err(|_| malformed(key, value))
+            }
+        }
+    )*};
+}
+
+integer!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+macro_rules! float {
+    ($($ty:ty),*) => {$(
+        impl MetadataValue for $ty {
+            const CAST: Cast = Cast::Number;
+
+            // `Display` for floats never uses an exponent and round-trips
+            fn encode(&self, key: &str) -> Result<Option<String>, MetadataError> {
+                if self.is_finite() {
+                    Ok(Some(self.to_string()))
+                } else {
+                    Err(MetadataError::Unrepresentable { key: key.to_string() })
+                }
+            }
+
+            fn decode(key: &str, value: Option<&str>) -> Result<Self, MetadataError> {
+                let value = required(key, value)?;
+
+                match value.parse::<$ty>() {
+                    Ok(number) if number.is_finite() => Ok(number),
+                    _ => Err(malformed(key, value)),
+                }
+            }
+        }
+    )*};
+}
+
+float!(f32, f64);
+
+impl<T: MetadataValue> MetadataValue for Option<T> {
+    const CAST: Cast = T::CAST;
+
+    fn encode(&self, key: &str) -> Result<Option<String>, MetadataError> {
+        match self {
+            Some(value) => value.encode(key),
+            None => Ok(None),
+        }
+    }
+
+    fn decode(key: &str, value: Option<&str>) -> Result<Self, MetadataError> {
+        value.map(|value| T::decode(key, Some(value))).transpose()
+    }
+}
+
+impl SearchQuery {
+    /// Filters lobbies by the metadata under `key`, encoded and cast as
+    /// [`MetadataValue`](trait.MetadataValue.html) does.
+    ///
+    /// ## Errors
+    ///
+    /// If `value` cannot be encoded, or is `None`.
+    pub fn filter_metadata<V: MetadataValue>(
+        &mut self,
+        key: &str,
+        comparison: Comparison,
+        value: &V,
+    ) -> Result<&mut Self, MetadataError> {
+        let encoded = Self::encode_metadata(key, value)?;
+
+        Ok(self.filter(format!("metadata.{}", key), comparison, encoded, V::CAST))
+    }
+
+    /// Sorts lobbies by how close the metadata under `key` is to `value`, encoded and cast as
+    /// [`MetadataValue`](trait.MetadataValue.html) does.
+    ///
+    /// ## Errors
+    ///
+    /// If `value` cannot be encoded, or is `None`.
+    pub fn sort_metadata<V: MetadataValue>(
+        &mut self,
+        key: &str,
+        value: &V,
+    ) -> Result<&mut Self, MetadataError> {
+        let encoded = Self::encode_metadata(key, value)?;
+
+        Ok(self.sort(format!("metadata.{}", key), encoded, V::CAST))
+    }
+
+    fn encode_metadata<V: MetadataValue>(key: &str, value: &V) -> Result<String, MetadataError> {
+        value.encode(key)?.ok_or_else(|| MetadataError::Missing {
+            key: key.to_string(),
+        })
+    }
+}
+
+/// # Typed Metadata
+impl<E> Discord<'_, E> {
+    /// Decodes the metadata of a lobby.
+    ///
+    /// ## Errors
+    ///
+    /// Fails like [`iter_lobby_metadata`](#method.iter_lobby_metadata) or when decoding.
+    pub fn lobby_metadata_as<T: LobbyMetadata>(
+        &self,
+        lobby_id: LobbyID,
+    ) -> Result<T, MetadataError> {
+        let metadata = self
+            .iter_lobby_metadata(lobby_id)?
+            .collect::<crate::Result<HashMap<_, _>>>()?;
+
+        T::from_metadata(&metadata)
+    }
+
+    /// Decodes the metadata of a lobby member.
+    ///
+    /// ## Errors
+    ///
+    /// Fails like [`iter_lobby_member_metadata`](#method.iter_lobby_member_metadata) or when
+    /// decoding.
+    pub fn lobby_member_metadata_as<T: LobbyMetadata>(
+        &self,
+        lobby_id: LobbyID,
+        user_id: UserID,
+    ) -> Result<T, MetadataError> {
+        let metadata = self
+            .iter_lobby_member_metadata(lobby_id, user_id)?
+            .collect::<crate::Result<HashMap<_, _>>>()?;
+
+        T::from_metadata(&metadata)
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::fixtures::{create_lobby, run},
+        LobbyKind, LobbyMemberTransaction, LobbyMetadata, LobbyTransaction, Result,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn typed_metadata_round_trips() {
+        #[derive(Debug, LobbyMetadata, PartialEq)]
+        struct Match {
+            mode: String,
+            #[lobby_metadata(rename = "rank")]
+            skill: i32,
+            ratio: f64,
+            ranked: bool,
+            password: Option<String>,
+        }
+
+        #[derive(Debug, LobbyMetadata, PartialEq)]
+        struct Player {
+            team: u8,
+        }
+
+        let mut discord = Discord::<()>::mock();
+        let expected = Match {
+            mode: "ranked".to_string(),
+            skill: -20,
+            ratio: 0.000_001,
+            ranked: true,
+            password: None,
+        };
+
+        let lobby_id = create_lobby(
+            &mut discord,
+            LobbyTransaction::new()
+                .kind(LobbyKind::Public)
+                .capacity(4)
+                .add_typed_metadata(&expected)
+                .unwrap(),
+        )
+        .id();
+
+        assert_eq!(discord.lobby_metadata(lobby_id, "rank").unwrap(), "-20");
+        assert_eq!(
+            discord.lobby_metadata_as::<Match>(lobby_id).unwrap(),
+            expected
+        );
+
+        discord.update_member(
+            lobby_id,
+            1,
+            LobbyMemberTransaction::new()
+                .add_typed_metadata(&Player { team: 2 })
+                .unwrap(),
+            |_, res| res.unwrap(),
+        );
+        run(&mut discord);
+
+        assert_eq!(
+            discord.lobby_member_metadata_as::<Player>(lobby_id, 1),
+            Ok(Player { team: 2 })
+        );
+        assert_eq!(
+            discord.lobby_member_metadata_as::<Match>(lobby_id, 1),
+            Err(MetadataError::Missing {
+                key: "mode".to_string()
+            })
+        );
+
+        assert!(LobbyTransaction::new()
+            .add_typed_metadata(&Match {
+                ratio: f64::NAN,
+                ..expected
+            })
+            .is_err());
+
+        let found = Rc::new(RefCell::new(None));
+        discord.lobby_search(
+            SearchQuery::new()
+                .filter_metadata("rank", Comparison::GreaterThan, &-100_i32)
+                .unwrap(),
+            {
+                let found = found.clone();
+                move |discord, res: Result<()>| {
+                    res.unwrap();
+                    *found.borrow_mut() = Some(discord.lobby_count());
+                }
+            },
+        );
+        run(&mut discord);
+
+        assert_eq!(*found.borrow(), Some(1));
+    }
+}
@@ -40,8 +40,10 @@ impl<'d, E> Discord<'d, E> {
     ) {
         debug_assert!((0..=100).contains(&percent_complete));
 
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("set_user_achievement", achievement_id),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.achievement_manager();
@@ -73,8 +75,10 @@ impl<'d, E> Discord<'d, E> {
     /// # Ok(()) }
     /// ```
     pub fn fetch_user_achievements(&self, callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>)) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("fetch_user_achievements"),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.achievement_manager();
@@ -97,8 +97,10 @@ impl<'d, E> Discord<'d, E> {
         activity: &Activity,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("update_activity"),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.activity_manager();
@@ -129,8 +131,10 @@ impl<'d, E> Discord<'d, E> {
     /// # Ok(()) }
     /// ```
     pub fn clear_activity(&self, callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>)) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("clear_activity"),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.activity_manager();
@@ -169,8 +173,10 @@ impl<'d, E> Discord<'d, E> {
         reply: RequestReply,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("send_request_reply", user_id),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.activity_manager();
@@ -221,8 +227,10 @@ impl<'d, E> Discord<'d, E> {
             content.to_mut().push('\0')
         }
 
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("send_invite", user_id),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.activity_manager();
@@ -271,8 +279,10 @@ impl<'d, E> Discord<'d, E> {
         user_id: UserID,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("accept_invite", user_id),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.activity_manager();
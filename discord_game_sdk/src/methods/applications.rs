@@ -67,8 +67,10 @@ impl<'d, E> Discord<'d, E> {
     /// # Ok(()) }
     /// ```
     pub fn validate_or_exit(&self, callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>)) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("validate_or_exit"),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.application_manager();
@@ -101,6 +103,7 @@ impl<'d, E> Discord<'d, E> {
     /// ```
     pub fn oauth2_token(&self, callback: impl 'd + FnOnce(&Discord<'d, E>, Result<&OAuth2Token>)) {
         let (ptr, fun) = self.two_params(
+            operation!("oauth2_token"),
             move |discord, res: sys::EDiscordResult, token: *mut sys::DiscordOAuth2Token| {
                 callback(
                     discord,
@@ -136,6 +139,7 @@ impl<'d, E> Discord<'d, E> {
     /// ```
     pub fn app_ticket(&self, callback: impl 'd + FnOnce(&Discord<'d, E>, Result<&str>)) {
         let (ptr, fun) = self.two_params(
+            operation!("app_ticket"),
             move |discord, res: sys::EDiscordResult, string: *const u8| {
                 callback(
                     discord,
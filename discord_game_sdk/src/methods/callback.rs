@@ -1,11 +1,15 @@
-use crate::{utils, Discord};
+use crate::{operation::Operation, sys, utils, Discord};
 use std::{ffi::c_void, panic::UnwindSafe};
 
 impl<'d, E> Discord<'d, E> {
-    pub(crate) fn one_param<A: UnwindSafe>(
+    pub(crate) fn one_param(
         &self,
-        callback: impl 'd + FnOnce(&Discord<'d, E>, A),
-    ) -> (*mut c_void, Option<unsafe extern "C" fn(*mut c_void, A)>) {
+        operation: Operation,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, sys::EDiscordResult),
+    ) -> (
+        *mut c_void,
+        Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>,
+    ) {
         extern "C" fn one_param_from_c<F: FnOnce(A), A: UnwindSafe>(ptr: *mut c_void, a: A) {
             utils::abort_on_panic(|| {
                 // SAFETY:
@@ -28,13 +32,17 @@ impl<'d, E> Discord<'d, E> {
         }
 
         let dref = self.ref_copy();
-        one_param_align_types(move |a| callback(&*dref, a))
+        one_param_align_types(move |res| operation.complete(res, || callback(&*dref, res)))
     }
 
-    pub(crate) fn two_params<A: UnwindSafe, B: UnwindSafe>(
+    pub(crate) fn two_params<B: UnwindSafe>(
         &self,
-        callback: impl 'd + FnOnce(&Discord<'d, E>, A, B),
-    ) -> (*mut c_void, Option<unsafe extern "C" fn(*mut c_void, A, B)>) {
+        operation: Operation,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, sys::EDiscordResult, B),
+    ) -> (
+        *mut c_void,
+        Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult, B)>,
+    ) {
         extern "C" fn two_params_from_c<F: FnOnce(A, B), A: UnwindSafe, B: UnwindSafe>(
             ptr: *mut c_void,
             a: A,
@@ -57,15 +65,16 @@ impl<'d, E> Discord<'d, E> {
         }
 
         let dref = self.ref_copy();
-        two_params_align_types(move |a, b| callback(&*dref, a, b))
+        two_params_align_types(move |res, b| operation.complete(res, || callback(&*dref, res, b)))
     }
 
-    pub(crate) fn three_params<A: UnwindSafe, B: UnwindSafe, C: UnwindSafe>(
+    pub(crate) fn three_params<B: UnwindSafe, C: UnwindSafe>(
         &self,
-        callback: impl 'd + FnOnce(&Discord<'d, E>, A, B, C),
+        operation: Operation,
+        callback: impl 'd + FnOnce(&Discord<'d, E>, sys::EDiscordResult, B, C),
     ) -> (
         *mut c_void,
-        Option<unsafe extern "C" fn(*mut c_void, A, B, C)>,
+        Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult, B, C)>,
     ) {
         extern "C" fn three_params_from_c<
             F: FnOnce(A, B, C),
@@ -103,6 +112,8 @@ impl<'d, E> Discord<'d, E> {
         }
 
         let dref = self.ref_copy();
-        three_params_align_types(move |a, b, c| callback(&*dref, a, b, c))
+        three_params_align_types(move |res, b, c| {
+            operation.complete(res, || callback(&*dref, res, b, c))
+        })
     }
 }
@@ -1,10 +1,15 @@
 use crate::{
-    discord::{Discord, DiscordInner},
+    discord::{Discord, DiscordInner, LogSink},
+    discord_builder::Setup,
     events, sys,
     to_result::ToResult,
-    utils, ClientID, CreateFlags, EventHandler, Result,
+    utils, ClientID, CreateFlags, EventHandler, LogLevel, Managers, Result,
+};
+use std::{
+    cell::{Cell, UnsafeCell},
+    convert::TryFrom,
+    marker::PhantomData,
 };
-use std::{cell::UnsafeCell, convert::TryFrom, marker::PhantomData};
 
 /// # Core
 ///
@@ -30,7 +35,7 @@ use std::{cell::UnsafeCell, convert::TryFrom, marker::PhantomData};
 /// }
 /// # }
 /// ```
-impl<E> Discord<'_, E> {
+impl<'d, E> Discord<'d, E> {
     /// Calls [`with_create_flags`] with [`CreateFlags::Default`].
     ///
     /// [`with_create_flags`]: #method.with_create_flags
@@ -44,11 +49,86 @@ impl<E> Discord<'_, E> {
 
     /// Creates an instance of the main interface with the Discord Game SDK.
     ///
-    /// SDK log messages are forwarded to [`log`](https://docs.rs/log)
+    /// SDK log messages are forwarded to [`log`](https://docs.rs/log), or to
+    /// [`tracing`](https://docs.rs/tracing) with the `tracing` feature, see
+    /// [`set_log_level`](#method.set_log_level) and [`set_log_sink`](#method.set_log_sink)
+    ///
+    /// With the `dynamic` feature, the SDK library is loaded from the default library search
+    /// locations, see [`with_library`](#method.with_library).
     ///
     /// > [`Create` in official docs](https://discordapp.com/developers/docs/game-sdk/discord#create)  
     /// > [`SetLogHook` in official docs](https://discordapp.com/developers/docs/game-sdk/discord#setloghook)
     pub fn with_create_flags(client_id: ClientID, flags: CreateFlags) -> Result<Self>
+    where
+        E: EventHandler,
+    {
+        #[cfg(feature = "dynamic")]
+        return Self::with_library(sys::DEFAULT_LIBRARY_NAME, client_id, flags);
+
+        #[cfg(all(not(feature = "dynamic"), feature = "replay"))]
+        return Self::create(client_id, flags, None, Setup::default());
+
+        #[cfg(all(not(feature = "dynamic"), not(feature = "replay")))]
+        return Self::create(client_id, flags, Setup::default());
+    }
+
+    /// Loads the SDK library at `path`, then creates an instance of the main interface with it.
+    ///
+    /// `path` can be the file from the SDK archive as-is, no renaming is needed.
+    /// A bare file name is looked up in the platform's library search locations.
+    ///
+    /// Requires the `dynamic` feature.
+    ///
+    /// ## Errors
+    ///
+    /// If the library is missing or is not the Discord Game SDK,
+    /// [`Error::LibraryUnavailable`](enum.Error.html#variant.LibraryUnavailable) is returned,
+    /// the reason is logged.
+    ///
+    /// ```rust,no_run
+    /// # use discord_game_sdk::*;
+    /// # const DISCORD_CLIENT_ID: ClientID = 0;
+    /// match Discord::<()>::with_library("lib/discord_game_sdk.so", DISCORD_CLIENT_ID, CreateFlags::NoRequireDiscord) {
+    ///     Ok(discord) => { /* ... */ }
+    ///     Err(Error::LibraryUnavailable) => { /* play without Discord features */ }
+    ///     Err(err) => { /* ... */ }
+    /// }
+    /// ```
+    #[cfg(feature = "dynamic")]
+    pub fn with_library(
+        path: impl AsRef<std::ffi::OsStr>,
+        client_id: ClientID,
+        flags: CreateFlags,
+    ) -> Result<Self>
+    where
+        E: EventHandler,
+    {
+        let library = Self::open_library(path.as_ref())?;
+
+        #[cfg(feature = "replay")]
+        return Self::create(client_id, flags, library, None, Setup::default());
+
+        #[cfg(not(feature = "replay"))]
+        return Self::create(client_id, flags, library, Setup::default());
+    }
+
+    #[cfg(feature = "dynamic")]
+    pub(crate) fn open_library(path: &std::ffi::OsStr) -> Result<sys::DynamicLibrary> {
+        log::debug!("loading library from {:?}", path);
+
+        unsafe { sys::DynamicLibrary::open(path) }.map_err(|err| {
+            log::error!("could not load library from {:?}: {}", path, err);
+            crate::Error::LibraryUnavailable
+        })
+    }
+
+    pub(crate) fn create(
+        client_id: ClientID,
+        flags: CreateFlags,
+        #[cfg(feature = "dynamic")] library: sys::DynamicLibrary,
+        #[cfg(feature = "replay")] recording: Option<Box<dyn std::io::Write>>,
+        setup: Setup<'d>,
+    ) -> Result<Self>
     where
         E: EventHandler,
     {
@@ -72,6 +152,12 @@ impl<E> Discord<'_, E> {
             core: std::ptr::null_mut(),
             client_id,
             event_handler: UnsafeCell::new(None),
+            log_level: Cell::new(setup.log_level),
+            log_sink: UnsafeCell::new(setup.log_sink),
+            log_sink_generation: Cell::new(0),
+
+            #[cfg(feature = "dynamic")]
+            library: Some(library),
 
             achievement_events: events::achievement::<E>(),
             activity_events: events::activity::<E>(),
@@ -87,18 +173,39 @@ impl<E> Discord<'_, E> {
         let mut params = instance.create_params(flags.into());
 
         unsafe {
-            sys::DiscordCreate(
-                sys::DISCORD_VERSION,
-                &mut params,
-                &mut instance.inner_mut().core,
-            )
-            .to_result()?;
+            let mut core = std::ptr::null_mut();
+
+            let create = |params: &mut sys::DiscordCreateParams,
+                          core: &mut *mut sys::IDiscordCore| {
+                #[cfg(feature = "dynamic")]
+                return instance.inner().library.as_ref().unwrap().create(
+                    sys::DISCORD_VERSION,
+                    params,
+                    core,
+                );
+
+                #[cfg(not(feature = "dynamic"))]
+                return sys::DiscordCreate(sys::DISCORD_VERSION, params, core);
+            };
+
+            #[cfg(feature = "replay")]
+            let res = match recording {
+                Some(recording) => crate::replay::record(&mut params, &mut core, recording, create),
+                None => create(&mut params, &mut core),
+            };
+
+            #[cfg(not(feature = "replay"))]
+            let res = create(&mut params, &mut core);
+
+            res.to_result()?;
+
+            instance.inner_mut().core = core;
         }
 
         log::trace!("received pointer to {:p}", instance.inner().core);
 
         instance.set_log_hook();
-        instance.kickstart_managers();
+        instance.kickstart_managers(setup.managers);
 
         Ok(instance)
     }
@@ -155,49 +262,154 @@ impl<E> Discord<'_, E> {
         }
     }
 
-    fn set_log_hook(&self) {
-        extern "C" fn log_hook(
-            _: *mut std::ffi::c_void,
+    pub(crate) fn set_log_hook(&self) {
+        extern "C" fn log_hook<E>(
+            inner: *mut std::ffi::c_void,
             level: sys::EDiscordLogLevel,
             message: *const u8,
         ) {
             utils::abort_on_panic(|| {
-                let level = match level {
-                    sys::DiscordLogLevel_Error => log::Level::Error,
-                    sys::DiscordLogLevel_Warn => log::Level::Warn,
-                    sys::DiscordLogLevel_Info => log::Level::Info,
-                    sys::DiscordLogLevel_Debug => log::Level::Debug,
-                    _ => log::Level::Trace,
-                };
-
-                log::log!(level, "SDK: {}", unsafe { utils::charptr_to_str(message) });
+                let inner = unsafe { &*(inner as *const DiscordInner<'_, E>) };
+                let message = unsafe { utils::charptr_to_str(message) };
+
+                // Not every implementation of the SDK filters messages itself
+                if LogLevel::from(level) > inner.log_level.get() {
+                    return;
+                }
+
+                // SAFETY: `log_sink` is an `UnsafeCell`, the sink is taken out while it runs so
+                // that replacing it from within does not drop it
+                let sink = unsafe { (*inner.log_sink.get()).take() };
+                let generation = inner.log_sink_generation.get();
+
+                match sink {
+                    Some(mut sink) => {
+                        sink(level.into(), message);
+
+                        // Unless the sink was set or cleared from within
+                        if inner.log_sink_generation.get() == generation {
+                            unsafe { *inner.log_sink.get() = Some(sink) }
+                        }
+                    }
+
+                    None => forward_log(level, message),
+                }
             })
         }
 
         unsafe {
             (*self.inner().core).set_log_hook.unwrap()(
                 self.inner().core,
-                sys::DiscordLogLevel_Debug,
-                // SAFETY: this is never used
-                std::ptr::null_mut(),
-                Some(log_hook),
+                self.inner().log_level.get().into(),
+                self.0 as *mut std::ffi::c_void,
+                Some(log_hook::<E>),
             );
         }
     }
 
+    /// Sets the minimum level of the SDK log messages to forward, `LogLevel::Debug` by default
+    ///
+    /// > [`SetLogHook` in official docs](https://discordapp.com/developers/docs/game-sdk/discord#setloghook)
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// if !cfg!(debug_assertions) {
+    ///     discord.set_log_level(LogLevel::Warn);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn set_log_level(&self, level: LogLevel) {
+        self.inner().log_level.set(level);
+        self.set_log_hook();
+    }
+
+    /// The minimum level of the SDK log messages to forward
+    pub fn log_level(&self) -> LogLevel {
+        self.inner().log_level.get()
+    }
+
+    /// Sends SDK log messages to `sink` instead of [`log`](https://docs.rs/log)
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// discord.set_log_sink(|level, message| {
+    ///     if level == LogLevel::Error {
+    ///         eprintln!("Discord: {}", message);
+    ///     }
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn set_log_sink(&self, sink: impl 'd + FnMut(LogLevel, &str)) {
+        self.replace_log_sink(Some(Box::new(sink)))
+    }
+
+    /// Writes SDK log messages to `writer` instead of [`log`](https://docs.rs/log), one per line
+    ///
+    /// Lines look like `[warn] message`. Write failures are reported to `log`.
+    ///
+    /// ```rust,no_run
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> std::io::Result<()> {
+    /// discord.set_log_level(LogLevel::Debug);
+    /// discord.set_log_writer(std::fs::File::create("discord.log")?);
+    /// # Ok(()) }
+    /// ```
+    pub fn set_log_writer(&self, mut writer: impl 'd + std::io::Write) {
+        self.set_log_sink(move |level, message| {
+            if let Err(error) = writeln!(writer, "[{}] {}", level, message) {
+                log::warn!("could not write SDK log message: {}", error);
+            }
+        })
+    }
+
+    /// Sends SDK log messages to [`log`](https://docs.rs/log) again, dropping the sink
+    pub fn clear_log_sink(&self) {
+        self.replace_log_sink(None)
+    }
+
+    fn replace_log_sink(&self, sink: Option<LogSink<'d>>) {
+        let inner = self.inner();
+        inner
+            .log_sink_generation
+            .set(inner.log_sink_generation.get().wrapping_add(1));
+
+        // SAFETY: `log_sink` is an `UnsafeCell`, see `set_log_hook`
+        drop(unsafe { std::mem::replace(&mut *inner.log_sink.get(), sink) })
+    }
+
     // To start producing events, the SDK must initialize the related manager
-    // We initialize all managers that produce events to kickstart event passing
-    fn kickstart_managers(&self) {
+    // We initialize the managers that produce events to kickstart event passing
+    pub(crate) fn kickstart_managers(&self, managers: Managers) {
         unsafe {
-            self.achievement_manager();
-            self.activity_manager();
-            self.lobby_manager();
-            self.network_manager();
-            self.overlay_manager();
-            self.relationship_manager();
-            self.store_manager();
-            self.user_manager();
-            self.voice_manager();
+            if managers.contains(Managers::ACHIEVEMENTS) {
+                self.achievement_manager();
+            }
+            if managers.contains(Managers::ACTIVITIES) {
+                self.activity_manager();
+            }
+            if managers.contains(Managers::LOBBIES) {
+                self.lobby_manager();
+            }
+            if managers.contains(Managers::NETWORKING) {
+                self.network_manager();
+            }
+            if managers.contains(Managers::OVERLAY) {
+                self.overlay_manager();
+            }
+            if managers.contains(Managers::RELATIONSHIPS) {
+                self.relationship_manager();
+            }
+            if managers.contains(Managers::STORE) {
+                self.store_manager();
+            }
+            if managers.contains(Managers::USERS) {
+                self.user_manager();
+            }
+            if managers.contains(Managers::VOICE) {
+                self.voice_manager();
+            }
         }
     }
 
@@ -267,3 +479,105 @@ impl<E> Discord<'_, E> {
         (*self.inner().core).get_voice_manager.unwrap()(self.inner().core)
     }
 }
+
+fn forward_log(level: sys::EDiscordLogLevel, message: &str) {
+    #[cfg(feature = "tracing")]
+    {
+        macro_rules! sdk_event {
+            ($level:ident) => {
+                tracing::event!(
+                    tracing::Level::$level,
+                    sdk_level = level,
+                    "SDK: {}",
+                    message
+                )
+            };
+        }
+
+        match level {
+            sys::DiscordLogLevel_Error => sdk_event!(ERROR),
+            sys::DiscordLogLevel_Warn => sdk_event!(WARN),
+            sys::DiscordLogLevel_Info => sdk_event!(INFO),
+            sys::DiscordLogLevel_Debug => sdk_event!(DEBUG),
+            _ => sdk_event!(TRACE),
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    {
+        let level = match level {
+            sys::DiscordLogLevel_Error => log::Level::Error,
+            sys::DiscordLogLevel_Warn => log::Level::Warn,
+            sys::DiscordLogLevel_Info => log::Level::Info,
+            sys::DiscordLogLevel_Debug => log::Level::Debug,
+            _ => log::Level::Trace,
+        };
+
+        log::log!(level, "SDK: {}", message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::fixtures::{log, run},
+        Discord, LogLevel,
+    };
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    #[test]
+    fn log_messages_reach_the_sink_above_the_level() {
+        let messages = Rc::new(RefCell::new(Vec::new()));
+
+        for &level in &[LogLevel::Warn, LogLevel::Info] {
+            let discord = &mut Discord::<()>::mock();
+            let messages = messages.clone();
+
+            discord.set_log_level(level);
+            discord.set_log_sink(move |level, message| {
+                messages.borrow_mut().push((level, message.to_string()))
+            });
+            run(discord);
+        }
+
+        assert_eq!(
+            *messages.borrow(),
+            vec![(LogLevel::Info, "connected to mock client".to_string())]
+        );
+    }
+
+    #[test]
+    fn log_sink_can_clear_itself() {
+        let discord = Discord::<()>::mock();
+        let calls = Rc::new(Cell::new(0));
+        let discord_ptr = &discord as *const Discord<'_, ()>;
+
+        discord.set_log_sink({
+            let calls = calls.clone();
+            move |_, _| {
+                calls.set(calls.get() + 1);
+                unsafe { (*discord_ptr).clear_log_sink() }
+            }
+        });
+
+        log(&discord, "first");
+        log(&discord, "second");
+
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[cfg(feature = "dynamic")]
+    #[test]
+    fn missing_library_is_unavailable() {
+        let res = Discord::<()>::with_library(
+            "/nonexistent/discord_game_sdk.so",
+            0,
+            crate::CreateFlags::NoRequireDiscord,
+        );
+
+        assert_eq!(res.err(), Some(crate::Error::LibraryUnavailable));
+    }
+}
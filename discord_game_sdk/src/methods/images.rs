@@ -34,6 +34,7 @@ impl<'d, E> Discord<'d, E> {
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<ImageHandle>),
     ) {
         let (ptr, fun) = self.two_params(
+            operation!("fetch_image", image_id = handle.id()),
             move |discord, res: sys::EDiscordResult, image_handle: sys::DiscordImageHandle| {
                 callback(discord, res.to_result().map(|()| ImageHandle(image_handle)))
             },
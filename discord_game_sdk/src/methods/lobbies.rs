@@ -1,6 +1,6 @@
 use crate::{
     iter, sys, to_result::ToResult, utils, Discord, Lobby, LobbyID, LobbyMemberTransaction,
-    LobbyTransaction, NetworkChannelID, Reliability, Result, SearchQuery, UserID,
+    LobbyTransaction, NetworkChannelID, Reliability, Result, SearchQuery, User, UserID,
 };
 use std::{
     borrow::Cow,
@@ -43,6 +43,7 @@ impl<'d, E> Discord<'d, E> {
         }
 
         let (ptr, fun) = self.two_params(
+            operation!("create_lobby"),
             move |discord, res: sys::EDiscordResult, lobby: *mut sys::DiscordLobby| {
                 callback(
                     discord,
@@ -78,8 +79,10 @@ impl<'d, E> Discord<'d, E> {
             return callback(self, Err(e));
         }
 
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("update_lobby", lobby_id),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe { (*mgr).update_lobby.unwrap()(mgr, lobby_id, tx, ptr, fun) }
     }
@@ -92,8 +95,10 @@ impl<'d, E> Discord<'d, E> {
         lobby_id: LobbyID,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("delete_lobby", lobby_id),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.lobby_manager();
@@ -123,6 +128,7 @@ impl<'d, E> Discord<'d, E> {
         }
 
         let (ptr, fun) = self.two_params(
+            operation!("connect_lobby", lobby_id),
             move |discord, res: sys::EDiscordResult, lobby: *mut sys::DiscordLobby| {
                 callback(
                     discord,
@@ -165,6 +171,7 @@ impl<'d, E> Discord<'d, E> {
         }
 
         let (ptr, fun) = self.two_params(
+            operation!("connect_lobby_with_activity_secret"),
             move |discord, res: sys::EDiscordResult, lobby: *mut sys::DiscordLobby| {
                 callback(
                     discord,
@@ -194,8 +201,10 @@ impl<'d, E> Discord<'d, E> {
         lobby_id: LobbyID,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("disconnect_lobby", lobby_id),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.lobby_manager();
@@ -369,8 +378,10 @@ impl<'d, E> Discord<'d, E> {
             return callback(self, Err(e));
         }
 
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("update_member", lobby_id, user_id),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe { (*mgr).update_member.unwrap()(mgr, lobby_id, user_id, tx, ptr, fun) }
     }
@@ -431,6 +442,53 @@ impl<'d, E> Discord<'d, E> {
         ))
     }
 
+    /// Returns the user information of a lobby member.
+    ///
+    /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#getmemberuser)
+    pub fn lobby_member_user(&self, lobby_id: LobbyID, user_id: UserID) -> Result<User> {
+        let mut user = User(sys::DiscordUser::default());
+
+        unsafe {
+            let mgr = self.lobby_manager();
+
+            (*mgr).get_member_user.unwrap()(mgr, lobby_id, user_id, &mut user.0).to_result()?;
+        }
+
+        Ok(user)
+    }
+
+    /// Returns an `Iterator` over the user IDs and user information of the members of a lobby.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>, lobby_id: LobbyID) -> Result<()> {
+    /// for member in discord.iter_lobby_members(lobby_id)? {
+    ///     let (user_id, user) = member?;
+    ///     println!("{}: {}#{}", user_id, user.username(), user.discriminator());
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn iter_lobby_members(
+        &self,
+        lobby_id: LobbyID,
+    ) -> Result<
+        impl '_
+            + DoubleEndedIterator<Item = Result<(UserID, User)>>
+            + ExactSizeIterator
+            + std::iter::FusedIterator
+            + std::fmt::Debug,
+    > {
+        Ok(iter::Collection::new(
+            Box::new(move |i| {
+                let discord = self.ref_copy();
+                let user_id = discord.lobby_member_id_at(lobby_id, i)?;
+
+                Ok((user_id, discord.lobby_member_user(lobby_id, user_id)?))
+            }),
+            self.lobby_member_count(lobby_id)?,
+        ))
+    }
+
     /// Returns member metadata value for a given key.
     ///
     /// ## Performance
@@ -571,8 +629,10 @@ impl<'d, E> Discord<'d, E> {
 
         debug_assert!(u32::try_from(buffer.len()).is_ok());
 
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("send_lobby_message", lobby_id),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.lobby_manager();
@@ -615,8 +675,10 @@ impl<'d, E> Discord<'d, E> {
             return callback(self, Err(e));
         }
 
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("lobby_search"),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe { (*mgr).search.unwrap()(mgr, tx, ptr, fun) }
     }
@@ -690,8 +752,10 @@ impl<'d, E> Discord<'d, E> {
         lobby_id: LobbyID,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("connect_lobby_voice", lobby_id),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.lobby_manager();
@@ -708,8 +772,10 @@ impl<'d, E> Discord<'d, E> {
         lobby_id: LobbyID,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("disconnect_lobby_voice", lobby_id),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.lobby_manager();
@@ -801,3 +867,57 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::fixtures::{create_lobby, run},
+        Discord, Error, LobbyKind, LobbyTransaction, Result, SearchQuery,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn lobbies_are_shared_between_create_and_search() {
+        let mut discord = Discord::<()>::mock();
+
+        let lobby_id = create_lobby(
+            &mut discord,
+            LobbyTransaction::new()
+                .kind(LobbyKind::Public)
+                .capacity(4)
+                .add_metadata("mode".to_string(), "ranked".to_string()),
+        )
+        .id();
+
+        let lobby = discord.lobby(lobby_id).unwrap();
+        assert_eq!(lobby.capacity(), 4);
+        assert_eq!(discord.lobby_member_count(lobby_id).unwrap(), 1);
+        assert_eq!(discord.lobby_metadata(lobby_id, "mode").unwrap(), "ranked");
+
+        let members: Vec<_> = discord
+            .iter_lobby_members(lobby_id)
+            .unwrap()
+            .map(|member| member.unwrap())
+            .collect();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].0, 1);
+        assert_eq!(members[0].1.username(), "mock");
+        assert_eq!(
+            discord.lobby_member_user(lobby_id, 2).err(),
+            Some(Error::NotFound)
+        );
+
+        let searched = Rc::new(RefCell::new(None));
+
+        discord.lobby_search(SearchQuery::new().limit(10), {
+            let searched = searched.clone();
+            move |_, res: Result<()>| *searched.borrow_mut() = Some(res)
+        });
+
+        run(&mut discord);
+
+        assert_eq!(*searched.borrow(), Some(Ok(())));
+        assert_eq!(discord.lobby_count(), 1);
+        assert_eq!(discord.lobby_id_at(0).unwrap(), lobby_id);
+    }
+}
@@ -83,8 +83,10 @@ impl<'d, E> Discord<'d, E> {
         opened: bool,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("set_overlay_opened"),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.overlay_manager();
@@ -113,8 +115,10 @@ impl<'d, E> Discord<'d, E> {
         action: Action,
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>),
     ) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("open_invite_overlay"),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.overlay_manager();
@@ -155,8 +159,10 @@ impl<'d, E> Discord<'d, E> {
             code.to_mut().push('\0')
         }
 
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("open_guild_invite_overlay"),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.overlay_manager();
@@ -182,8 +188,10 @@ impl<'d, E> Discord<'d, E> {
     /// # Ok(()) }
     /// ```
     pub fn open_voice_settings(&self, callback: impl 'd + FnOnce(&Discord<'d, E>, Result<()>)) {
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("open_voice_settings"),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.overlay_manager();
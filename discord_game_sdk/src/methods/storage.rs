@@ -1,4 +1,4 @@
-use crate::{iter, sys, to_result::ToResult, utils, Discord, FileStat, Result};
+use crate::{iter, sys, to_result::ToResult, utils, Discord, FileStat, Result, StorageFile};
 use std::{
     borrow::Cow,
     convert::{TryFrom, TryInto},
@@ -97,6 +97,7 @@ impl<'d, E> Discord<'d, E> {
         }
 
         let (ptr, fun) = self.three_params(
+            operation!("read_file_async"),
             move |discord, res: sys::EDiscordResult, data: *mut u8, data_len: u32| {
                 callback(
                     discord,
@@ -147,6 +148,7 @@ impl<'d, E> Discord<'d, E> {
         }
 
         let (ptr, fun) = self.three_params(
+            operation!("read_file_async_partial"),
             move |discord, res: sys::EDiscordResult, data: *mut u8, data_len: u32| {
                 callback(
                     discord,
@@ -249,8 +251,10 @@ impl<'d, E> Discord<'d, E> {
 
         debug_assert!(u32::try_from(buffer.len()).is_ok());
 
-        let (ptr, fun) = self
-            .one_param(move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()));
+        let (ptr, fun) = self.one_param(
+            operation!("write_file_async"),
+            move |discord, res: sys::EDiscordResult| callback(discord, res.to_result()),
+        );
 
         unsafe {
             let mgr = self.storage_manager();
@@ -268,6 +272,68 @@ impl<'d, E> Discord<'d, E> {
         }
     }
 
+    /// Opens an existing file for reading, seeking and writing, see
+    /// [`StorageFile`](struct.StorageFile.html).
+    ///
+    /// The whole file is read synchronously.
+    ///
+    /// ## Performance
+    ///
+    /// A nul byte will be appended to `filename` if one is not present.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> Result<()> {
+    /// let mut file = discord.open_file("profile_1.save\0")?;
+    /// // e.g. `bincode::deserialize_from(&mut file)`
+    /// # Ok(()) }
+    /// ```
+    pub fn open_file<'s>(
+        &self,
+        filename: impl Into<Cow<'s, str>>,
+    ) -> Result<StorageFile<'_, 'd, E>> {
+        let mut filename = filename.into().into_owned();
+
+        if !filename.ends_with('\0') {
+            filename.push('\0')
+        }
+
+        let size = self.file_stat(filename.as_str())?.size();
+        let mut contents = vec![0; size.try_into().unwrap_or(usize::MAX)];
+
+        let read = self.read_file(filename.as_str(), &mut contents)?;
+        contents.truncate(read.try_into().unwrap_or(usize::MAX));
+
+        Ok(StorageFile::new(self, filename, contents, false))
+    }
+
+    /// Creates an empty file for writing, see [`StorageFile`](struct.StorageFile.html).
+    ///
+    /// Nothing is written before the file is flushed, an existing file is then replaced.
+    ///
+    /// ## Performance
+    ///
+    /// A nul byte will be appended to `filename` if one is not present.
+    ///
+    /// ```rust
+    /// # use discord_game_sdk::*;
+    /// # fn example(discord: Discord<'_, ()>) -> std::io::Result<()> {
+    /// let mut file = discord.create_file("profile_1.save\0");
+    /// // e.g. `bincode::serialize_into(&mut file, &profile)`
+    /// file.close()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn create_file<'s>(&self, filename: impl Into<Cow<'s, str>>) -> StorageFile<'_, 'd, E> {
+        let mut filename = filename.into().into_owned();
+
+        if !filename.ends_with('\0') {
+            filename.push('\0')
+        }
+
+        // Even when nothing is written, flushing must replace an existing file
+        StorageFile::new(self, filename, Vec::new(), true)
+    }
+
     /// Deletes written data for the given key.
     ///
     /// ## Performance
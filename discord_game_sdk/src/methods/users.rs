@@ -58,6 +58,7 @@ impl<'d, E> Discord<'d, E> {
         callback: impl 'd + FnOnce(&Discord<'d, E>, Result<&User>),
     ) {
         let (ptr, fun) = self.two_params(
+            operation!("user", user_id),
             move |discord, res: sys::EDiscordResult, user: *mut sys::DiscordUser| {
                 callback(
                     discord,
@@ -0,0 +1,268 @@
+use crate::{sys, Error};
+
+/// Faults injected by a mocked SDK, see
+/// [`MockConfig::with_faults`](struct.MockConfig.html#method.with_faults)
+///
+/// Methods are named after the SDK's functions, which are also the names of most methods of
+/// [`Discord`](struct.Discord.html): `"update_activity"`, `"create_lobby"`, etc.
+/// Names shared by several managers can be prefixed with theirs: `"storage.count"`,
+/// `"relationships.count"`. Managers are named `achievements`, `activities`, `applications`,
+/// `images`, `lobbies`, `networking`, `overlay`, `relationships`, `storage`, `store`, `users`
+/// and `voice`.
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # fn example() -> Result<()> {
+/// let mut faults = MockFaults::new();
+/// faults
+///     .with_error_times("update_activity", Error::RateLimited, 1)
+///     .with_callback_delay("create_lobby", 3)
+///     .with_not_running_after(100);
+///
+/// let mut config = MockConfig::new();
+/// config.with_faults(faults);
+///
+/// let mut discord = Discord::<()>::mock_with(&config);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MockFaults {
+    pub(crate) errors: Vec<MethodError>,
+    pub(crate) delays: Vec<(String, u32)>,
+    pub(crate) dropped_network_messages: u32,
+    pub(crate) reordered_events: bool,
+    pub(crate) not_running_after: Option<u32>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct MethodError {
+    pub(crate) method: String,
+    pub(crate) result: sys::EDiscordResult,
+    pub(crate) times: Option<u32>,
+}
+
+impl MockFaults {
+    /// Creates a plan without faults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes every call to `method` fail with `error`
+    ///
+    /// Methods with a callback call back with `error` during the next call to
+    /// [`run_callbacks`](struct.Discord.html#method.run_callbacks), without any effect.
+    /// Methods the SDK does not let fail, such as `lobby_count`, leave their results untouched.
+    ///
+    /// `Error::LibraryUnavailable` is only returned when creating an instance, calls fail with
+    /// `Error::Internal` instead. `Error::InvalidString` is found before calling the SDK, calls
+    /// fail with `Error::InvalidPayload` instead.
+    pub fn with_error(&mut self, method: &str, error: Error) -> &mut Self {
+        self.errors.push(MethodError {
+            method: method.to_string(),
+            result: result(error),
+            times: None,
+        });
+        self
+    }
+
+    /// Makes the next `times` calls to `method` fail with `error`, the ones after succeed
+    pub fn with_error_times(&mut self, method: &str, error: Error, times: u32) -> &mut Self {
+        self.errors.push(MethodError {
+            method: method.to_string(),
+            result: result(error),
+            times: Some(times),
+        });
+        self
+    }
+
+    /// Completes calls to `method`, along with their callbacks and events, `ticks` calls to
+    /// [`run_callbacks`](struct.Discord.html#method.run_callbacks) later than usual
+    pub fn with_callback_delay(&mut self, method: &str, ticks: u32) -> &mut Self {
+        self.delays.push((method.to_string(), ticks));
+        self
+    }
+
+    /// Drops one network message out of every `every`, sent to peers and lobby members alike
+    ///
+    /// Sending still succeeds, the message is never received. `0` drops none, `1` drops all.
+    pub fn with_dropped_network_messages(&mut self, every: u32) -> &mut Self {
+        self.dropped_network_messages = every;
+        self
+    }
+
+    /// Fires the events of each call to
+    /// [`run_callbacks`](struct.Discord.html#method.run_callbacks) in reverse order
+    pub fn with_reordered_events(&mut self) -> &mut Self {
+        self.reordered_events = true;
+        self
+    }
+
+    /// Simulates Discord closing after `ticks` calls to
+    /// [`run_callbacks`](struct.Discord.html#method.run_callbacks)
+    ///
+    /// From then on, `run_callbacks` and every call fail with `Error::NotRunning`, and nothing
+    /// is called back until the instance is dropped.
+    pub fn with_not_running_after(&mut self, ticks: u32) -> &mut Self {
+        self.not_running_after = Some(ticks);
+        self
+    }
+}
+
+/// Whether `method`, as configured, designates the SDK function `function` of `manager`
+pub(crate) fn designates(method: &str, manager: &str, function: &str) -> bool {
+    match method.find('.') {
+        Some(dot) => &method[..dot] == manager && &method[dot + 1..] == function,
+        None => method == function,
+    }
+}
+
+fn result(error: Error) -> sys::EDiscordResult {
+    use Error::*;
+
+    match error {
+        ServiceUnavailable => sys::DiscordResult_ServiceUnavailable,
+        InvalidVersion => sys::DiscordResult_InvalidVersion,
+        LockFailed => sys::DiscordResult_LockFailed,
+        Internal | LibraryUnavailable => sys::DiscordResult_InternalError,
+        InvalidPayload | InvalidString(_) => sys::DiscordResult_InvalidPayload,
+        InvalidCommand => sys::DiscordResult_InvalidCommand,
+        InvalidPermissions => sys::DiscordResult_InvalidPermissions,
+        NotFetched => sys::DiscordResult_NotFetched,
+        NotFound => sys::DiscordResult_NotFound,
+        Conflict => sys::DiscordResult_Conflict,
+        InvalidSecret => sys::DiscordResult_InvalidSecret,
+        InvalidJoinSecret => sys::DiscordResult_InvalidJoinSecret,
+        NoEligibleActivity => sys::DiscordResult_NoEligibleActivity,
+        InvalidInvite => sys::DiscordResult_InvalidInvite,
+        NotAuthenticated => sys::DiscordResult_NotAuthenticated,
+        InvalidAccessToken => sys::DiscordResult_InvalidAccessToken,
+        ApplicationMismatch => sys::DiscordResult_ApplicationMismatch,
+        InvalidDataUrl => sys::DiscordResult_InvalidDataUrl,
+        InvalidBase64 => sys::DiscordResult_InvalidBase64,
+        NotFiltered => sys::DiscordResult_NotFiltered,
+        LobbyFull => sys::DiscordResult_LobbyFull,
+        InvalidLobbySecret => sys::DiscordResult_InvalidLobbySecret,
+        InvalidFilename => sys::DiscordResult_InvalidFilename,
+        InvalidFileSize => sys::DiscordResult_InvalidFileSize,
+        InvalidEntitlement => sys::DiscordResult_InvalidEntitlement,
+        NotInstalled => sys::DiscordResult_NotInstalled,
+        NotRunning => sys::DiscordResult_NotRunning,
+        InsufficientBuffer => sys::DiscordResult_InsufficientBuffer,
+        PurchaseCanceled => sys::DiscordResult_PurchaseCanceled,
+        InvalidGuild => sys::DiscordResult_InvalidGuild,
+        InvalidEvent => sys::DiscordResult_InvalidEvent,
+        InvalidChannel => sys::DiscordResult_InvalidChannel,
+        InvalidOrigin => sys::DiscordResult_InvalidOrigin,
+        RateLimited => sys::DiscordResult_RateLimited,
+        OAuth2 => sys::DiscordResult_OAuth2Error,
+        SelectChannelTimeout => sys::DiscordResult_SelectChannelTimeout,
+        GetGuildTimeout => sys::DiscordResult_GetGuildTimeout,
+        SelectVoiceForceRequired => sys::DiscordResult_SelectVoiceForceRequired,
+        CaptureShortcutAlreadyListening => sys::DiscordResult_CaptureShortcutAlreadyListening,
+        UnauthorizedForAchievement => sys::DiscordResult_UnauthorizedForAchievement,
+        InvalidGiftCode => sys::DiscordResult_InvalidGiftCode,
+        Purchase => sys::DiscordResult_PurchaseError,
+        TransactionAborted => sys::DiscordResult_TransactionAborted,
+        Undefined(result) => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::fixtures::{faulty, queued},
+        Event, Reliability, SearchQuery,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn faults_are_injected_as_planned() {
+        let mut faults = MockFaults::new();
+        faults
+            .with_error_times("clear_activity", Error::RateLimited, 1)
+            .with_error("storage.write", Error::InvalidFileSize)
+            .with_callback_delay("lobbies.search", 2)
+            .with_dropped_network_messages(2)
+            .with_reordered_events()
+            .with_not_running_after(5);
+
+        let mut discord = queued(&faulty(faults));
+
+        discord.run_callbacks().unwrap();
+
+        let events: Vec<_> = discord.drain_events().collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::NetworkRouteUpdate {
+                    route: "mock-route-1".to_string()
+                },
+                Event::RelationshipsRefresh,
+                Event::CurrentUserUpdate,
+            ]
+        );
+
+        let cleared = Rc::new(RefCell::new(Vec::new()));
+        for _ in 0..2 {
+            let cleared = cleared.clone();
+            discord.clear_activity(move |_, res| cleared.borrow_mut().push(res));
+        }
+
+        assert_eq!(
+            discord.write_file("save.dat", b"level 2").err(),
+            Some(Error::InvalidFileSize)
+        );
+
+        let peer_id = discord.peer_id();
+        discord.open_peer(peer_id, "mock-route-1").unwrap();
+        discord
+            .open_channel(peer_id, 0, Reliability::Reliable)
+            .unwrap();
+        for i in 0..4 {
+            discord.send_message(peer_id, 0, [i]).unwrap();
+        }
+
+        discord.run_callbacks().unwrap();
+
+        assert_eq!(*cleared.borrow(), vec![Err(Error::RateLimited), Ok(())]);
+
+        let received: Vec<_> = discord
+            .drain_events()
+            .filter_map(|event| match event {
+                Event::NetworkMessage { data, .. } => Some(data),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(received, vec![vec![2], vec![0]]);
+
+        let searched = Rc::new(RefCell::new(None));
+        discord.lobby_search(&SearchQuery::new(), {
+            let searched = searched.clone();
+            move |_, res| *searched.borrow_mut() = Some(res)
+        });
+
+        discord.run_callbacks().unwrap();
+        discord.run_callbacks().unwrap();
+        assert_eq!(*searched.borrow(), None);
+
+        discord.run_callbacks().unwrap();
+        assert_eq!(*searched.borrow(), Some(Ok(())));
+
+        let cleared = Rc::new(RefCell::new(None));
+        discord.clear_activity({
+            let cleared = cleared.clone();
+            move |_, res| *cleared.borrow_mut() = Some(res)
+        });
+
+        assert_eq!(discord.run_callbacks(), Err(Error::NotRunning));
+        assert_eq!(
+            discord.open_peer(peer_id, "route").err(),
+            Some(Error::NotRunning)
+        );
+        assert_eq!(*cleared.borrow(), None);
+
+        drop(discord);
+        assert_eq!(*cleared.borrow(), Some(Err(Error::TransactionAborted)));
+    }
+}
@@ -1,171 +1,453 @@
-use crate::sys;
-use std::ffi::c_void;
-
-const CORE: &sys::IDiscordCore = &sys::IDiscordCore {
-    destroy: {
-        unsafe extern "C" fn destroy(_: *mut sys::IDiscordCore) {
-            while let Some(closure) = STATE.as_mut().unwrap().queue.pop() {
-                closure()
-            }
+use crate::{
+    backend::{self, Backend, Interface, LogHook, Managers},
+    mock::{
+        faults::designates,
+        service::{Client, Inbox, Service},
+        MockConfig, MockFaults,
+    },
+    sys,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    ffi::{c_void, CStr},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+mod achievements;
+mod activities;
+mod applications;
+mod faults;
+mod images;
+mod lobbies;
+mod networking;
+mod overlay;
+mod relationships;
+mod storage;
+mod store;
+mod users;
+mod voice;
+
+pub(crate) use self::lobbies::Lobby;
+
+// SAFETY: `interface` must point to the `vtable` of an `Interface<T, Mock>` owned by a live `Mock`
+pub(crate) unsafe fn mock<'a, T>(interface: *mut T) -> &'a Mock {
+    backend::backend(interface)
+}
+
+struct Task {
+    run: Box<dyn FnOnce(&Mock)>,
+    abort: Box<dyn FnOnce()>,
+    delay: u32,
+    event: bool,
+}
+
+// The fault plan along with how far the session went
+struct Faults {
+    plan: MockFaults,
+    ticks: u32,
+    network_messages: u32,
+}
+
+impl Faults {
+    fn stopped(&self) -> bool {
+        matches!(self.plan.not_running_after, Some(ticks) if self.ticks >= ticks)
+    }
+}
+
+pub(crate) struct Mock {
+    core: Interface<sys::IDiscordCore, Mock>,
+    managers: Managers<Mock>,
+
+    pub(crate) params: sys::DiscordCreateParams,
+    log_hook: Cell<Option<(*mut c_void, LogHook)>>,
+    pub(crate) state: RefCell<State>,
+    queue: RefCell<VecDeque<Task>>,
+    faults: RefCell<Faults>,
+    method: Cell<Option<(&'static str, &'static str)>>,
+    service: Arc<Mutex<Service>>,
+    inbox: Inbox,
+}
+
+pub(crate) type Callback = Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>;
+
+#[derive(Default)]
+pub(crate) struct State {
+    pub(crate) next_id: i64,
+    pub(crate) ready: bool,
+
+    pub(crate) current_user: sys::DiscordUser,
+    pub(crate) premium_type: sys::EDiscordPremiumType,
+    pub(crate) user_flags: sys::EDiscordUserFlag,
+    pub(crate) users: Vec<sys::DiscordUser>,
+
+    pub(crate) locale: String,
+    pub(crate) branch: String,
+
+    pub(crate) achievements: Vec<sys::DiscordUserAchievement>,
+
+    pub(crate) activity: Option<sys::DiscordActivity>,
+
+    pub(crate) fetched_images: HashSet<(i64, u32)>,
+
+    pub(crate) search_results: Vec<sys::DiscordLobbyId>,
+
+    pub(crate) peer_id: sys::DiscordNetworkPeerId,
+    pub(crate) peers: HashMap<sys::DiscordNetworkPeerId, HashMap<u8, bool>>,
 
-            drop(STATE.take());
+    pub(crate) overlay_locked: bool,
+
+    pub(crate) relationships: Vec<sys::DiscordRelationship>,
+    pub(crate) filtered_relationships: Option<Vec<sys::DiscordRelationship>>,
+
+    pub(crate) files: BTreeMap<String, (Vec<u8>, u64)>,
+
+    pub(crate) skus: Vec<sys::DiscordSku>,
+    pub(crate) skus_fetched: bool,
+    pub(crate) entitlements: Vec<sys::DiscordEntitlement>,
+    pub(crate) entitlements_fetched: bool,
+
+    pub(crate) input_mode: sys::DiscordInputMode,
+    pub(crate) self_mute: bool,
+    pub(crate) self_deaf: bool,
+    pub(crate) local_mutes: HashMap<sys::DiscordUserId, bool>,
+    pub(crate) local_volumes: HashMap<sys::DiscordUserId, u8>,
+}
+
+impl State {
+    pub(crate) fn next_id(&mut self) -> i64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    pub(crate) fn user(&self, user_id: sys::DiscordUserId) -> Option<sys::DiscordUser> {
+        std::iter::once(&self.current_user)
+            .chain(&self.users)
+            .find(|user| user.id == user_id)
+            .copied()
+    }
+}
+
+impl Mock {
+    /// Runs `run` during the next call to `run_callbacks`, or `abort` if the instance is destroyed
+    /// before that
+    pub(crate) fn schedule(
+        &self,
+        run: impl 'static + FnOnce(&Mock),
+        abort: impl 'static + FnOnce(),
+    ) {
+        let delay = match self.method.get() {
+            Some((manager, name)) => self
+                .faults
+                .borrow()
+                .plan
+                .delays
+                .iter()
+                .rev()
+                .find(|(method, _)| designates(method, manager, name))
+                .map_or(0, |(_, ticks)| *ticks),
+            None => 0,
+        };
+
+        self.queue.borrow_mut().push_back(Task {
+            run: Box::new(run),
+            abort: Box::new(abort),
+            delay,
+            event: false,
+        });
+    }
+
+    /// Runs `run` during the next call to `run_callbacks`
+    pub(crate) fn emit(&self, run: impl 'static + FnOnce(&Mock)) {
+        self.schedule(run, || {});
+
+        if let Some(task) = self.queue.borrow_mut().back_mut() {
+            task.event = true;
         }
+    }
 
-        Some(destroy)
-    },
+    /// Calls back with the result of `run` during the next call to `run_callbacks`
+    pub(crate) fn respond(
+        &self,
+        callback_data: *mut c_void,
+        callback: Callback,
+        run: impl 'static + FnOnce(&Mock) -> sys::EDiscordResult,
+    ) {
+        self.schedule(
+            move |mock| {
+                let res = run(mock);
+                unsafe { callback.unwrap()(callback_data, res) }
+            },
+            move || unsafe {
+                callback.unwrap()(callback_data, sys::DiscordResult_TransactionAborted)
+            },
+        )
+    }
 
-    run_callbacks: {
-        unsafe extern "C" fn run_callbacks(_: *mut sys::IDiscordCore) -> sys::EDiscordResult {
-            while let Some(closure) = STATE.as_mut().unwrap().queue.pop() {
-                closure()
-            }
+    /// Lobbies and instances shared with other instances
+    pub(crate) fn service(&self) -> MutexGuard<'_, Service> {
+        self.service.lock().unwrap()
+    }
+
+    /// Looks up a user known to this instance or connected to the same service
+    pub(crate) fn user(&self, user_id: sys::DiscordUserId) -> Option<sys::DiscordUser> {
+        let user = self.state.borrow().user(user_id);
 
-            sys::DiscordResult_Ok
+        user.or_else(|| {
+            self.service()
+                .clients
+                .iter()
+                .map(|client| client.user)
+                .find(|user| user.id == user_id)
+        })
+    }
+
+    /// Whether `inbox` is the one of this instance
+    pub(crate) fn owns(&self, inbox: &Inbox) -> bool {
+        Arc::ptr_eq(&self.inbox, inbox)
+    }
+
+    pub(crate) fn log(&self, level: sys::EDiscordLogLevel, message: &str) {
+        if let Some((hook_data, hook)) = self.log_hook.get() {
+            let message = format!("{}\0", message);
+            unsafe { hook(hook_data, level, message.as_ptr()) }
         }
+    }
 
-        Some(run_callbacks)
-    },
+    /// Runs `run` as the method `name` of `manager`, what it schedules is delayed as planned
+    fn during<R>(&self, manager: &'static str, name: &'static str, run: impl FnOnce() -> R) -> R {
+        let outer = self.method.replace(Some((manager, name)));
+        let res = run();
+        self.method.set(outer);
 
-    get_achievement_manager: {
-        unsafe extern "C" fn get_achievement_manager(
-            _: *mut sys::IDiscordCore,
-        ) -> *mut sys::IDiscordAchievementManager {
-            ACHIEVEMENT_MANAGER as *const _ as *mut _
+        res
+    }
+
+    /// Result the method running should fail with, if any
+    fn fault(&self) -> Option<sys::EDiscordResult> {
+        let mut faults = self.faults.borrow_mut();
+
+        if faults.stopped() {
+            return Some(sys::DiscordResult_NotRunning);
         }
 
-        Some(get_achievement_manager)
-    },
+        let (manager, name) = self.method.get()?;
 
-    set_log_hook: None,
-    get_application_manager: None,
-    get_user_manager: None,
-    get_image_manager: None,
-    get_activity_manager: None,
-    get_relationship_manager: None,
-    get_lobby_manager: None,
-    get_network_manager: None,
-    get_overlay_manager: None,
-    get_storage_manager: None,
-    get_store_manager: None,
-    get_voice_manager: None,
-};
+        let error = faults
+            .plan
+            .errors
+            .iter_mut()
+            .find(|error| error.times != Some(0) && designates(&error.method, manager, name))?;
 
-const ACHIEVEMENT_MANAGER: &sys::IDiscordAchievementManager = &sys::IDiscordAchievementManager {
-    set_user_achievement: {
-        unsafe extern "C" fn set_user_achievements(
-            _: *mut sys::IDiscordAchievementManager,
-            achievement_id: sys::DiscordSnowflake,
-            percent_complete: u8,
-            callback_data: *mut c_void,
-            callback: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>,
-        ) {
-            STATE.as_mut().unwrap().queue.push(Box::new(move || {
-                let state = STATE.as_mut().unwrap();
-
-                for achievement in state.achievements.iter_mut() {
-                    if achievement.achievement_id == achievement_id {
-                        achievement.percent_complete = percent_complete;
-
-                        (*state.params.achievement_events)
-                            .on_user_achievement_update
-                            .unwrap()(
-                            state.params.event_data, achievement as *const _ as *mut _
-                        )
-                    }
-                }
-
-                callback.unwrap()(callback_data, sys::DiscordResult_Ok);
-            }))
+        if let Some(times) = &mut error.times {
+            *times -= 1;
         }
 
-        Some(set_user_achievements)
-    },
+        Some(error.result)
+    }
+
+    /// Whether the network message being sent is lost on the way
+    pub(crate) fn drop_network_message(&self) -> bool {
+        let mut faults = self.faults.borrow_mut();
 
-    fetch_user_achievements: {
-        unsafe extern "C" fn fetch_user_achievements(
-            _: *mut sys::IDiscordAchievementManager,
-            callback_data: *mut c_void,
-            callback: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult)>,
-        ) {
-            STATE.as_mut().unwrap().queue.push(Box::new(move || {
-                callback.unwrap()(callback_data, sys::DiscordResult_Ok);
-            }))
+        if faults.plan.dropped_network_messages == 0 {
+            return false;
         }
 
-        Some(fetch_user_achievements)
-    },
+        faults.network_messages += 1;
+
+        if faults.network_messages < faults.plan.dropped_network_messages {
+            return false;
+        }
 
-    get_user_achievement: {
-        unsafe extern "C" fn get_user_achievement(
-            _: *mut sys::IDiscordAchievementManager,
-            user_achievement_id: sys::DiscordSnowflake,
-            user_achievement: *mut sys::DiscordUserAchievement,
-        ) -> sys::EDiscordResult {
-            for achievement in &STATE.as_ref().unwrap().achievements {
-                if achievement.achievement_id == user_achievement_id {
-                    *user_achievement = *achievement;
-
-                    return sys::DiscordResult_Ok;
-                }
+        faults.network_messages = 0;
+        true
+    }
+
+    unsafe fn tick(&self) -> sys::EDiscordResult {
+        let reordered_events = {
+            let mut faults = self.faults.borrow_mut();
+
+            if faults.stopped() {
+                return sys::DiscordResult_NotRunning;
             }
 
-            sys::DiscordResult_NotFound
+            faults.ticks += 1;
+            faults.plan.reordered_events
+        };
+
+        // What other instances sent is received as events
+        let deliveries = std::mem::take(&mut *self.inbox.lock().unwrap());
+
+        for delivery in deliveries {
+            self.emit(delivery);
         }
 
-        Some(get_user_achievement)
-    },
+        let queued = std::mem::take(&mut *self.queue.borrow_mut());
+        let mut tasks = Vec::new();
 
-    count_user_achievements: {
-        unsafe extern "C" fn count_user_achievements(
-            _: *mut sys::IDiscordAchievementManager,
-            count: *mut i32,
-        ) {
-            *count = STATE.as_ref().unwrap().achievements.len() as i32;
+        for mut task in queued {
+            if task.delay == 0 {
+                tasks.push(task);
+            } else {
+                task.delay -= 1;
+                self.queue.borrow_mut().push_back(task);
+            }
         }
-        Some(count_user_achievements)
-    },
 
-    get_user_achievement_at: {
-        unsafe extern "C" fn get_user_achievement_at(
-            _: *mut sys::IDiscordAchievementManager,
-            index: i32,
-            user_achievement: *mut sys::DiscordUserAchievement,
-        ) -> sys::EDiscordResult {
-            *user_achievement = STATE.as_ref().unwrap().achievements[index as usize];
+        if reordered_events {
+            let events = (0..tasks.len())
+                .filter(|&i| tasks[i].event)
+                .collect::<Vec<_>>();
 
-            sys::DiscordResult_Ok
+            for i in 0..events.len() / 2 {
+                tasks.swap(events[i], events[events.len() - 1 - i]);
+            }
         }
 
-        Some(get_user_achievement_at)
-    },
-};
+        for task in tasks {
+            (task.run)(self);
+        }
 
-#[derive(Default)]
-struct State {
-    params: sys::DiscordCreateParams,
-    achievements: Vec<sys::DiscordUserAchievement>,
-    queue: Vec<Box<dyn FnOnce()>>,
+        sys::DiscordResult_Ok
+    }
+
+    unsafe fn abort_all(&self) {
+        loop {
+            let task = self.queue.borrow_mut().pop_front();
+
+            match task {
+                Some(task) => (task.abort)(),
+                None => break,
+            }
+        }
+    }
+}
+
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+pub(crate) unsafe fn ptr_to_string(ptr: *const u8) -> String {
+    CStr::from_ptr(ptr as *const _)
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub(crate) fn string_to_charbuf(charbuf: &mut [u8], value: &str) {
+    let len = value.len().min(charbuf.len() - 1);
+
+    charbuf[..len].copy_from_slice(&value.as_bytes()[..len]);
+    charbuf[len] = 0;
 }
 
-static mut STATE: Option<State> = None;
+impl Backend for Mock {
+    fn managers(&self) -> &Managers<Self> {
+        &self.managers
+    }
+
+    unsafe fn destroy(mock: *mut Self) {
+        (*mock).abort_all();
+        lobbies::disconnect(&*mock);
 
-pub(crate) unsafe fn create_mock(params: sys::DiscordCreateParams) -> *mut sys::IDiscordCore {
-    if STATE.is_some() {
-        panic!("can only hold one instance lol");
+        drop(Box::from_raw(mock));
     }
 
-    STATE = Some(State {
-        params,
-        achievements: (0..10)
-            .map(|achievement_id| sys::DiscordUserAchievement {
-                user_id: 0,
-                achievement_id,
-                percent_complete: 0,
-                unlocked_at: [0; 64],
-            })
+    unsafe fn run_callbacks(&self) -> sys::EDiscordResult {
+        self.tick()
+    }
+
+    unsafe fn set_log_hook(
+        &self,
+        _min_level: sys::EDiscordLogLevel,
+        hook: Option<(*mut c_void, LogHook)>,
+    ) {
+        self.log_hook.set(hook);
+    }
+}
+
+pub(crate) unsafe fn create_mock(
+    params: sys::DiscordCreateParams,
+    config: &MockConfig,
+) -> *mut sys::IDiscordCore {
+    let mut state = State {
+        next_id: 1_000,
+        current_user: config.current_user,
+        premium_type: config.premium_type,
+        user_flags: config.user_flags,
+        users: config.users.clone(),
+        locale: config.locale.clone(),
+        branch: config.branch.clone(),
+        achievements: config.achievements.clone(),
+        overlay_locked: true,
+        relationships: config.relationships.clone(),
+        files: config
+            .files
+            .iter()
+            .map(|(name, data)| (name.clone(), (data.clone(), now())))
             .collect(),
+        skus: config.skus.clone(),
+        entitlements: config.entitlements.clone(),
         ..Default::default()
+    };
+
+    state.peer_id = state.current_user.id as u64;
+
+    let service = match &config.service {
+        Some(service) => service.service.clone(),
+        None => Arc::default(),
+    };
+
+    let inbox = Inbox::default();
+
+    service.lock().unwrap().clients.push(Client {
+        user: state.current_user,
+        peer_id: state.peer_id,
+        inbox: inbox.clone(),
     });
 
-    CORE as *const _ as *mut _
+    let mock = Box::into_raw(Box::new(Mock {
+        core: backend::core(),
+        managers: Managers {
+            achievements: Interface::new(faults::vtable()),
+            activities: Interface::new(faults::vtable()),
+            applications: Interface::new(faults::vtable()),
+            images: Interface::new(faults::vtable()),
+            lobbies: Interface::new(faults::vtable()),
+            networking: Interface::new(faults::vtable()),
+            overlay: Interface::new(faults::vtable()),
+            relationships: Interface::new(faults::vtable()),
+            storage: Interface::new(faults::vtable()),
+            store: Interface::new(faults::vtable()),
+            users: Interface::new(faults::vtable()),
+            voice: Interface::new(faults::vtable()),
+        },
+
+        params,
+        log_hook: Cell::new(None),
+        state: RefCell::new(state),
+        queue: RefCell::new(VecDeque::new()),
+        faults: RefCell::new(Faults {
+            plan: config.faults.clone(),
+            ticks: 0,
+            network_messages: 0,
+        }),
+        method: Cell::new(None),
+        service,
+        inbox,
+    }));
+
+    (*mock).core.backend = mock;
+    (*mock).managers.attach(mock);
+
+    // The SDK connects to the client after being created, these are sent once it's done
+    users::connect(&*mock);
+    relationships::connect(&*mock);
+    networking::connect(&*mock);
+
+    (*mock).core.as_ptr()
 }
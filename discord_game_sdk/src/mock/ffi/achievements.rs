@@ -0,0 +1,129 @@
+use super::{mock, string_to_charbuf, Callback};
+use crate::sys;
+use std::{convert::TryFrom, ffi::c_void};
+
+pub(super) const MANAGER: sys::IDiscordAchievementManager = sys::IDiscordAchievementManager {
+    set_user_achievement: {
+        unsafe extern "C" fn set_user_achievement(
+            manager: *mut sys::IDiscordAchievementManager,
+            achievement_id: sys::DiscordSnowflake,
+            percent_complete: u8,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, move |mock| {
+                if percent_complete > 100 {
+                    return sys::DiscordResult_InvalidPayload;
+                }
+
+                let mut achievement = {
+                    let mut state = mock.state.borrow_mut();
+                    let user_id = state.current_user.id;
+
+                    let index = match state
+                        .achievements
+                        .iter()
+                        .position(|a| a.achievement_id == achievement_id)
+                    {
+                        Some(index) => index,
+                        None => {
+                            state.achievements.push(sys::DiscordUserAchievement {
+                                user_id,
+                                achievement_id,
+                                ..Default::default()
+                            });
+                            state.achievements.len() - 1
+                        }
+                    };
+
+                    let achievement = &mut state.achievements[index];
+                    achievement.percent_complete = percent_complete;
+
+                    if percent_complete == 100 && achievement.unlocked_at[0] == 0 {
+                        string_to_charbuf(&mut achievement.unlocked_at, &super::now().to_string());
+                    }
+
+                    *achievement
+                };
+
+                (*mock.params.achievement_events)
+                    .on_user_achievement_update
+                    .unwrap()(mock.params.event_data, &mut achievement);
+
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(set_user_achievement)
+    },
+
+    fetch_user_achievements: {
+        unsafe extern "C" fn fetch_user_achievements(
+            manager: *mut sys::IDiscordAchievementManager,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, |_| sys::DiscordResult_Ok)
+        }
+
+        Some(fetch_user_achievements)
+    },
+
+    count_user_achievements: {
+        unsafe extern "C" fn count_user_achievements(
+            manager: *mut sys::IDiscordAchievementManager,
+            count: *mut i32,
+        ) {
+            *count = i32::try_from(mock(manager).state.borrow().achievements.len()).unwrap();
+        }
+
+        Some(count_user_achievements)
+    },
+
+    get_user_achievement: {
+        unsafe extern "C" fn get_user_achievement(
+            manager: *mut sys::IDiscordAchievementManager,
+            user_achievement_id: sys::DiscordSnowflake,
+            user_achievement: *mut sys::DiscordUserAchievement,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            match state
+                .achievements
+                .iter()
+                .find(|a| a.achievement_id == user_achievement_id)
+            {
+                Some(achievement) => {
+                    *user_achievement = *achievement;
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(get_user_achievement)
+    },
+
+    get_user_achievement_at: {
+        unsafe extern "C" fn get_user_achievement_at(
+            manager: *mut sys::IDiscordAchievementManager,
+            index: i32,
+            user_achievement: *mut sys::DiscordUserAchievement,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            match usize::try_from(index)
+                .ok()
+                .and_then(|index| state.achievements.get(index))
+            {
+                Some(achievement) => {
+                    *user_achievement = *achievement;
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(get_user_achievement_at)
+    },
+};
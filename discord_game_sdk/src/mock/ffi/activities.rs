@@ -0,0 +1,122 @@
+use super::{mock, Callback};
+use crate::sys;
+use std::ffi::c_void;
+
+pub(super) const MANAGER: sys::IDiscordActivityManager = sys::IDiscordActivityManager {
+    register_command: {
+        unsafe extern "C" fn register_command(
+            _: *mut sys::IDiscordActivityManager,
+            _command: *const u8,
+        ) -> sys::EDiscordResult {
+            sys::DiscordResult_Ok
+        }
+
+        Some(register_command)
+    },
+
+    register_steam: {
+        unsafe extern "C" fn register_steam(
+            _: *mut sys::IDiscordActivityManager,
+            _steam_id: u32,
+        ) -> sys::EDiscordResult {
+            sys::DiscordResult_Ok
+        }
+
+        Some(register_steam)
+    },
+
+    update_activity: {
+        unsafe extern "C" fn update_activity(
+            manager: *mut sys::IDiscordActivityManager,
+            activity: *mut sys::DiscordActivity,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            let activity = *activity;
+
+            mock(manager).respond(callback_data, callback, move |mock| {
+                mock.state.borrow_mut().activity = Some(activity);
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(update_activity)
+    },
+
+    clear_activity: {
+        unsafe extern "C" fn clear_activity(
+            manager: *mut sys::IDiscordActivityManager,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, |mock| {
+                mock.state.borrow_mut().activity = None;
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(clear_activity)
+    },
+
+    send_request_reply: {
+        unsafe extern "C" fn send_request_reply(
+            manager: *mut sys::IDiscordActivityManager,
+            user_id: sys::DiscordUserId,
+            _reply: sys::EDiscordActivityJoinRequestReply,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, move |mock| {
+                match mock.user(user_id) {
+                    Some(_) => sys::DiscordResult_Ok,
+                    None => sys::DiscordResult_NotFound,
+                }
+            })
+        }
+
+        Some(send_request_reply)
+    },
+
+    send_invite: {
+        unsafe extern "C" fn send_invite(
+            manager: *mut sys::IDiscordActivityManager,
+            user_id: sys::DiscordUserId,
+            _type: sys::EDiscordActivityActionType,
+            _content: *const u8,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, move |mock| {
+                let state = mock.state.borrow();
+
+                if state.activity.is_none() {
+                    sys::DiscordResult_NoEligibleActivity
+                } else if mock.user(user_id).is_none() {
+                    sys::DiscordResult_NotFound
+                } else {
+                    sys::DiscordResult_Ok
+                }
+            })
+        }
+
+        Some(send_invite)
+    },
+
+    accept_invite: {
+        unsafe extern "C" fn accept_invite(
+            manager: *mut sys::IDiscordActivityManager,
+            user_id: sys::DiscordUserId,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, move |mock| {
+                match mock.user(user_id) {
+                    Some(_) => sys::DiscordResult_Ok,
+                    None => sys::DiscordResult_InvalidInvite,
+                }
+            })
+        }
+
+        Some(accept_invite)
+    },
+};
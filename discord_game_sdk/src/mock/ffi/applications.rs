@@ -0,0 +1,104 @@
+use super::{mock, string_to_charbuf, Callback};
+use crate::sys;
+use std::{convert::TryFrom, ffi::c_void};
+
+pub(super) const MANAGER: sys::IDiscordApplicationManager = sys::IDiscordApplicationManager {
+    validate_or_exit: {
+        unsafe extern "C" fn validate_or_exit(
+            manager: *mut sys::IDiscordApplicationManager,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, |_| sys::DiscordResult_Ok)
+        }
+
+        Some(validate_or_exit)
+    },
+
+    get_current_locale: {
+        unsafe extern "C" fn get_current_locale(
+            manager: *mut sys::IDiscordApplicationManager,
+            locale: *mut sys::DiscordLocale,
+        ) {
+            string_to_charbuf(&mut *locale, &mock(manager).state.borrow().locale);
+        }
+
+        Some(get_current_locale)
+    },
+
+    get_current_branch: {
+        unsafe extern "C" fn get_current_branch(
+            manager: *mut sys::IDiscordApplicationManager,
+            branch: *mut sys::DiscordBranch,
+        ) {
+            string_to_charbuf(&mut *branch, &mock(manager).state.borrow().branch);
+        }
+
+        Some(get_current_branch)
+    },
+
+    get_oauth2_token: {
+        unsafe extern "C" fn get_oauth2_token(
+            manager: *mut sys::IDiscordApplicationManager,
+            callback_data: *mut c_void,
+            callback: Option<
+                unsafe extern "C" fn(
+                    *mut c_void,
+                    sys::EDiscordResult,
+                    *mut sys::DiscordOAuth2Token,
+                ),
+            >,
+        ) {
+            mock(manager).schedule(
+                move |mock| {
+                    let mut token = sys::DiscordOAuth2Token::default();
+
+                    let user_id = mock.state.borrow().current_user.id;
+                    string_to_charbuf(&mut token.access_token, &format!("mock-token-{}", user_id));
+                    string_to_charbuf(&mut token.scopes, "identify");
+                    token.expires = i64::try_from(super::now()).unwrap() + 7 * 24 * 60 * 60;
+
+                    callback.unwrap()(callback_data, sys::DiscordResult_Ok, &mut token)
+                },
+                move || {
+                    callback.unwrap()(
+                        callback_data,
+                        sys::DiscordResult_TransactionAborted,
+                        &mut sys::DiscordOAuth2Token::default(),
+                    )
+                },
+            )
+        }
+
+        Some(get_oauth2_token)
+    },
+
+    get_ticket: {
+        unsafe extern "C" fn get_ticket(
+            manager: *mut sys::IDiscordApplicationManager,
+            callback_data: *mut c_void,
+            callback: Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult, *const u8)>,
+        ) {
+            mock(manager).schedule(
+                move |mock| {
+                    let ticket = format!(
+                        "mock-ticket-{}-{}\0",
+                        mock.params.client_id,
+                        mock.state.borrow().current_user.id
+                    );
+
+                    callback.unwrap()(callback_data, sys::DiscordResult_Ok, ticket.as_ptr())
+                },
+                move || {
+                    callback.unwrap()(
+                        callback_data,
+                        sys::DiscordResult_TransactionAborted,
+                        "\0".as_ptr(),
+                    )
+                },
+            )
+        }
+
+        Some(get_ticket)
+    },
+};
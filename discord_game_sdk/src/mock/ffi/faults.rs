@@ -0,0 +1,179 @@
+// Managers handed to the SDK user go through these tables first: they fail calls as the fault
+// plan says before handing the rest over to the managers of the mock, and note which method is
+// running so what it schedules can be delayed.
+
+use super::mock;
+use crate::{sys, tables::Pointer};
+use std::ffi::c_void;
+
+/// Table of the mock implementing the manager
+pub(super) trait Inner: Sized {
+    const INNER: Self;
+}
+
+/// Table failing calls before handing them to `Inner::INNER`
+pub(super) trait Faulty: Inner {
+    const FAULTY: Self;
+}
+
+pub(super) fn vtable<T: Faulty>() -> T {
+    T::FAULTY
+}
+
+/// Transactions and search queries are consumed by the SDK even when the call fails
+pub(super) trait Release {
+    unsafe fn release(self);
+}
+
+/// What a function returns when it fails
+trait Fails {
+    const FALLIBLE: bool;
+
+    fn failed(result: sys::EDiscordResult) -> Self;
+}
+
+impl Fails for sys::EDiscordResult {
+    const FALLIBLE: bool = true;
+
+    fn failed(result: sys::EDiscordResult) -> Self {
+        result
+    }
+}
+
+impl Fails for () {
+    const FALLIBLE: bool = false;
+
+    fn failed(_: sys::EDiscordResult) -> Self {}
+}
+
+macro_rules! has_callback {
+    () => {
+        false
+    };
+    ($callback:ident) => {
+        true
+    };
+}
+
+macro_rules! release_arg {
+    (handle $arg:ident: $ty:ty) => {
+        Release::release($arg)
+    };
+    ($kind:ident $arg:ident $([$len:ident])?: $ty:ty) => {};
+}
+
+// Callbacks of failed calls get nothing to read
+macro_rules! blank_arg {
+    (val $arg:ident: $ty:ty) => {
+        let $arg: $ty = std::mem::zeroed();
+    };
+    (ptr $arg:ident: $ty:ty) => {
+        let mut $arg = <<$ty as Pointer>::Target as Default>::default();
+        let $arg: $ty = &mut $arg;
+    };
+    (str $arg:ident: $ty:ty) => {
+        let $arg: $ty = b"\0".as_ptr();
+    };
+    (buf $arg:ident [$len:ident]: $ty:ty) => {
+        let $arg: $ty = std::ptr::null_mut();
+    };
+    (len $arg:ident: $ty:ty) => {
+        let $arg: $ty = 0;
+    };
+}
+
+macro_rules! faulty_methods {
+    ($(
+        $field:ident $iface:ident {
+            $(
+                fn $name:ident (
+                    $( $kind:ident $arg:ident $([$len:ident])? : $ty:ty ),*
+                )
+                $( => $callback:ident (
+                    $( $ckind:ident $carg:ident $([$clen:ident])? : $cty:ty ),*
+                ) )?
+                $( -> $ret:ty )?;
+            )*
+        } manual { $( $manual:ident ),* }
+    )*) => {
+        $(
+            impl Faulty for sys::$iface {
+                const FAULTY: Self = sys::$iface {
+                    $(
+                        $name: {
+                            #[allow(unused_variables)]
+                            unsafe extern "C" fn $name(
+                                interface: *mut sys::$iface,
+                                $( $arg: $ty, )*
+                                $(
+                                    callback_data: *mut c_void,
+                                    $callback: Option<
+                                        unsafe extern "C" fn(
+                                            *mut c_void,
+                                            sys::EDiscordResult
+                                            $(, $cty)*
+                                        )
+                                    >,
+                                )?
+                            ) -> ret!($($ret)?) {
+                                let mock = mock(interface);
+                                let fallible = <ret!($($ret)?) as Fails>::FALLIBLE
+                                    || has_callback!($($callback)?);
+
+                                mock.during(stringify!($field), stringify!($name), || {
+                                    let fault = if fallible { mock.fault() } else { None };
+
+                                    if let Some(result) = fault {
+                                        $( release_arg!($kind $arg $([$len])?: $ty); )*
+
+                                        $(
+                                            mock.schedule(
+                                                move |_| {
+                                                    $( blank_arg!($ckind $carg $([$clen])?: $cty); )*
+                                                    $callback.unwrap()(callback_data, result $(, $carg)*)
+                                                },
+                                                move || {
+                                                    $( blank_arg!($ckind $carg $([$clen])?: $cty); )*
+                                                    $callback.unwrap()(
+                                                        callback_data,
+                                                        sys::DiscordResult_TransactionAborted
+                                                        $(, $carg)*
+                                                    )
+                                                },
+                                            );
+                                        )?
+
+                                        return Fails::failed(result);
+                                    }
+
+                                    <sys::$iface as Inner>::INNER.$name.unwrap()(
+                                        interface
+                                        $(, $arg)*
+                                        $(, callback_data, $callback)?
+                                    )
+                                })
+                            }
+
+                            Some($name)
+                        },
+                    )*
+                    $( $manual: <Self as Inner>::INNER.$manual, )*
+                };
+            }
+        )*
+    };
+}
+
+sdk_methods!(faulty_methods);
+
+macro_rules! faulty_managers {
+    ($( $getter:ident $field:ident $iface:ident ),*) => {
+        $(
+            impl Inner for sys::$iface {
+                const INNER: Self = super::$field::MANAGER;
+            }
+        )*
+    };
+}
+
+sdk_managers!(faulty_managers);
@@ -0,0 +1,98 @@
+use super::mock;
+use crate::sys;
+use std::{convert::TryFrom, ffi::c_void};
+
+pub(super) const MANAGER: sys::IDiscordImageManager = sys::IDiscordImageManager {
+    fetch: {
+        unsafe extern "C" fn fetch(
+            manager: *mut sys::IDiscordImageManager,
+            handle: sys::DiscordImageHandle,
+            _refresh: bool,
+            callback_data: *mut c_void,
+            callback: Option<
+                unsafe extern "C" fn(*mut c_void, sys::EDiscordResult, sys::DiscordImageHandle),
+            >,
+        ) {
+            mock(manager).schedule(
+                move |mock| {
+                    let res = {
+                        let known = mock.user(handle.id).is_some();
+                        let mut state = mock.state.borrow_mut();
+
+                        if handle.type_ != sys::DiscordImageType_User || !known {
+                            sys::DiscordResult_NotFound
+                        } else if !(16..=256).contains(&handle.size)
+                            || !handle.size.is_power_of_two()
+                        {
+                            sys::DiscordResult_InvalidPayload
+                        } else {
+                            state.fetched_images.insert((handle.id, handle.size));
+                            sys::DiscordResult_Ok
+                        }
+                    };
+
+                    callback.unwrap()(callback_data, res, handle)
+                },
+                move || {
+                    callback.unwrap()(callback_data, sys::DiscordResult_TransactionAborted, handle)
+                },
+            )
+        }
+
+        Some(fetch)
+    },
+
+    get_dimensions: {
+        unsafe extern "C" fn get_dimensions(
+            manager: *mut sys::IDiscordImageManager,
+            handle: sys::DiscordImageHandle,
+            dimensions: *mut sys::DiscordImageDimensions,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            if !state.fetched_images.contains(&(handle.id, handle.size)) {
+                return sys::DiscordResult_NotFetched;
+            }
+
+            (*dimensions).width = handle.size;
+            (*dimensions).height = handle.size;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(get_dimensions)
+    },
+
+    get_data: {
+        unsafe extern "C" fn get_data(
+            manager: *mut sys::IDiscordImageManager,
+            handle: sys::DiscordImageHandle,
+            data: *mut u8,
+            data_length: u32,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            if !state.fetched_images.contains(&(handle.id, handle.size)) {
+                return sys::DiscordResult_NotFetched;
+            }
+
+            let expected = 4 * u64::from(handle.size) * u64::from(handle.size);
+
+            if u64::from(data_length) < expected {
+                return sys::DiscordResult_InsufficientBuffer;
+            }
+
+            // Every avatar is a single color derived from the user ID
+            let [r, g, b, ..] = handle.id.to_le_bytes();
+            let data = std::slice::from_raw_parts_mut(data, usize::try_from(expected).unwrap());
+
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.copy_from_slice(&[r, g, b, 0xff]);
+            }
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(get_data)
+    },
+};
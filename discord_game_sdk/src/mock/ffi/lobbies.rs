@@ -0,0 +1,1433 @@
+use super::{faults::Release, mock, ptr_to_string, string_to_charbuf, Callback, Mock};
+use crate::{mock::service::Service, sys};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    ffi::c_void,
+    mem::size_of,
+};
+
+pub(crate) struct Lobby {
+    pub(crate) lobby: sys::DiscordLobby,
+    pub(crate) metadata: BTreeMap<String, String>,
+    pub(crate) members: Vec<Member>,
+}
+
+pub(crate) struct Member {
+    pub(crate) user: sys::DiscordUser,
+    pub(crate) metadata: BTreeMap<String, String>,
+    pub(crate) voice: bool,
+    pub(crate) network: bool,
+    pub(crate) channels: HashMap<u8, bool>,
+}
+
+impl Lobby {
+    pub(crate) fn member(&self, user_id: sys::DiscordUserId) -> Option<&Member> {
+        self.members.iter().find(|member| member.user.id == user_id)
+    }
+
+    fn member_mut(&mut self, user_id: sys::DiscordUserId) -> Option<&mut Member> {
+        self.members
+            .iter_mut()
+            .find(|member| member.user.id == user_id)
+    }
+
+    fn member_ids(&self) -> Vec<sys::DiscordUserId> {
+        self.members.iter().map(|member| member.user.id).collect()
+    }
+
+    fn activity_secret(&self) -> String {
+        format!(
+            "{}:{}",
+            self.lobby.id,
+            crate::utils::charbuf_to_str(&self.lobby.secret)
+        )
+    }
+
+    // Searches can target metadata (`metadata.key`) or some of the lobby's properties
+    fn search_value(&self, key: &str) -> Option<String> {
+        match key {
+            "id" => Some(self.lobby.id.to_string()),
+            "owner_id" => Some(self.lobby.owner_id.to_string()),
+            "capacity" => Some(self.lobby.capacity.to_string()),
+            "slots" => Some(
+                (self.lobby.capacity as usize)
+                    .saturating_sub(self.members.len())
+                    .to_string(),
+            ),
+            "member_count" => Some(self.members.len().to_string()),
+            _ => self
+                .metadata
+                .get(key.strip_prefix("metadata.").unwrap_or(key))
+                .cloned(),
+        }
+    }
+}
+
+impl Member {
+    pub(crate) fn new(user: sys::DiscordUser) -> Self {
+        Self {
+            user,
+            metadata: BTreeMap::new(),
+            voice: false,
+            network: false,
+            channels: HashMap::new(),
+        }
+    }
+}
+
+#[repr(C)]
+struct LobbyTransaction {
+    vtable: sys::IDiscordLobbyTransaction,
+    kind: Option<sys::EDiscordLobbyType>,
+    owner: Option<sys::DiscordUserId>,
+    capacity: Option<u32>,
+    locked: Option<bool>,
+    metadata: Vec<(String, Option<String>)>,
+}
+
+#[repr(C)]
+struct LobbyMemberTransaction {
+    vtable: sys::IDiscordLobbyMemberTransaction,
+    metadata: Vec<(String, Option<String>)>,
+}
+
+#[repr(C)]
+struct SearchQuery {
+    vtable: sys::IDiscordLobbySearchQuery,
+    filters: Vec<(
+        String,
+        sys::EDiscordLobbySearchComparison,
+        sys::EDiscordLobbySearchCast,
+        String,
+    )>,
+    sorts: Vec<(String, sys::EDiscordLobbySearchCast, String)>,
+    limit: Option<u32>,
+}
+
+impl Release for *mut sys::IDiscordLobbyTransaction {
+    unsafe fn release(self) {
+        drop(Box::from_raw(self as *mut LobbyTransaction))
+    }
+}
+
+impl Release for *mut sys::IDiscordLobbyMemberTransaction {
+    unsafe fn release(self) {
+        drop(Box::from_raw(self as *mut LobbyMemberTransaction))
+    }
+}
+
+impl Release for *mut sys::IDiscordLobbySearchQuery {
+    unsafe fn release(self) {
+        drop(Box::from_raw(self as *mut SearchQuery))
+    }
+}
+
+const MAX_KEY_LEN: usize = size_of::<sys::DiscordMetadataKey>() - 1;
+const MAX_VALUE_LEN: usize = size_of::<sys::DiscordMetadataValue>() - 1;
+
+unsafe fn metadata_entry(
+    key: *const u8,
+    value: Option<*const u8>,
+) -> Option<(String, Option<String>)> {
+    let key = ptr_to_string(key);
+    let value = value.map(|value| ptr_to_string(value));
+
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        return None;
+    }
+
+    if matches!(&value, Some(value) if value.len() > MAX_VALUE_LEN) {
+        return None;
+    }
+
+    Some((key, value))
+}
+
+fn apply_metadata(metadata: &mut BTreeMap<String, String>, changes: Vec<(String, Option<String>)>) {
+    for (key, value) in changes {
+        match value {
+            Some(value) => {
+                metadata.insert(key, value);
+            }
+            None => {
+                metadata.remove(&key);
+            }
+        }
+    }
+}
+
+const LOBBY_TRANSACTION: sys::IDiscordLobbyTransaction = sys::IDiscordLobbyTransaction {
+    set_type: {
+        unsafe extern "C" fn set_type(
+            tx: *mut sys::IDiscordLobbyTransaction,
+            type_: sys::EDiscordLobbyType,
+        ) -> sys::EDiscordResult {
+            (*(tx as *mut LobbyTransaction)).kind = Some(type_);
+            sys::DiscordResult_Ok
+        }
+
+        Some(set_type)
+    },
+
+    set_owner: {
+        unsafe extern "C" fn set_owner(
+            tx: *mut sys::IDiscordLobbyTransaction,
+            owner_id: sys::DiscordUserId,
+        ) -> sys::EDiscordResult {
+            (*(tx as *mut LobbyTransaction)).owner = Some(owner_id);
+            sys::DiscordResult_Ok
+        }
+
+        Some(set_owner)
+    },
+
+    set_capacity: {
+        unsafe extern "C" fn set_capacity(
+            tx: *mut sys::IDiscordLobbyTransaction,
+            capacity: u32,
+        ) -> sys::EDiscordResult {
+            (*(tx as *mut LobbyTransaction)).capacity = Some(capacity);
+            sys::DiscordResult_Ok
+        }
+
+        Some(set_capacity)
+    },
+
+    set_metadata: {
+        unsafe extern "C" fn set_metadata(
+            tx: *mut sys::IDiscordLobbyTransaction,
+            key: *mut u8,
+            value: *mut u8,
+        ) -> sys::EDiscordResult {
+            match metadata_entry(key, Some(value)) {
+                Some(entry) => {
+                    (*(tx as *mut LobbyTransaction)).metadata.push(entry);
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_InvalidPayload,
+            }
+        }
+
+        Some(set_metadata)
+    },
+
+    delete_metadata: {
+        unsafe extern "C" fn delete_metadata(
+            tx: *mut sys::IDiscordLobbyTransaction,
+            key: *mut u8,
+        ) -> sys::EDiscordResult {
+            match metadata_entry(key, None) {
+                Some(entry) => {
+                    (*(tx as *mut LobbyTransaction)).metadata.push(entry);
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_InvalidPayload,
+            }
+        }
+
+        Some(delete_metadata)
+    },
+
+    set_locked: {
+        unsafe extern "C" fn set_locked(
+            tx: *mut sys::IDiscordLobbyTransaction,
+            locked: bool,
+        ) -> sys::EDiscordResult {
+            (*(tx as *mut LobbyTransaction)).locked = Some(locked);
+            sys::DiscordResult_Ok
+        }
+
+        Some(set_locked)
+    },
+};
+
+const LOBBY_MEMBER_TRANSACTION: sys::IDiscordLobbyMemberTransaction =
+    sys::IDiscordLobbyMemberTransaction {
+        set_metadata: {
+            unsafe extern "C" fn set_metadata(
+                tx: *mut sys::IDiscordLobbyMemberTransaction,
+                key: *mut u8,
+                value: *mut u8,
+            ) -> sys::EDiscordResult {
+                match metadata_entry(key, Some(value)) {
+                    Some(entry) => {
+                        (*(tx as *mut LobbyMemberTransaction)).metadata.push(entry);
+                        sys::DiscordResult_Ok
+                    }
+                    None => sys::DiscordResult_InvalidPayload,
+                }
+            }
+
+            Some(set_metadata)
+        },
+
+        delete_metadata: {
+            unsafe extern "C" fn delete_metadata(
+                tx: *mut sys::IDiscordLobbyMemberTransaction,
+                key: *mut u8,
+            ) -> sys::EDiscordResult {
+                match metadata_entry(key, None) {
+                    Some(entry) => {
+                        (*(tx as *mut LobbyMemberTransaction)).metadata.push(entry);
+                        sys::DiscordResult_Ok
+                    }
+                    None => sys::DiscordResult_InvalidPayload,
+                }
+            }
+
+            Some(delete_metadata)
+        },
+    };
+
+const SEARCH_QUERY: sys::IDiscordLobbySearchQuery = sys::IDiscordLobbySearchQuery {
+    filter: {
+        unsafe extern "C" fn filter(
+            query: *mut sys::IDiscordLobbySearchQuery,
+            key: *mut u8,
+            comparison: sys::EDiscordLobbySearchComparison,
+            cast: sys::EDiscordLobbySearchCast,
+            value: *mut u8,
+        ) -> sys::EDiscordResult {
+            (*(query as *mut SearchQuery)).filters.push((
+                ptr_to_string(key),
+                comparison,
+                cast,
+                ptr_to_string(value),
+            ));
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(filter)
+    },
+
+    sort: {
+        unsafe extern "C" fn sort(
+            query: *mut sys::IDiscordLobbySearchQuery,
+            key: *mut u8,
+            cast: sys::EDiscordLobbySearchCast,
+            value: *mut u8,
+        ) -> sys::EDiscordResult {
+            (*(query as *mut SearchQuery)).sorts.push((
+                ptr_to_string(key),
+                cast,
+                ptr_to_string(value),
+            ));
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(sort)
+    },
+
+    limit: {
+        unsafe extern "C" fn limit(
+            query: *mut sys::IDiscordLobbySearchQuery,
+            limit: u32,
+        ) -> sys::EDiscordResult {
+            (*(query as *mut SearchQuery)).limit = Some(limit);
+            sys::DiscordResult_Ok
+        }
+
+        Some(limit)
+    },
+
+    distance: {
+        unsafe extern "C" fn distance(
+            _: *mut sys::IDiscordLobbySearchQuery,
+            _distance: sys::EDiscordLobbySearchDistance,
+        ) -> sys::EDiscordResult {
+            sys::DiscordResult_Ok
+        }
+
+        Some(distance)
+    },
+};
+
+fn compare(actual: &str, cast: sys::EDiscordLobbySearchCast, expected: &str) -> Option<Ordering> {
+    if cast == sys::DiscordLobbySearchCast_Number {
+        let actual = actual.parse::<f64>().ok()?;
+        let expected = expected.parse::<f64>().ok()?;
+        actual.partial_cmp(&expected)
+    } else {
+        Some(actual.cmp(expected))
+    }
+}
+
+fn distance(actual: &str, cast: sys::EDiscordLobbySearchCast, expected: &str) -> f64 {
+    if cast == sys::DiscordLobbySearchCast_Number {
+        match (actual.parse::<f64>(), expected.parse::<f64>()) {
+            (Ok(actual), Ok(expected)) => (actual - expected).abs(),
+            _ => f64::INFINITY,
+        }
+    } else if actual == expected {
+        0.0
+    } else {
+        1.0
+    }
+}
+
+fn search_lobbies(service: &Service, query: &SearchQuery) -> Vec<sys::DiscordLobbyId> {
+    let mut results = service
+        .lobbies
+        .values()
+        .filter(|lobby| lobby.lobby.type_ == sys::DiscordLobbyType_Public)
+        .filter(|lobby| {
+            query.filters.iter().all(|(key, comparison, cast, value)| {
+                let ordering = match lobby
+                    .search_value(key)
+                    .and_then(|actual| compare(&actual, *cast, value))
+                {
+                    Some(ordering) => ordering,
+                    None => return false,
+                };
+
+                match *comparison {
+                    sys::DiscordLobbySearchComparison_LessThanOrEqual => {
+                        ordering != Ordering::Greater
+                    }
+                    sys::DiscordLobbySearchComparison_LessThan => ordering == Ordering::Less,
+                    sys::DiscordLobbySearchComparison_Equal => ordering == Ordering::Equal,
+                    sys::DiscordLobbySearchComparison_GreaterThan => ordering == Ordering::Greater,
+                    sys::DiscordLobbySearchComparison_GreaterThanOrEqual => {
+                        ordering != Ordering::Less
+                    }
+                    sys::DiscordLobbySearchComparison_NotEqual => ordering != Ordering::Equal,
+                    _ => false,
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for (key, cast, value) in query.sorts.iter().rev() {
+        results.sort_by(|a, b| {
+            let a = a
+                .search_value(key)
+                .map_or(f64::INFINITY, |a| distance(&a, *cast, value));
+            let b = b
+                .search_value(key)
+                .map_or(f64::INFINITY, |b| distance(&b, *cast, value));
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        });
+    }
+
+    if let Some(limit) = query.limit {
+        results.truncate(limit as usize);
+    }
+
+    results.into_iter().map(|lobby| lobby.lobby.id).collect()
+}
+
+unsafe fn events(mock: &Mock) -> &sys::IDiscordLobbyEvents {
+    &*mock.params.lobby_events
+}
+
+// Calls `event` for each instance of `members`: right away for this one, during their next call
+// to `run_callbacks` for the others
+fn notify(
+    mock: &Mock,
+    members: &[sys::DiscordUserId],
+    event: impl 'static + Send + Clone + FnOnce(&Mock),
+) {
+    let mut notified = false;
+
+    for client in &mock.service().clients {
+        if !members.contains(&client.user.id) {
+            continue;
+        }
+
+        if mock.owns(&client.inbox) {
+            notified = true;
+        } else {
+            client.inbox.lock().unwrap().push(Box::new(event.clone()));
+        }
+    }
+
+    if notified {
+        event(mock)
+    }
+}
+
+type LobbyCallback =
+    Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult, *mut sys::DiscordLobby)>;
+
+// Calls back with the lobby produced by `run` during the next call to `run_callbacks`
+fn respond_with_lobby(
+    mock: &Mock,
+    callback_data: *mut c_void,
+    callback: LobbyCallback,
+    run: impl 'static + FnOnce(&Mock) -> Result<sys::DiscordLobby, sys::EDiscordResult>,
+) {
+    mock.schedule(
+        move |mock| unsafe {
+            match run(mock) {
+                Ok(mut lobby) => {
+                    callback.unwrap()(callback_data, sys::DiscordResult_Ok, &mut lobby)
+                }
+                Err(res) => {
+                    callback.unwrap()(callback_data, res, &mut sys::DiscordLobby::default())
+                }
+            }
+        },
+        move || unsafe {
+            callback.unwrap()(
+                callback_data,
+                sys::DiscordResult_TransactionAborted,
+                &mut sys::DiscordLobby::default(),
+            )
+        },
+    )
+}
+
+fn connect(
+    mock: &Mock,
+    lobby_id: sys::DiscordLobbyId,
+    secret: &str,
+) -> Result<sys::DiscordLobby, sys::EDiscordResult> {
+    let user = mock.state.borrow().current_user;
+
+    let (lobby, members) = {
+        let mut service = mock.service();
+
+        let lobby = service
+            .lobbies
+            .get_mut(&lobby_id)
+            .ok_or(sys::DiscordResult_NotFound)?;
+
+        if crate::utils::charbuf_to_str(&lobby.lobby.secret) != secret {
+            return Err(sys::DiscordResult_InvalidLobbySecret);
+        }
+
+        if lobby.member(user.id).is_some() {
+            return Ok(lobby.lobby);
+        }
+
+        if lobby.members.len() >= lobby.lobby.capacity as usize {
+            return Err(sys::DiscordResult_LobbyFull);
+        }
+
+        if lobby.lobby.locked {
+            return Err(sys::DiscordResult_InvalidPermissions);
+        }
+
+        let members = lobby.member_ids();
+        lobby.members.push(Member::new(user));
+
+        (lobby.lobby, members)
+    };
+
+    notify(mock, &members, move |mock| unsafe {
+        events(mock).on_member_connect.unwrap()(mock.params.event_data, lobby_id, user.id)
+    });
+
+    Ok(lobby)
+}
+
+// Removes a member, ownership is handed over to the next member and empty lobbies are deleted
+fn disconnect_member(
+    mock: &Mock,
+    lobby_id: sys::DiscordLobbyId,
+    user_id: sys::DiscordUserId,
+) -> Result<(), sys::EDiscordResult> {
+    let (members, handed_over) = {
+        let mut service = mock.service();
+
+        let lobby = service
+            .lobbies
+            .get_mut(&lobby_id)
+            .ok_or(sys::DiscordResult_NotFound)?;
+
+        if lobby.member(user_id).is_none() {
+            return Err(sys::DiscordResult_NotFound);
+        }
+
+        lobby.members.retain(|member| member.user.id != user_id);
+
+        let handed_over = match lobby.members.first() {
+            Some(member) if lobby.lobby.owner_id == user_id => {
+                lobby.lobby.owner_id = member.user.id;
+                true
+            }
+            Some(_) => false,
+            None => {
+                service.lobbies.remove(&lobby_id);
+                return Ok(());
+            }
+        };
+
+        (lobby.member_ids(), handed_over)
+    };
+
+    notify(mock, &members, move |mock| unsafe {
+        events(mock).on_member_disconnect.unwrap()(mock.params.event_data, lobby_id, user_id);
+
+        if handed_over {
+            events(mock).on_lobby_update.unwrap()(mock.params.event_data, lobby_id);
+        }
+    });
+
+    Ok(())
+}
+
+/// Leaves every lobby as the instance is destroyed, unless the user is still connected through
+/// another one
+pub(super) fn disconnect(mock: &Mock) {
+    let user_id = mock.state.borrow().current_user.id;
+
+    let lobby_ids = {
+        let mut service = mock.service();
+        service.clients.retain(|client| !mock.owns(&client.inbox));
+
+        if service
+            .clients
+            .iter()
+            .any(|client| client.user.id == user_id)
+        {
+            return;
+        }
+
+        service
+            .lobbies
+            .values()
+            .filter(|lobby| lobby.member(user_id).is_some())
+            .map(|lobby| lobby.lobby.id)
+            .collect::<Vec<_>>()
+    };
+
+    for lobby_id in lobby_ids {
+        let _ = disconnect_member(mock, lobby_id, user_id);
+    }
+}
+
+fn with_lobby<R>(
+    mock: &Mock,
+    lobby_id: sys::DiscordLobbyId,
+    f: impl FnOnce(&Lobby) -> Result<R, sys::EDiscordResult>,
+) -> Result<R, sys::EDiscordResult> {
+    let service = mock.service();
+
+    f(service
+        .lobbies
+        .get(&lobby_id)
+        .ok_or(sys::DiscordResult_NotFound)?)
+}
+
+fn into_result(res: Result<(), sys::EDiscordResult>) -> sys::EDiscordResult {
+    res.err().unwrap_or(sys::DiscordResult_Ok)
+}
+
+pub(super) const MANAGER: sys::IDiscordLobbyManager = sys::IDiscordLobbyManager {
+    get_lobby_create_transaction: {
+        unsafe extern "C" fn get_lobby_create_transaction(
+            _: *mut sys::IDiscordLobbyManager,
+            transaction: *mut *mut sys::IDiscordLobbyTransaction,
+        ) -> sys::EDiscordResult {
+            *transaction = Box::into_raw(Box::new(LobbyTransaction {
+                vtable: LOBBY_TRANSACTION,
+                kind: None,
+                owner: None,
+                capacity: None,
+                locked: None,
+                metadata: Vec::new(),
+            })) as *mut _;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(get_lobby_create_transaction)
+    },
+
+    get_lobby_update_transaction: {
+        unsafe extern "C" fn get_lobby_update_transaction(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            transaction: *mut *mut sys::IDiscordLobbyTransaction,
+        ) -> sys::EDiscordResult {
+            if !mock(manager).service().lobbies.contains_key(&lobby_id) {
+                return sys::DiscordResult_NotFound;
+            }
+
+            *transaction = Box::into_raw(Box::new(LobbyTransaction {
+                vtable: LOBBY_TRANSACTION,
+                kind: None,
+                owner: None,
+                capacity: None,
+                locked: None,
+                metadata: Vec::new(),
+            })) as *mut _;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(get_lobby_update_transaction)
+    },
+
+    get_member_update_transaction: {
+        unsafe extern "C" fn get_member_update_transaction(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            user_id: sys::DiscordUserId,
+            transaction: *mut *mut sys::IDiscordLobbyMemberTransaction,
+        ) -> sys::EDiscordResult {
+            into_result(with_lobby(mock(manager), lobby_id, |lobby| {
+                lobby.member(user_id).ok_or(sys::DiscordResult_NotFound)?;
+
+                *transaction = Box::into_raw(Box::new(LobbyMemberTransaction {
+                    vtable: LOBBY_MEMBER_TRANSACTION,
+                    metadata: Vec::new(),
+                })) as *mut _;
+
+                Ok(())
+            }))
+        }
+
+        Some(get_member_update_transaction)
+    },
+
+    create_lobby: {
+        unsafe extern "C" fn create_lobby(
+            manager: *mut sys::IDiscordLobbyManager,
+            transaction: *mut sys::IDiscordLobbyTransaction,
+            callback_data: *mut c_void,
+            callback: LobbyCallback,
+        ) {
+            let tx = Box::from_raw(transaction as *mut LobbyTransaction);
+
+            respond_with_lobby(mock(manager), callback_data, callback, move |mock| {
+                let user = mock.state.borrow().current_user;
+                let mut service = mock.service();
+
+                if matches!(tx.owner, Some(owner) if owner != user.id) {
+                    return Err(sys::DiscordResult_InvalidPermissions);
+                }
+
+                let mut lobby = sys::DiscordLobby {
+                    id: service.next_id(),
+                    type_: tx.kind.unwrap_or(sys::DiscordLobbyType_Private),
+                    owner_id: user.id,
+                    capacity: tx.capacity.unwrap_or(16),
+                    locked: tx.locked.unwrap_or(false),
+                    ..Default::default()
+                };
+
+                let secret = format!("mock-secret-{}", service.next_id());
+                string_to_charbuf(&mut lobby.secret, &secret);
+
+                let mut metadata = BTreeMap::new();
+                apply_metadata(&mut metadata, tx.metadata);
+
+                service.lobbies.insert(
+                    lobby.id,
+                    Lobby {
+                        lobby,
+                        metadata,
+                        members: vec![Member::new(user)],
+                    },
+                );
+
+                Ok(lobby)
+            })
+        }
+
+        Some(create_lobby)
+    },
+
+    update_lobby: {
+        unsafe extern "C" fn update_lobby(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            transaction: *mut sys::IDiscordLobbyTransaction,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            let tx = Box::from_raw(transaction as *mut LobbyTransaction);
+
+            mock(manager).respond(callback_data, callback, move |mock| {
+                let user_id = mock.state.borrow().current_user.id;
+
+                let members = {
+                    let mut service = mock.service();
+
+                    let lobby = match service.lobbies.get_mut(&lobby_id) {
+                        Some(lobby) => lobby,
+                        None => return sys::DiscordResult_NotFound,
+                    };
+
+                    if lobby.lobby.owner_id != user_id {
+                        return sys::DiscordResult_InvalidPermissions;
+                    }
+
+                    if let Some(owner) = tx.owner {
+                        if lobby.member(owner).is_none() {
+                            return sys::DiscordResult_NotFound;
+                        }
+
+                        lobby.lobby.owner_id = owner;
+                    }
+
+                    if let Some(kind) = tx.kind {
+                        lobby.lobby.type_ = kind;
+                    }
+
+                    if let Some(capacity) = tx.capacity {
+                        lobby.lobby.capacity = capacity;
+                    }
+
+                    if let Some(locked) = tx.locked {
+                        lobby.lobby.locked = locked;
+                    }
+
+                    apply_metadata(&mut lobby.metadata, tx.metadata);
+
+                    lobby.member_ids()
+                };
+
+                notify(mock, &members, move |mock| {
+                    events(mock).on_lobby_update.unwrap()(mock.params.event_data, lobby_id)
+                });
+
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(update_lobby)
+    },
+
+    delete_lobby: {
+        unsafe extern "C" fn delete_lobby(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, move |mock| {
+                let user_id = mock.state.borrow().current_user.id;
+
+                let members = {
+                    let mut service = mock.service();
+
+                    match service.lobbies.get(&lobby_id) {
+                        None => return sys::DiscordResult_NotFound,
+                        Some(lobby) if lobby.lobby.owner_id != user_id => {
+                            return sys::DiscordResult_InvalidPermissions
+                        }
+                        Some(_) => {}
+                    }
+
+                    service.lobbies.remove(&lobby_id).unwrap().member_ids()
+                };
+
+                mock.state
+                    .borrow_mut()
+                    .search_results
+                    .retain(|id| *id != lobby_id);
+
+                notify(mock, &members, move |mock| {
+                    events(mock).on_lobby_delete.unwrap()(mock.params.event_data, lobby_id, 0)
+                });
+
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(delete_lobby)
+    },
+
+    connect_lobby: {
+        unsafe extern "C" fn connect_lobby(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            secret: *mut u8,
+            callback_data: *mut c_void,
+            callback: LobbyCallback,
+        ) {
+            let secret = ptr_to_string(secret);
+
+            respond_with_lobby(mock(manager), callback_data, callback, move |mock| {
+                connect(mock, lobby_id, &secret)
+            })
+        }
+
+        Some(connect_lobby)
+    },
+
+    connect_lobby_with_activity_secret: {
+        unsafe extern "C" fn connect_lobby_with_activity_secret(
+            manager: *mut sys::IDiscordLobbyManager,
+            activity_secret: *mut u8,
+            callback_data: *mut c_void,
+            callback: LobbyCallback,
+        ) {
+            let activity_secret = ptr_to_string(activity_secret);
+
+            respond_with_lobby(mock(manager), callback_data, callback, move |mock| {
+                let mut parts = activity_secret.splitn(2, ':');
+
+                match (parts.next().and_then(|id| id.parse().ok()), parts.next()) {
+                    (Some(lobby_id), Some(secret)) => connect(mock, lobby_id, secret),
+                    _ => Err(sys::DiscordResult_InvalidLobbySecret),
+                }
+            })
+        }
+
+        Some(connect_lobby_with_activity_secret)
+    },
+
+    disconnect_lobby: {
+        unsafe extern "C" fn disconnect_lobby(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, move |mock| {
+                let user_id = mock.state.borrow().current_user.id;
+
+                into_result(disconnect_member(mock, lobby_id, user_id))
+            })
+        }
+
+        Some(disconnect_lobby)
+    },
+
+    get_lobby: {
+        unsafe extern "C" fn get_lobby(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            lobby: *mut sys::DiscordLobby,
+        ) -> sys::EDiscordResult {
+            into_result(with_lobby(mock(manager), lobby_id, |found| {
+                *lobby = found.lobby;
+                Ok(())
+            }))
+        }
+
+        Some(get_lobby)
+    },
+
+    get_lobby_activity_secret: {
+        unsafe extern "C" fn get_lobby_activity_secret(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            secret: *mut sys::DiscordLobbySecret,
+        ) -> sys::EDiscordResult {
+            into_result(with_lobby(mock(manager), lobby_id, |lobby| {
+                string_to_charbuf(&mut *secret, &lobby.activity_secret());
+                Ok(())
+            }))
+        }
+
+        Some(get_lobby_activity_secret)
+    },
+
+    get_lobby_metadata_value: {
+        unsafe extern "C" fn get_lobby_metadata_value(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            key: *mut u8,
+            value: *mut sys::DiscordMetadataValue,
+        ) -> sys::EDiscordResult {
+            let key = ptr_to_string(key);
+
+            into_result(with_lobby(mock(manager), lobby_id, |lobby| {
+                let found = lobby
+                    .metadata
+                    .get(&key)
+                    .ok_or(sys::DiscordResult_NotFound)?;
+                string_to_charbuf(&mut *value, found);
+                Ok(())
+            }))
+        }
+
+        Some(get_lobby_metadata_value)
+    },
+
+    get_lobby_metadata_key: {
+        unsafe extern "C" fn get_lobby_metadata_key(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            index: i32,
+            key: *mut sys::DiscordMetadataKey,
+        ) -> sys::EDiscordResult {
+            into_result(with_lobby(mock(manager), lobby_id, |lobby| {
+                let found = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| lobby.metadata.keys().nth(index))
+                    .ok_or(sys::DiscordResult_NotFound)?;
+
+                string_to_charbuf(&mut *key, found);
+                Ok(())
+            }))
+        }
+
+        Some(get_lobby_metadata_key)
+    },
+
+    lobby_metadata_count: {
+        unsafe extern "C" fn lobby_metadata_count(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            count: *mut i32,
+        ) -> sys::EDiscordResult {
+            into_result(with_lobby(mock(manager), lobby_id, |lobby| {
+                *count = i32::try_from(lobby.metadata.len()).unwrap();
+                Ok(())
+            }))
+        }
+
+        Some(lobby_metadata_count)
+    },
+
+    member_count: {
+        unsafe extern "C" fn member_count(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            count: *mut i32,
+        ) -> sys::EDiscordResult {
+            into_result(with_lobby(mock(manager), lobby_id, |lobby| {
+                *count = i32::try_from(lobby.members.len()).unwrap();
+                Ok(())
+            }))
+        }
+
+        Some(member_count)
+    },
+
+    get_member_user_id: {
+        unsafe extern "C" fn get_member_user_id(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            index: i32,
+            user_id: *mut sys::DiscordUserId,
+        ) -> sys::EDiscordResult {
+            into_result(with_lobby(mock(manager), lobby_id, |lobby| {
+                let member = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| lobby.members.get(index))
+                    .ok_or(sys::DiscordResult_NotFound)?;
+
+                *user_id = member.user.id;
+                Ok(())
+            }))
+        }
+
+        Some(get_member_user_id)
+    },
+
+    get_member_user: {
+        unsafe extern "C" fn get_member_user(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            user_id: sys::DiscordUserId,
+            user: *mut sys::DiscordUser,
+        ) -> sys::EDiscordResult {
+            into_result(with_lobby(mock(manager), lobby_id, |lobby| {
+                *user = lobby
+                    .member(user_id)
+                    .ok_or(sys::DiscordResult_NotFound)?
+                    .user;
+                Ok(())
+            }))
+        }
+
+        Some(get_member_user)
+    },
+
+    get_member_metadata_value: {
+        unsafe extern "C" fn get_member_metadata_value(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            user_id: sys::DiscordUserId,
+            key: *mut u8,
+            value: *mut sys::DiscordMetadataValue,
+        ) -> sys::EDiscordResult {
+            let key = ptr_to_string(key);
+
+            into_result(with_lobby(mock(manager), lobby_id, |lobby| {
+                let member = lobby.member(user_id).ok_or(sys::DiscordResult_NotFound)?;
+                let found = member
+                    .metadata
+                    .get(&key)
+                    .ok_or(sys::DiscordResult_NotFound)?;
+
+                string_to_charbuf(&mut *value, found);
+                Ok(())
+            }))
+        }
+
+        Some(get_member_metadata_value)
+    },
+
+    get_member_metadata_key: {
+        unsafe extern "C" fn get_member_metadata_key(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            user_id: sys::DiscordUserId,
+            index: i32,
+            key: *mut sys::DiscordMetadataKey,
+        ) -> sys::EDiscordResult {
+            into_result(with_lobby(mock(manager), lobby_id, |lobby| {
+                let member = lobby.member(user_id).ok_or(sys::DiscordResult_NotFound)?;
+                let found = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| member.metadata.keys().nth(index))
+                    .ok_or(sys::DiscordResult_NotFound)?;
+
+                string_to_charbuf(&mut *key, found);
+                Ok(())
+            }))
+        }
+
+        Some(get_member_metadata_key)
+    },
+
+    member_metadata_count: {
+        unsafe extern "C" fn member_metadata_count(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            user_id: sys::DiscordUserId,
+            count: *mut i32,
+        ) -> sys::EDiscordResult {
+            into_result(with_lobby(mock(manager), lobby_id, |lobby| {
+                let member = lobby.member(user_id).ok_or(sys::DiscordResult_NotFound)?;
+                *count = i32::try_from(member.metadata.len()).unwrap();
+                Ok(())
+            }))
+        }
+
+        Some(member_metadata_count)
+    },
+
+    update_member: {
+        unsafe extern "C" fn update_member(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            user_id: sys::DiscordUserId,
+            transaction: *mut sys::IDiscordLobbyMemberTransaction,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            let tx = Box::from_raw(transaction as *mut LobbyMemberTransaction);
+
+            mock(manager).respond(callback_data, callback, move |mock| {
+                let current_user_id = mock.state.borrow().current_user.id;
+
+                let members = {
+                    let mut service = mock.service();
+
+                    let lobby = match service.lobbies.get_mut(&lobby_id) {
+                        Some(lobby) => lobby,
+                        None => return sys::DiscordResult_NotFound,
+                    };
+
+                    if user_id != current_user_id && lobby.lobby.owner_id != current_user_id {
+                        return sys::DiscordResult_InvalidPermissions;
+                    }
+
+                    match lobby.member_mut(user_id) {
+                        Some(member) => apply_metadata(&mut member.metadata, tx.metadata),
+                        None => return sys::DiscordResult_NotFound,
+                    }
+
+                    lobby.member_ids()
+                };
+
+                notify(mock, &members, move |mock| {
+                    events(mock).on_member_update.unwrap()(
+                        mock.params.event_data,
+                        lobby_id,
+                        user_id,
+                    )
+                });
+
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(update_member)
+    },
+
+    send_lobby_message: {
+        unsafe extern "C" fn send_lobby_message(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            data: *mut u8,
+            data_length: u32,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            let data = std::slice::from_raw_parts(data, data_length as usize).to_vec();
+
+            mock(manager).respond(callback_data, callback, move |mock| {
+                let user_id = mock.state.borrow().current_user.id;
+
+                let members = with_lobby(mock, lobby_id, |lobby| {
+                    lobby.member(user_id).ok_or(sys::DiscordResult_NotFound)?;
+                    Ok(lobby.member_ids())
+                });
+
+                let members = match members {
+                    Ok(members) => members,
+                    Err(res) => return res,
+                };
+
+                notify(mock, &members, move |mock| {
+                    let mut data = data;
+
+                    events(mock).on_lobby_message.unwrap()(
+                        mock.params.event_data,
+                        lobby_id,
+                        user_id,
+                        data.as_mut_ptr(),
+                        data_length,
+                    )
+                });
+
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(send_lobby_message)
+    },
+
+    get_search_query: {
+        unsafe extern "C" fn get_search_query(
+            _: *mut sys::IDiscordLobbyManager,
+            query: *mut *mut sys::IDiscordLobbySearchQuery,
+        ) -> sys::EDiscordResult {
+            *query = Box::into_raw(Box::new(SearchQuery {
+                vtable: SEARCH_QUERY,
+                filters: Vec::new(),
+                sorts: Vec::new(),
+                limit: None,
+            })) as *mut _;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(get_search_query)
+    },
+
+    search: {
+        unsafe extern "C" fn search(
+            manager: *mut sys::IDiscordLobbyManager,
+            query: *mut sys::IDiscordLobbySearchQuery,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            let query = Box::from_raw(query as *mut SearchQuery);
+
+            mock(manager).respond(callback_data, callback, move |mock| {
+                let results = search_lobbies(&mock.service(), &query);
+                mock.state.borrow_mut().search_results = results;
+
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(search)
+    },
+
+    lobby_count: {
+        unsafe extern "C" fn lobby_count(manager: *mut sys::IDiscordLobbyManager, count: *mut i32) {
+            *count = i32::try_from(mock(manager).state.borrow().search_results.len()).unwrap();
+        }
+
+        Some(lobby_count)
+    },
+
+    get_lobby_id: {
+        unsafe extern "C" fn get_lobby_id(
+            manager: *mut sys::IDiscordLobbyManager,
+            index: i32,
+            lobby_id: *mut sys::DiscordLobbyId,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            match usize::try_from(index)
+                .ok()
+                .and_then(|index| state.search_results.get(index))
+            {
+                Some(found) => {
+                    *lobby_id = *found;
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(get_lobby_id)
+    },
+
+    connect_voice: {
+        unsafe extern "C" fn connect_voice(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, move |mock| {
+                into_result(with_current_member(mock, lobby_id, |member| {
+                    member.voice = true;
+                    Ok(())
+                }))
+            })
+        }
+
+        Some(connect_voice)
+    },
+
+    disconnect_voice: {
+        unsafe extern "C" fn disconnect_voice(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, move |mock| {
+                into_result(with_current_member(mock, lobby_id, |member| {
+                    member.voice = false;
+                    Ok(())
+                }))
+            })
+        }
+
+        Some(disconnect_voice)
+    },
+
+    connect_network: {
+        unsafe extern "C" fn connect_network(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+        ) -> sys::EDiscordResult {
+            into_result(with_current_member(mock(manager), lobby_id, |member| {
+                member.network = true;
+                Ok(())
+            }))
+        }
+
+        Some(connect_network)
+    },
+
+    disconnect_network: {
+        unsafe extern "C" fn disconnect_network(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+        ) -> sys::EDiscordResult {
+            into_result(with_current_member(mock(manager), lobby_id, |member| {
+                member.network = false;
+                member.channels.clear();
+                Ok(())
+            }))
+        }
+
+        Some(disconnect_network)
+    },
+
+    flush_network: {
+        unsafe extern "C" fn flush_network(
+            _: *mut sys::IDiscordLobbyManager,
+        ) -> sys::EDiscordResult {
+            sys::DiscordResult_Ok
+        }
+
+        Some(flush_network)
+    },
+
+    open_network_channel: {
+        unsafe extern "C" fn open_network_channel(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            channel_id: u8,
+            reliable: bool,
+        ) -> sys::EDiscordResult {
+            into_result(with_current_member(mock(manager), lobby_id, |member| {
+                if !member.network {
+                    return Err(sys::DiscordResult_InvalidCommand);
+                }
+
+                member.channels.insert(channel_id, reliable);
+                Ok(())
+            }))
+        }
+
+        Some(open_network_channel)
+    },
+
+    send_network_message: {
+        unsafe extern "C" fn send_network_message(
+            manager: *mut sys::IDiscordLobbyManager,
+            lobby_id: sys::DiscordLobbyId,
+            user_id: sys::DiscordUserId,
+            channel_id: u8,
+            data: *mut u8,
+            data_length: u32,
+        ) -> sys::EDiscordResult {
+            let mock = mock(manager);
+            let current_user_id = mock.state.borrow().current_user.id;
+
+            let res = with_lobby(mock, lobby_id, |lobby| {
+                let sender = lobby
+                    .member(current_user_id)
+                    .ok_or(sys::DiscordResult_NotFound)?;
+
+                if !sender.network || !sender.channels.contains_key(&channel_id) {
+                    return Err(sys::DiscordResult_InvalidCommand);
+                }
+
+                let recipient = lobby.member(user_id).ok_or(sys::DiscordResult_NotFound)?;
+
+                if !recipient.network {
+                    return Err(sys::DiscordResult_NotFound);
+                }
+
+                Ok(())
+            });
+
+            if let Err(res) = res {
+                return res;
+            }
+
+            if !mock.drop_network_message() {
+                let data = std::slice::from_raw_parts(data, data_length as usize).to_vec();
+
+                mock.service().deliver(user_id, move |mock| {
+                    let mut data = data;
+
+                    events(mock).on_network_message.unwrap()(
+                        mock.params.event_data,
+                        lobby_id,
+                        current_user_id,
+                        channel_id,
+                        data.as_mut_ptr(),
+                        data_length,
+                    )
+                });
+            }
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(send_network_message)
+    },
+};
+
+fn with_current_member(
+    mock: &Mock,
+    lobby_id: sys::DiscordLobbyId,
+    f: impl FnOnce(&mut Member) -> Result<(), sys::EDiscordResult>,
+) -> Result<(), sys::EDiscordResult> {
+    let user_id = mock.state.borrow().current_user.id;
+    let mut service = mock.service();
+
+    let member = service
+        .lobbies
+        .get_mut(&lobby_id)
+        .ok_or(sys::DiscordResult_NotFound)?
+        .member_mut(user_id)
+        .ok_or(sys::DiscordResult_NotFound)?;
+
+    f(member)
+}
@@ -0,0 +1,192 @@
+use super::{mock, ptr_to_string, Mock};
+use crate::sys;
+use std::collections::HashMap;
+
+pub(super) fn connect(mock: &Mock) {
+    mock.emit(|mock| {
+        let route = format!("mock-route-{}\0", mock.state.borrow().peer_id);
+
+        unsafe {
+            (*mock.params.network_events).on_route_update.unwrap()(
+                mock.params.event_data,
+                route.as_ptr(),
+            )
+        }
+    });
+}
+
+pub(super) const MANAGER: sys::IDiscordNetworkManager = sys::IDiscordNetworkManager {
+    get_peer_id: {
+        unsafe extern "C" fn get_peer_id(
+            manager: *mut sys::IDiscordNetworkManager,
+            peer_id: *mut sys::DiscordNetworkPeerId,
+        ) {
+            *peer_id = mock(manager).state.borrow().peer_id;
+        }
+
+        Some(get_peer_id)
+    },
+
+    flush: {
+        unsafe extern "C" fn flush(_: *mut sys::IDiscordNetworkManager) -> sys::EDiscordResult {
+            sys::DiscordResult_Ok
+        }
+
+        Some(flush)
+    },
+
+    open_peer: {
+        unsafe extern "C" fn open_peer(
+            manager: *mut sys::IDiscordNetworkManager,
+            peer_id: sys::DiscordNetworkPeerId,
+            route_data: *const u8,
+        ) -> sys::EDiscordResult {
+            if ptr_to_string(route_data).is_empty() {
+                return sys::DiscordResult_InvalidPayload;
+            }
+
+            mock(manager)
+                .state
+                .borrow_mut()
+                .peers
+                .entry(peer_id)
+                .or_insert_with(HashMap::new);
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(open_peer)
+    },
+
+    update_peer: {
+        unsafe extern "C" fn update_peer(
+            manager: *mut sys::IDiscordNetworkManager,
+            peer_id: sys::DiscordNetworkPeerId,
+            route_data: *const u8,
+        ) -> sys::EDiscordResult {
+            if ptr_to_string(route_data).is_empty() {
+                return sys::DiscordResult_InvalidPayload;
+            }
+
+            if mock(manager).state.borrow().peers.contains_key(&peer_id) {
+                sys::DiscordResult_Ok
+            } else {
+                sys::DiscordResult_NotFound
+            }
+        }
+
+        Some(update_peer)
+    },
+
+    close_peer: {
+        unsafe extern "C" fn close_peer(
+            manager: *mut sys::IDiscordNetworkManager,
+            peer_id: sys::DiscordNetworkPeerId,
+        ) -> sys::EDiscordResult {
+            match mock(manager).state.borrow_mut().peers.remove(&peer_id) {
+                Some(_) => sys::DiscordResult_Ok,
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(close_peer)
+    },
+
+    open_channel: {
+        unsafe extern "C" fn open_channel(
+            manager: *mut sys::IDiscordNetworkManager,
+            peer_id: sys::DiscordNetworkPeerId,
+            channel_id: sys::DiscordNetworkChannelId,
+            reliable: bool,
+        ) -> sys::EDiscordResult {
+            match mock(manager).state.borrow_mut().peers.get_mut(&peer_id) {
+                Some(channels) => {
+                    channels.insert(channel_id, reliable);
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(open_channel)
+    },
+
+    close_channel: {
+        unsafe extern "C" fn close_channel(
+            manager: *mut sys::IDiscordNetworkManager,
+            peer_id: sys::DiscordNetworkPeerId,
+            channel_id: sys::DiscordNetworkChannelId,
+        ) -> sys::EDiscordResult {
+            match mock(manager)
+                .state
+                .borrow_mut()
+                .peers
+                .get_mut(&peer_id)
+                .and_then(|channels| channels.remove(&channel_id))
+            {
+                Some(_) => sys::DiscordResult_Ok,
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(close_channel)
+    },
+
+    send_message: {
+        unsafe extern "C" fn send_message(
+            manager: *mut sys::IDiscordNetworkManager,
+            peer_id: sys::DiscordNetworkPeerId,
+            channel_id: sys::DiscordNetworkChannelId,
+            data: *mut u8,
+            data_length: u32,
+        ) -> sys::EDiscordResult {
+            let mock = mock(manager);
+            let own_peer_id = {
+                let state = mock.state.borrow();
+
+                match state.peers.get(&peer_id) {
+                    Some(channels) if channels.contains_key(&channel_id) => {}
+                    _ => return sys::DiscordResult_NotFound,
+                }
+
+                state.peer_id
+            };
+
+            if mock.drop_network_message() {
+                return sys::DiscordResult_Ok;
+            }
+
+            let data = std::slice::from_raw_parts(data, data_length as usize).to_vec();
+
+            // Received only if the peer opened the same channel with us
+            let deliver = move |mock: &Mock| {
+                let opened = matches!(
+                    mock.state.borrow().peers.get(&own_peer_id),
+                    Some(channels) if channels.contains_key(&channel_id)
+                );
+
+                if opened {
+                    let mut data = data;
+
+                    (*mock.params.network_events).on_message.unwrap()(
+                        mock.params.event_data,
+                        own_peer_id,
+                        channel_id,
+                        data.as_mut_ptr(),
+                        data_length,
+                    )
+                }
+            };
+
+            for client in &mock.service().clients {
+                if client.peer_id == peer_id {
+                    client.inbox.lock().unwrap().push(Box::new(deliver.clone()));
+                }
+            }
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(send_message)
+    },
+};
@@ -0,0 +1,103 @@
+use super::{mock, ptr_to_string, Callback};
+use crate::sys;
+use std::ffi::c_void;
+
+pub(super) const MANAGER: sys::IDiscordOverlayManager = sys::IDiscordOverlayManager {
+    is_enabled: {
+        unsafe extern "C" fn is_enabled(_: *mut sys::IDiscordOverlayManager, enabled: *mut bool) {
+            *enabled = true;
+        }
+
+        Some(is_enabled)
+    },
+
+    is_locked: {
+        unsafe extern "C" fn is_locked(
+            manager: *mut sys::IDiscordOverlayManager,
+            locked: *mut bool,
+        ) {
+            *locked = mock(manager).state.borrow().overlay_locked;
+        }
+
+        Some(is_locked)
+    },
+
+    set_locked: {
+        unsafe extern "C" fn set_locked(
+            manager: *mut sys::IDiscordOverlayManager,
+            locked: bool,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, move |mock| {
+                let changed = {
+                    let mut state = mock.state.borrow_mut();
+                    std::mem::replace(&mut state.overlay_locked, locked) != locked
+                };
+
+                if changed {
+                    (*mock.params.overlay_events).on_toggle.unwrap()(
+                        mock.params.event_data,
+                        locked,
+                    );
+                }
+
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(set_locked)
+    },
+
+    open_activity_invite: {
+        unsafe extern "C" fn open_activity_invite(
+            manager: *mut sys::IDiscordOverlayManager,
+            _type: sys::EDiscordActivityActionType,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, |mock| {
+                if mock.state.borrow().activity.is_none() {
+                    sys::DiscordResult_NoEligibleActivity
+                } else {
+                    sys::DiscordResult_Ok
+                }
+            })
+        }
+
+        Some(open_activity_invite)
+    },
+
+    open_guild_invite: {
+        unsafe extern "C" fn open_guild_invite(
+            manager: *mut sys::IDiscordOverlayManager,
+            code: *const u8,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            let code = ptr_to_string(code);
+
+            mock(manager).respond(callback_data, callback, move |_| {
+                if code.is_empty() {
+                    sys::DiscordResult_InvalidInvite
+                } else {
+                    sys::DiscordResult_Ok
+                }
+            })
+        }
+
+        Some(open_guild_invite)
+    },
+
+    open_voice_settings: {
+        unsafe extern "C" fn open_voice_settings(
+            manager: *mut sys::IDiscordOverlayManager,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, |_| sys::DiscordResult_Ok)
+        }
+
+        Some(open_voice_settings)
+    },
+};
@@ -0,0 +1,105 @@
+use super::{mock, Mock};
+use crate::sys;
+use std::{convert::TryFrom, ffi::c_void};
+
+pub(super) fn connect(mock: &Mock) {
+    mock.emit(|mock| unsafe {
+        (*mock.params.relationship_events).on_refresh.unwrap()(mock.params.event_data)
+    });
+}
+
+pub(super) const MANAGER: sys::IDiscordRelationshipManager = sys::IDiscordRelationshipManager {
+    filter: {
+        unsafe extern "C" fn filter(
+            manager: *mut sys::IDiscordRelationshipManager,
+            filter_data: *mut c_void,
+            filter: Option<
+                unsafe extern "C" fn(*mut c_void, *mut sys::DiscordRelationship) -> bool,
+            >,
+        ) {
+            let mock = mock(manager);
+
+            // The filter is user code, it may call back into the relationship manager
+            let mut relationships = mock.state.borrow().relationships.clone();
+            relationships.retain(|relationship| {
+                let mut relationship = *relationship;
+                filter.unwrap()(filter_data, &mut relationship)
+            });
+
+            mock.state.borrow_mut().filtered_relationships = Some(relationships);
+        }
+
+        Some(filter)
+    },
+
+    count: {
+        unsafe extern "C" fn count(
+            manager: *mut sys::IDiscordRelationshipManager,
+            count: *mut i32,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            *count = i32::try_from(
+                state
+                    .filtered_relationships
+                    .as_ref()
+                    .unwrap_or(&state.relationships)
+                    .len(),
+            )
+            .unwrap();
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(count)
+    },
+
+    get: {
+        unsafe extern "C" fn get(
+            manager: *mut sys::IDiscordRelationshipManager,
+            user_id: sys::DiscordUserId,
+            relationship: *mut sys::DiscordRelationship,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            match state
+                .relationships
+                .iter()
+                .find(|relationship| relationship.user.id == user_id)
+            {
+                Some(found) => {
+                    *relationship = *found;
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(get)
+    },
+
+    get_at: {
+        unsafe extern "C" fn get_at(
+            manager: *mut sys::IDiscordRelationshipManager,
+            index: u32,
+            relationship: *mut sys::DiscordRelationship,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            match state
+                .filtered_relationships
+                .as_ref()
+                .unwrap_or(&state.relationships)
+                .get(index as usize)
+            {
+                Some(found) => {
+                    *relationship = *found;
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(get_at)
+    },
+};
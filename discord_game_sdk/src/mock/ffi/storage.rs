@@ -0,0 +1,299 @@
+use super::{mock, now, ptr_to_string, string_to_charbuf, Callback, State};
+use crate::sys;
+use std::{convert::TryFrom, ffi::c_void, mem::size_of};
+
+type DataCallback = Option<unsafe extern "C" fn(*mut c_void, sys::EDiscordResult, *mut u8, u32)>;
+
+const MAX_FILENAME_LEN: usize = size_of::<sys::DiscordFileStat>() - 2 * size_of::<u64>() - 1;
+
+unsafe fn filename(name: *const u8) -> Result<String, sys::EDiscordResult> {
+    let name = ptr_to_string(name);
+
+    if name.is_empty() || name.len() > MAX_FILENAME_LEN {
+        return Err(sys::DiscordResult_InvalidFilename);
+    }
+
+    Ok(name)
+}
+
+fn file_stat(name: &str, data: &[u8], last_modified: u64) -> sys::DiscordFileStat {
+    let mut stat = sys::DiscordFileStat {
+        size: data.len() as u64,
+        last_modified,
+        ..Default::default()
+    };
+
+    string_to_charbuf(&mut stat.filename, name);
+
+    stat
+}
+
+fn read_file(
+    state: &State,
+    name: &str,
+    offset: u64,
+    length: u64,
+) -> Result<Vec<u8>, sys::EDiscordResult> {
+    let (data, _) = state.files.get(name).ok_or(sys::DiscordResult_NotFound)?;
+
+    let start = usize::try_from(offset)
+        .ok()
+        .filter(|start| *start <= data.len())
+        .ok_or(sys::DiscordResult_InvalidFileSize)?;
+    let end = usize::try_from(offset.saturating_add(length))
+        .unwrap_or(usize::MAX)
+        .min(data.len());
+
+    Ok(data[start..end].to_vec())
+}
+
+unsafe fn respond_with_data(
+    manager: *mut sys::IDiscordStorageManager,
+    callback_data: *mut c_void,
+    callback: DataCallback,
+    run: impl 'static + FnOnce(&State) -> Result<Vec<u8>, sys::EDiscordResult>,
+) {
+    mock(manager).schedule(
+        move |mock| {
+            let res = run(&mock.state.borrow());
+
+            match res {
+                Ok(mut data) => callback.unwrap()(
+                    callback_data,
+                    sys::DiscordResult_Ok,
+                    data.as_mut_ptr(),
+                    u32::try_from(data.len()).unwrap(),
+                ),
+                Err(res) => callback.unwrap()(callback_data, res, std::ptr::null_mut(), 0),
+            }
+        },
+        move || {
+            callback.unwrap()(
+                callback_data,
+                sys::DiscordResult_TransactionAborted,
+                std::ptr::null_mut(),
+                0,
+            )
+        },
+    )
+}
+
+pub(super) const MANAGER: sys::IDiscordStorageManager = sys::IDiscordStorageManager {
+    read: {
+        unsafe extern "C" fn read(
+            manager: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            data: *mut u8,
+            data_length: u32,
+            read: *mut u32,
+        ) -> sys::EDiscordResult {
+            let res = filename(name).and_then(|name| {
+                read_file(&mock(manager).state.borrow(), &name, 0, data_length.into())
+            });
+
+            match res {
+                Ok(contents) => {
+                    std::ptr::copy_nonoverlapping(contents.as_ptr(), data, contents.len());
+                    *read = u32::try_from(contents.len()).unwrap();
+                    sys::DiscordResult_Ok
+                }
+                Err(res) => res,
+            }
+        }
+
+        Some(read)
+    },
+
+    read_async: {
+        unsafe extern "C" fn read_async(
+            manager: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            callback_data: *mut c_void,
+            callback: DataCallback,
+        ) {
+            let name = filename(name);
+
+            respond_with_data(manager, callback_data, callback, move |state| {
+                read_file(state, &name?, 0, u64::MAX)
+            })
+        }
+
+        Some(read_async)
+    },
+
+    read_async_partial: {
+        unsafe extern "C" fn read_async_partial(
+            manager: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            offset: u64,
+            length: u64,
+            callback_data: *mut c_void,
+            callback: DataCallback,
+        ) {
+            let name = filename(name);
+
+            respond_with_data(manager, callback_data, callback, move |state| {
+                read_file(state, &name?, offset, length)
+            })
+        }
+
+        Some(read_async_partial)
+    },
+
+    write: {
+        unsafe extern "C" fn write(
+            manager: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            data: *mut u8,
+            data_length: u32,
+        ) -> sys::EDiscordResult {
+            match filename(name) {
+                Ok(name) => {
+                    let data = std::slice::from_raw_parts(data, data_length as usize).to_vec();
+                    mock(manager)
+                        .state
+                        .borrow_mut()
+                        .files
+                        .insert(name, (data, now()));
+                    sys::DiscordResult_Ok
+                }
+                Err(res) => res,
+            }
+        }
+
+        Some(write)
+    },
+
+    write_async: {
+        unsafe extern "C" fn write_async(
+            manager: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            data: *mut u8,
+            data_length: u32,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            let name = filename(name);
+            let data = std::slice::from_raw_parts(data, data_length as usize).to_vec();
+
+            mock(manager).respond(callback_data, callback, move |mock| match name {
+                Ok(name) => {
+                    mock.state.borrow_mut().files.insert(name, (data, now()));
+                    sys::DiscordResult_Ok
+                }
+                Err(res) => res,
+            })
+        }
+
+        Some(write_async)
+    },
+
+    delete_: {
+        unsafe extern "C" fn delete_(
+            manager: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+        ) -> sys::EDiscordResult {
+            match filename(name) {
+                Ok(name) => match mock(manager).state.borrow_mut().files.remove(&name) {
+                    Some(_) => sys::DiscordResult_Ok,
+                    None => sys::DiscordResult_NotFound,
+                },
+                Err(res) => res,
+            }
+        }
+
+        Some(delete_)
+    },
+
+    exists: {
+        unsafe extern "C" fn exists(
+            manager: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            exists: *mut bool,
+        ) -> sys::EDiscordResult {
+            match filename(name) {
+                Ok(name) => {
+                    *exists = mock(manager).state.borrow().files.contains_key(&name);
+                    sys::DiscordResult_Ok
+                }
+                Err(res) => res,
+            }
+        }
+
+        Some(exists)
+    },
+
+    count: {
+        unsafe extern "C" fn count(manager: *mut sys::IDiscordStorageManager, count: *mut i32) {
+            *count = i32::try_from(mock(manager).state.borrow().files.len()).unwrap();
+        }
+
+        Some(count)
+    },
+
+    stat: {
+        unsafe extern "C" fn stat(
+            manager: *mut sys::IDiscordStorageManager,
+            name: *const u8,
+            stat: *mut sys::DiscordFileStat,
+        ) -> sys::EDiscordResult {
+            let name = match filename(name) {
+                Ok(name) => name,
+                Err(res) => return res,
+            };
+
+            match mock(manager).state.borrow().files.get(&name) {
+                Some((data, last_modified)) => {
+                    *stat = file_stat(&name, data, *last_modified);
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(stat)
+    },
+
+    stat_at: {
+        unsafe extern "C" fn stat_at(
+            manager: *mut sys::IDiscordStorageManager,
+            index: i32,
+            stat: *mut sys::DiscordFileStat,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            match usize::try_from(index)
+                .ok()
+                .and_then(|index| state.files.iter().nth(index))
+            {
+                Some((name, (data, last_modified))) => {
+                    *stat = file_stat(name, data, *last_modified);
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(stat_at)
+    },
+
+    get_path: {
+        unsafe extern "C" fn get_path(
+            manager: *mut sys::IDiscordStorageManager,
+            path: *mut sys::DiscordPath,
+        ) -> sys::EDiscordResult {
+            // Files are kept in memory, this path is not backed by anything
+            let path_str = format!(
+                "{}/discord_game_sdk_mock/{}",
+                std::env::temp_dir().display(),
+                mock(manager).params.client_id
+            );
+
+            string_to_charbuf(&mut *path, &path_str);
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(get_path)
+    },
+};
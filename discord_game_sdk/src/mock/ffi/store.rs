@@ -0,0 +1,228 @@
+use super::{mock, Callback};
+use crate::sys;
+use std::{convert::TryFrom, ffi::c_void};
+
+pub(super) const MANAGER: sys::IDiscordStoreManager = sys::IDiscordStoreManager {
+    fetch_skus: {
+        unsafe extern "C" fn fetch_skus(
+            manager: *mut sys::IDiscordStoreManager,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, |mock| {
+                mock.state.borrow_mut().skus_fetched = true;
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(fetch_skus)
+    },
+
+    count_skus: {
+        unsafe extern "C" fn count_skus(manager: *mut sys::IDiscordStoreManager, count: *mut i32) {
+            let state = mock(manager).state.borrow();
+
+            *count = if state.skus_fetched {
+                i32::try_from(state.skus.len()).unwrap()
+            } else {
+                0
+            };
+        }
+
+        Some(count_skus)
+    },
+
+    get_sku: {
+        unsafe extern "C" fn get_sku(
+            manager: *mut sys::IDiscordStoreManager,
+            sku_id: sys::DiscordSnowflake,
+            sku: *mut sys::DiscordSku,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            if !state.skus_fetched {
+                return sys::DiscordResult_NotFetched;
+            }
+
+            match state.skus.iter().find(|found| found.id == sku_id) {
+                Some(found) => {
+                    *sku = *found;
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(get_sku)
+    },
+
+    get_sku_at: {
+        unsafe extern "C" fn get_sku_at(
+            manager: *mut sys::IDiscordStoreManager,
+            index: i32,
+            sku: *mut sys::DiscordSku,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            if !state.skus_fetched {
+                return sys::DiscordResult_NotFetched;
+            }
+
+            match usize::try_from(index)
+                .ok()
+                .and_then(|index| state.skus.get(index))
+            {
+                Some(found) => {
+                    *sku = *found;
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(get_sku_at)
+    },
+
+    fetch_entitlements: {
+        unsafe extern "C" fn fetch_entitlements(
+            manager: *mut sys::IDiscordStoreManager,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, |mock| {
+                mock.state.borrow_mut().entitlements_fetched = true;
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(fetch_entitlements)
+    },
+
+    count_entitlements: {
+        unsafe extern "C" fn count_entitlements(
+            manager: *mut sys::IDiscordStoreManager,
+            count: *mut i32,
+        ) {
+            let state = mock(manager).state.borrow();
+
+            *count = if state.entitlements_fetched {
+                i32::try_from(state.entitlements.len()).unwrap()
+            } else {
+                0
+            };
+        }
+
+        Some(count_entitlements)
+    },
+
+    get_entitlement: {
+        unsafe extern "C" fn get_entitlement(
+            manager: *mut sys::IDiscordStoreManager,
+            entitlement_id: sys::DiscordSnowflake,
+            entitlement: *mut sys::DiscordEntitlement,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            if !state.entitlements_fetched {
+                return sys::DiscordResult_NotFetched;
+            }
+
+            match state
+                .entitlements
+                .iter()
+                .find(|found| found.id == entitlement_id)
+            {
+                Some(found) => {
+                    *entitlement = *found;
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(get_entitlement)
+    },
+
+    get_entitlement_at: {
+        unsafe extern "C" fn get_entitlement_at(
+            manager: *mut sys::IDiscordStoreManager,
+            index: i32,
+            entitlement: *mut sys::DiscordEntitlement,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            if !state.entitlements_fetched {
+                return sys::DiscordResult_NotFetched;
+            }
+
+            match usize::try_from(index)
+                .ok()
+                .and_then(|index| state.entitlements.get(index))
+            {
+                Some(found) => {
+                    *entitlement = *found;
+                    sys::DiscordResult_Ok
+                }
+                None => sys::DiscordResult_NotFound,
+            }
+        }
+
+        Some(get_entitlement_at)
+    },
+
+    has_sku_entitlement: {
+        unsafe extern "C" fn has_sku_entitlement(
+            manager: *mut sys::IDiscordStoreManager,
+            sku_id: sys::DiscordSnowflake,
+            has_entitlement: *mut bool,
+        ) -> sys::EDiscordResult {
+            *has_entitlement = mock(manager)
+                .state
+                .borrow()
+                .entitlements
+                .iter()
+                .any(|entitlement| entitlement.sku_id == sku_id);
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(has_sku_entitlement)
+    },
+
+    start_purchase: {
+        unsafe extern "C" fn start_purchase(
+            manager: *mut sys::IDiscordStoreManager,
+            sku_id: sys::DiscordSnowflake,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, move |mock| {
+                let mut entitlement = {
+                    let mut state = mock.state.borrow_mut();
+
+                    if !state.skus.iter().any(|sku| sku.id == sku_id) {
+                        return sys::DiscordResult_NotFound;
+                    }
+
+                    let entitlement = sys::DiscordEntitlement {
+                        id: state.next_id(),
+                        type_: sys::DiscordEntitlementType_Purchase,
+                        sku_id,
+                    };
+
+                    state.entitlements.push(entitlement);
+                    entitlement
+                };
+
+                (*mock.params.store_events).on_entitlement_create.unwrap()(
+                    mock.params.event_data,
+                    &mut entitlement,
+                );
+
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(start_purchase)
+    },
+};
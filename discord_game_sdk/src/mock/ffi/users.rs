@@ -0,0 +1,112 @@
+use super::{mock, Mock};
+use crate::sys;
+use std::ffi::c_void;
+
+pub(super) fn connect(mock: &Mock) {
+    mock.emit(|mock| {
+        mock.state.borrow_mut().ready = true;
+        mock.log(sys::DiscordLogLevel_Info, "connected to mock client");
+
+        unsafe {
+            (*mock.params.user_events).on_current_user_update.unwrap()(mock.params.event_data)
+        }
+    });
+}
+
+pub(super) const MANAGER: sys::IDiscordUserManager = sys::IDiscordUserManager {
+    get_current_user: {
+        unsafe extern "C" fn get_current_user(
+            manager: *mut sys::IDiscordUserManager,
+            current_user: *mut sys::DiscordUser,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            // Not available until `on_current_user_update` has fired
+            if !state.ready {
+                return sys::DiscordResult_NotFound;
+            }
+
+            *current_user = state.current_user;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(get_current_user)
+    },
+
+    get_user: {
+        unsafe extern "C" fn get_user(
+            manager: *mut sys::IDiscordUserManager,
+            user_id: sys::DiscordUserId,
+            callback_data: *mut c_void,
+            callback: Option<
+                unsafe extern "C" fn(*mut c_void, sys::EDiscordResult, *mut sys::DiscordUser),
+            >,
+        ) {
+            mock(manager).schedule(
+                move |mock| {
+                    let user = mock.user(user_id);
+
+                    match user {
+                        Some(mut user) => {
+                            callback.unwrap()(callback_data, sys::DiscordResult_Ok, &mut user)
+                        }
+                        None => callback.unwrap()(
+                            callback_data,
+                            sys::DiscordResult_NotFound,
+                            &mut sys::DiscordUser::default(),
+                        ),
+                    }
+                },
+                move || {
+                    callback.unwrap()(
+                        callback_data,
+                        sys::DiscordResult_TransactionAborted,
+                        &mut sys::DiscordUser::default(),
+                    )
+                },
+            )
+        }
+
+        Some(get_user)
+    },
+
+    get_current_user_premium_type: {
+        unsafe extern "C" fn get_current_user_premium_type(
+            manager: *mut sys::IDiscordUserManager,
+            premium_type: *mut sys::EDiscordPremiumType,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            if !state.ready {
+                return sys::DiscordResult_NotFound;
+            }
+
+            *premium_type = state.premium_type;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(get_current_user_premium_type)
+    },
+
+    current_user_has_flag: {
+        unsafe extern "C" fn current_user_has_flag(
+            manager: *mut sys::IDiscordUserManager,
+            flag: sys::EDiscordUserFlag,
+            has_flag: *mut bool,
+        ) -> sys::EDiscordResult {
+            let state = mock(manager).state.borrow();
+
+            if !state.ready {
+                return sys::DiscordResult_NotFound;
+            }
+
+            *has_flag = state.user_flags & flag == flag;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(current_user_has_flag)
+    },
+};
@@ -0,0 +1,172 @@
+use super::{mock, Callback};
+use crate::sys;
+use std::ffi::c_void;
+
+const DEFAULT_LOCAL_VOLUME: u8 = 100;
+const MAX_LOCAL_VOLUME: u8 = 200;
+
+pub(super) const MANAGER: sys::IDiscordVoiceManager = sys::IDiscordVoiceManager {
+    get_input_mode: {
+        unsafe extern "C" fn get_input_mode(
+            manager: *mut sys::IDiscordVoiceManager,
+            input_mode: *mut sys::DiscordInputMode,
+        ) -> sys::EDiscordResult {
+            *input_mode = mock(manager).state.borrow().input_mode;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(get_input_mode)
+    },
+
+    set_input_mode: {
+        unsafe extern "C" fn set_input_mode(
+            manager: *mut sys::IDiscordVoiceManager,
+            input_mode: sys::DiscordInputMode,
+            callback_data: *mut c_void,
+            callback: Callback,
+        ) {
+            mock(manager).respond(callback_data, callback, move |mock| {
+                mock.state.borrow_mut().input_mode = input_mode;
+
+                (*mock.params.voice_events).on_settings_update.unwrap()(mock.params.event_data);
+
+                sys::DiscordResult_Ok
+            })
+        }
+
+        Some(set_input_mode)
+    },
+
+    is_self_mute: {
+        unsafe extern "C" fn is_self_mute(
+            manager: *mut sys::IDiscordVoiceManager,
+            mute: *mut bool,
+        ) -> sys::EDiscordResult {
+            *mute = mock(manager).state.borrow().self_mute;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(is_self_mute)
+    },
+
+    set_self_mute: {
+        unsafe extern "C" fn set_self_mute(
+            manager: *mut sys::IDiscordVoiceManager,
+            mute: bool,
+        ) -> sys::EDiscordResult {
+            mock(manager).state.borrow_mut().self_mute = mute;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(set_self_mute)
+    },
+
+    is_self_deaf: {
+        unsafe extern "C" fn is_self_deaf(
+            manager: *mut sys::IDiscordVoiceManager,
+            deaf: *mut bool,
+        ) -> sys::EDiscordResult {
+            *deaf = mock(manager).state.borrow().self_deaf;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(is_self_deaf)
+    },
+
+    set_self_deaf: {
+        unsafe extern "C" fn set_self_deaf(
+            manager: *mut sys::IDiscordVoiceManager,
+            deaf: bool,
+        ) -> sys::EDiscordResult {
+            mock(manager).state.borrow_mut().self_deaf = deaf;
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(set_self_deaf)
+    },
+
+    is_local_mute: {
+        unsafe extern "C" fn is_local_mute(
+            manager: *mut sys::IDiscordVoiceManager,
+            user_id: sys::DiscordSnowflake,
+            mute: *mut bool,
+        ) -> sys::EDiscordResult {
+            *mute = mock(manager)
+                .state
+                .borrow()
+                .local_mutes
+                .get(&user_id)
+                .copied()
+                .unwrap_or(false);
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(is_local_mute)
+    },
+
+    set_local_mute: {
+        unsafe extern "C" fn set_local_mute(
+            manager: *mut sys::IDiscordVoiceManager,
+            user_id: sys::DiscordSnowflake,
+            mute: bool,
+        ) -> sys::EDiscordResult {
+            mock(manager)
+                .state
+                .borrow_mut()
+                .local_mutes
+                .insert(user_id, mute);
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(set_local_mute)
+    },
+
+    get_local_volume: {
+        unsafe extern "C" fn get_local_volume(
+            manager: *mut sys::IDiscordVoiceManager,
+            user_id: sys::DiscordSnowflake,
+            volume: *mut u8,
+        ) -> sys::EDiscordResult {
+            *volume = mock(manager)
+                .state
+                .borrow()
+                .local_volumes
+                .get(&user_id)
+                .copied()
+                .unwrap_or(DEFAULT_LOCAL_VOLUME);
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(get_local_volume)
+    },
+
+    set_local_volume: {
+        unsafe extern "C" fn set_local_volume(
+            manager: *mut sys::IDiscordVoiceManager,
+            user_id: sys::DiscordSnowflake,
+            volume: u8,
+        ) -> sys::EDiscordResult {
+            if volume > MAX_LOCAL_VOLUME {
+                return sys::DiscordResult_InvalidPayload;
+            }
+
+            mock(manager)
+                .state
+                .borrow_mut()
+                .local_volumes
+                .insert(user_id, volume);
+
+            sys::DiscordResult_Ok
+        }
+
+        Some(set_local_volume)
+    },
+};
@@ -0,0 +1,79 @@
+//! Set-ups shared by the tests of the modules built on top of the mock
+
+use super::{ffi, MockConfig, MockFaults, MockService};
+use crate::{sys, Discord, EventHandler, EventQueue, Lobby, LobbyTransaction, UserID};
+use std::{cell::RefCell, rc::Rc};
+
+/// Runs callbacks until the operations started so far and their events are done
+pub(crate) fn run(discord: &mut Discord<'_, impl EventHandler>) {
+    for _ in 0..10 {
+        discord.run_callbacks().unwrap();
+    }
+}
+
+/// Makes the SDK log `message` as information
+pub(crate) fn log<E>(discord: &Discord<'_, E>, message: &str) {
+    unsafe { ffi::mock(discord.inner().core) }.log(sys::DiscordLogLevel_Info, message);
+}
+
+/// Configuration of user `user_id`, named `username`, connected to `service`
+pub(crate) fn service_user(service: &MockService, user_id: UserID, username: &str) -> MockConfig {
+    let mut config = MockConfig::new();
+    config
+        .with_current_user(user_id, username, &format!("{:04}", user_id))
+        .with_service(service);
+    config
+}
+
+/// Configuration misbehaving as planned by `faults`
+pub(crate) fn faulty(faults: MockFaults) -> MockConfig {
+    let mut config = MockConfig::new();
+    config.with_faults(faults);
+    config
+}
+
+/// Instance collecting its events in an `EventQueue`
+pub(crate) fn queued<'d>(config: &MockConfig) -> Discord<'d, EventQueue> {
+    let mut discord = Discord::mock_with(config);
+    *discord.event_handler_mut() = Some(EventQueue::default());
+    discord
+}
+
+/// Instance whose session is recorded to `writer`, without an event handler
+#[cfg(feature = "replay")]
+pub(crate) fn recorded<'d>(
+    config: &MockConfig,
+    writer: impl 'static + std::io::Write,
+) -> Discord<'d, EventQueue> {
+    let writer = Box::new(writer);
+
+    Discord::mock_backed(config.client_id, |mut params| {
+        let mut core = std::ptr::null_mut();
+
+        unsafe {
+            crate::replay::record(&mut params, &mut core, writer, |params, core| {
+                *core = ffi::create_mock(*params, config);
+                sys::DiscordResult_Ok
+            });
+        }
+
+        core
+    })
+}
+
+/// Creates a lobby described by `transaction` and runs callbacks until it exists
+pub(crate) fn create_lobby(
+    discord: &mut Discord<'_, impl EventHandler>,
+    transaction: &LobbyTransaction,
+) -> Lobby {
+    let lobby = Rc::new(RefCell::new(None));
+
+    discord.create_lobby(transaction, {
+        let lobby = lobby.clone();
+        move |_, res| *lobby.borrow_mut() = Some(res.unwrap().clone())
+    });
+    run(discord);
+
+    let lobby = lobby.borrow_mut().take();
+    lobby.unwrap()
+}
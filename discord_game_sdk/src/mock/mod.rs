@@ -1,13 +1,285 @@
 use crate::{
     discord::{Discord, DiscordInner},
-    events, CreateFlags, EventHandler, UserAchievement,
+    events, sys, ClientID, CreateFlags, EntitlementKind, EventHandler, LogLevel, PremiumKind,
+    RelationshipKind, SkuKind, Snowflake, Status, UserFlags, UserID,
+};
+use std::{
+    cell::{Cell, UnsafeCell},
+    marker::PhantomData,
 };
-use std::{cell::UnsafeCell, marker::PhantomData};
 
+mod faults;
 mod ffi;
+mod service;
+
+#[cfg(test)]
+pub(crate) mod fixtures;
+
+pub use self::{faults::MockFaults, service::MockService};
+
+/// Initial state of a mocked SDK
+///
+/// The mock runs entirely in-process: no native library, no Discord client.
+/// Asynchronous methods complete during the next call to
+/// [`Discord::run_callbacks`](struct.Discord.html#method.run_callbacks),
+/// and events are emitted the same way.
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # fn example() -> Result<()> {
+/// let mut config = MockConfig::new();
+/// config
+///     .with_current_user(42, "player", "1234")
+///     .with_relationship(RelationshipKind::Friend, 43, Status::Online)
+///     .with_file("save.dat", b"level 1".to_vec());
+///
+/// let mut discord = Discord::<()>::mock_with(&config);
+/// discord.run_callbacks()?;
+///
+/// assert_eq!(discord.current_user()?.id(), 42);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MockConfig {
+    pub(crate) client_id: ClientID,
+    pub(crate) current_user: sys::DiscordUser,
+    pub(crate) premium_type: sys::EDiscordPremiumType,
+    pub(crate) user_flags: sys::EDiscordUserFlag,
+    pub(crate) users: Vec<sys::DiscordUser>,
+    pub(crate) locale: String,
+    pub(crate) branch: String,
+    pub(crate) achievements: Vec<sys::DiscordUserAchievement>,
+    pub(crate) relationships: Vec<sys::DiscordRelationship>,
+    pub(crate) files: Vec<(String, Vec<u8>)>,
+    pub(crate) skus: Vec<sys::DiscordSku>,
+    pub(crate) entitlements: Vec<sys::DiscordEntitlement>,
+    pub(crate) faults: MockFaults,
+    pub(crate) service: Option<MockService>,
+}
+
+fn user(id: UserID, username: &str, discriminator: &str) -> sys::DiscordUser {
+    let mut user = sys::DiscordUser {
+        id,
+        ..sys::DiscordUser::default()
+    };
+
+    ffi::string_to_charbuf(&mut user.username, username);
+    ffi::string_to_charbuf(&mut user.discriminator, discriminator);
+
+    user
+}
+
+impl MockConfig {
+    /// Creates a configuration where the current user is `mock#0001` (ID 1), and nothing else
+    /// exists.
+    pub fn new() -> Self {
+        Self {
+            client_id: 0,
+            current_user: user(1, "mock", "0001"),
+            premium_type: sys::DiscordPremiumType_None,
+            user_flags: 0,
+            users: Vec::new(),
+            locale: "en-US".to_string(),
+            branch: "master".to_string(),
+            achievements: Vec::new(),
+            relationships: Vec::new(),
+            files: Vec::new(),
+            skus: Vec::new(),
+            entitlements: Vec::new(),
+            faults: MockFaults::new(),
+            service: None,
+        }
+    }
+
+    /// Client ID reported by the instance and used in storage paths and OAuth2 tickets
+    pub fn with_client_id(&mut self, client_id: ClientID) -> &mut Self {
+        self.client_id = client_id;
+        self
+    }
+
+    /// Replaces the current user
+    ///
+    /// `username` and `discriminator` are truncated to fit the SDK's buffers.
+    pub fn with_current_user(
+        &mut self,
+        id: UserID,
+        username: &str,
+        discriminator: &str,
+    ) -> &mut Self {
+        self.current_user = user(id, username, discriminator);
+        self
+    }
+
+    /// Adds a user that can be looked up with
+    /// [`Discord::user`](struct.Discord.html#method.user), join lobbies, etc.
+    pub fn with_user(&mut self, id: UserID, username: &str, discriminator: &str) -> &mut Self {
+        self.users.push(user(id, username, discriminator));
+        self
+    }
+
+    /// Adds a relationship with a user, the user must have been added with
+    /// [`with_user`](#method.with_user) beforehand or it will have an empty name
+    pub fn with_relationship(
+        &mut self,
+        kind: RelationshipKind,
+        user_id: UserID,
+        status: Status,
+    ) -> &mut Self {
+        let user = self
+            .users
+            .iter()
+            .find(|user| user.id == user_id)
+            .copied()
+            .unwrap_or_else(|| user(user_id, "", ""));
+
+        self.relationships.push(sys::DiscordRelationship {
+            type_: kind.into(),
+            user,
+            presence: sys::DiscordPresence {
+                status: status.into(),
+                ..sys::DiscordPresence::default()
+            },
+        });
+        self
+    }
+
+    /// Adds an achievement for the current user, with `percent_complete` progress
+    pub fn with_achievement(
+        &mut self,
+        achievement_id: Snowflake,
+        percent_complete: u8,
+    ) -> &mut Self {
+        self.achievements.push(sys::DiscordUserAchievement {
+            user_id: self.current_user.id,
+            achievement_id,
+            percent_complete,
+            ..sys::DiscordUserAchievement::default()
+        });
+        self
+    }
+
+    /// Adds a SKU, available once fetched
+    pub fn with_sku(
+        &mut self,
+        id: Snowflake,
+        kind: SkuKind,
+        name: &str,
+        amount: u32,
+        currency: &str,
+    ) -> &mut Self {
+        let mut sku = sys::DiscordSku {
+            id,
+            type_: kind.into(),
+            ..sys::DiscordSku::default()
+        };
+
+        sku.price.amount = amount;
+        ffi::string_to_charbuf(&mut sku.name, name);
+        ffi::string_to_charbuf(&mut sku.price.currency, currency);
+
+        self.skus.push(sku);
+        self
+    }
+
+    /// Adds an entitlement owned by the current user, available once fetched
+    pub fn with_entitlement(
+        &mut self,
+        id: Snowflake,
+        kind: EntitlementKind,
+        sku_id: Snowflake,
+    ) -> &mut Self {
+        self.entitlements.push(sys::DiscordEntitlement {
+            id,
+            type_: kind.into(),
+            sku_id,
+        });
+        self
+    }
+
+    /// Adds a file to storage
+    pub fn with_file(&mut self, filename: &str, data: Vec<u8>) -> &mut Self {
+        self.files.push((filename.to_string(), data));
+        self
+    }
+
+    /// Nitro subscription of the current user
+    pub fn with_premium_kind(&mut self, kind: PremiumKind) -> &mut Self {
+        self.premium_type = kind.into();
+        self
+    }
+
+    /// Flags of the current user
+    pub fn with_user_flags(&mut self, flags: UserFlags) -> &mut Self {
+        self.user_flags = flags.bits();
+        self
+    }
+
+    /// Locale reported by [`Discord::current_locale`](struct.Discord.html#method.current_locale)
+    pub fn with_locale(&mut self, locale: &str) -> &mut Self {
+        self.locale = locale.to_string();
+        self
+    }
+
+    /// Branch reported by [`Discord::current_branch`](struct.Discord.html#method.current_branch)
+    pub fn with_branch(&mut self, branch: &str) -> &mut Self {
+        self.branch = branch.to_string();
+        self
+    }
+
+    /// Makes the instance misbehave as planned: failing calls, late callbacks, lost network
+    /// messages, events out of order or Discord closing, see [`MockFaults`](struct.MockFaults.html)
+    pub fn with_faults(&mut self, faults: MockFaults) -> &mut Self {
+        self.faults = faults;
+        self
+    }
+
+    /// Connects the instance to a simulated Discord shared with other instances, to play with
+    /// other users in lobbies and over the network, see [`MockService`](struct.MockService.html)
+    ///
+    /// Without a service, the instance is alone.
+    pub fn with_service(&mut self, service: &MockService) -> &mut Self {
+        self.service = Some(service.clone());
+        self
+    }
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl<E> Discord<'_, E> {
-    pub(crate) fn mock() -> Self
+    /// Creates an instance backed by an in-process mock of the SDK, with the default
+    /// [`MockConfig`](struct.MockConfig.html).
+    ///
+    /// Requires the `mock` feature.
+    pub fn mock() -> Self
+    where
+        E: EventHandler,
+    {
+        Self::mock_with(&MockConfig::new())
+    }
+
+    /// Creates an instance backed by an in-process mock of the SDK.
+    ///
+    /// The current user becomes available after the first call to
+    /// [`run_callbacks`](#method.run_callbacks), as with the real SDK.
+    ///
+    /// Requires the `mock` feature.
+    pub fn mock_with(config: &MockConfig) -> Self
+    where
+        E: EventHandler,
+    {
+        Self::mock_backed(config.client_id, |params| unsafe {
+            ffi::create_mock(params, config)
+        })
+    }
+
+    fn mock_backed(
+        client_id: ClientID,
+        create: impl FnOnce(sys::DiscordCreateParams) -> *mut sys::IDiscordCore,
+    ) -> Self
     where
         E: EventHandler,
     {
@@ -15,8 +287,14 @@ impl<E> Discord<'_, E> {
             _invariant_lifetime: PhantomData,
 
             core: std::ptr::null_mut(),
-            client_id: 0,
+            client_id,
             event_handler: UnsafeCell::new(None),
+            log_level: Cell::new(LogLevel::Debug),
+            log_sink: UnsafeCell::new(None),
+            log_sink_generation: Cell::new(0),
+
+            #[cfg(feature = "dynamic")]
+            library: None,
 
             achievement_events: events::achievement::<E>(),
             activity_events: events::activity::<E>(),
@@ -31,7 +309,10 @@ impl<E> Discord<'_, E> {
 
         let params = instance.create_params(CreateFlags::Default.into());
 
-        instance.inner_mut().core = unsafe { ffi::create_mock(params) };
+        instance.inner_mut().core = create(params);
+
+        instance.set_log_hook();
+        instance.kickstart_managers(crate::Managers::all());
 
         instance
     }
@@ -39,6 +320,8 @@ impl<E> Discord<'_, E> {
 
 #[test]
 fn miri_tests() {
+    use crate::UserAchievement;
+
     struct E;
 
     impl EventHandler for E {
@@ -99,3 +382,110 @@ fn miri_tests() {
         discord.set_user_achievement(0, 99, |_discord, _res| {});
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{fixtures::run, *};
+    use crate::UserAchievement;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn achievements_update_from_callbacks_and_events() {
+        struct E(Rc<RefCell<Vec<(i64, u8)>>>);
+
+        impl EventHandler for E {
+            fn on_user_achievement_update(
+                &mut self,
+                discord: &Discord<'_, Self>,
+                user_achievement: &UserAchievement,
+            ) {
+                let id = user_achievement.achievement_id();
+                let percent = user_achievement.percent_complete();
+
+                self.0.borrow_mut().push((id, percent));
+
+                if percent == 99 {
+                    discord.set_user_achievement(id, 100, |_, res| res.unwrap());
+                }
+            }
+        }
+
+        let mut config = MockConfig::new();
+        for achievement_id in 0..10 {
+            config.with_achievement(achievement_id, 0);
+        }
+
+        let updates = Rc::new(RefCell::new(Vec::new()));
+
+        let mut discord = Discord::mock_with(&config);
+        *discord.event_handler_mut() = Some(E(updates.clone()));
+
+        discord.fetch_user_achievements(|discord, res| {
+            res.unwrap();
+            discord.set_user_achievement(0, 99, |_, res| res.unwrap());
+        });
+
+        run(&mut discord);
+
+        assert_eq!(*updates.borrow(), vec![(0, 99), (0, 100)]);
+        assert_eq!(discord.iter_user_achievements().count(), 10);
+        assert_eq!(discord.user_achievement(0).unwrap().percent_complete(), 100);
+        assert_eq!(discord.user_achievement(1).unwrap().percent_complete(), 0);
+    }
+
+    #[test]
+    fn current_user_after_connecting() {
+        let mut config = MockConfig::new();
+        config.with_current_user(42, "player", "1234");
+
+        let mut discord = Discord::<()>::mock_with(&config);
+        assert!(discord.current_user().is_err());
+
+        run(&mut discord);
+
+        let user = discord.current_user().unwrap();
+        assert_eq!(user.id(), 42);
+        assert_eq!(user.username(), "player");
+        assert_eq!(user.discriminator(), "1234");
+    }
+
+    #[test]
+    fn storage_round_trip() {
+        let mut config = MockConfig::new();
+        config.with_file("existing", b"hello".to_vec());
+
+        let discord = Discord::<()>::mock_with(&config);
+
+        let mut buffer = [0; 16];
+        assert_eq!(discord.read_file("existing", &mut buffer[..]).unwrap(), 5);
+        assert_eq!(&buffer[..5], b"hello");
+
+        discord.write_file("new", b"world").unwrap();
+        assert!(discord.file_exists("new").unwrap());
+        assert_eq!(discord.file_stat_count(), 2);
+
+        discord.delete_file("new").unwrap();
+        assert!(!discord.file_exists("new").unwrap());
+    }
+
+    #[test]
+    fn store_purchase_grants_entitlement() {
+        let mut config = MockConfig::new();
+        config.with_sku(7, SkuKind::DLC, "Expansion", 999, "USD");
+
+        let mut discord = Discord::<()>::mock_with(&config);
+        let purchased = Rc::new(RefCell::new(None));
+
+        assert!(!discord.has_entitlement(7).unwrap());
+
+        discord.start_purchase(7, {
+            let purchased = purchased.clone();
+            move |_, res| *purchased.borrow_mut() = Some(res)
+        });
+
+        run(&mut discord);
+
+        assert_eq!(*purchased.borrow(), Some(Ok(())));
+        assert!(discord.has_entitlement(7).unwrap());
+    }
+}
@@ -0,0 +1,251 @@
+use super::ffi::{Lobby, Mock};
+use crate::{sys, UserID};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// Simulated Discord service that mocked instances connect to, see
+/// [`MockConfig::with_service`](struct.MockConfig.html#method.with_service)
+///
+/// Instances connected to the same service are users of the same Discord: they see each other
+/// as users, share lobbies along with their metadata and messages, receive each other's member,
+/// voice and lobby events, and can send each other network messages, through lobbies or by
+/// opening peers with the routes they were given.
+///
+/// Messages and events sent to other instances are received during their next call to
+/// [`run_callbacks`](struct.Discord.html#method.run_callbacks). The service can be cloned and
+/// sent to other threads, so that each instance can run on its own.
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # fn example() -> Result<()> {
+/// let service = MockService::new();
+///
+/// let mut config = MockConfig::new();
+/// config.with_current_user(1, "host", "0001").with_service(&service);
+/// let mut host = Discord::<()>::mock_with(&config);
+///
+/// config.with_current_user(2, "guest", "0002");
+/// let mut guest = Discord::<()>::mock_with(&config);
+///
+/// host.create_lobby(LobbyTransaction::new().kind(LobbyKind::Public), |_, _| {});
+/// host.run_callbacks()?;
+///
+/// guest.lobby_search(&SearchQuery::new(), |_, _| {});
+/// guest.run_callbacks()?;
+///
+/// assert_eq!(guest.lobby_count(), 1);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Default)]
+pub struct MockService {
+    pub(crate) service: Arc<Mutex<Service>>,
+}
+
+/// Runs on the instance it was delivered to, during its next call to `run_callbacks`
+pub(crate) type Delivery = Box<dyn Send + FnOnce(&Mock)>;
+
+pub(crate) type Inbox = Arc<Mutex<Vec<Delivery>>>;
+
+pub(crate) struct Client {
+    pub(crate) user: sys::DiscordUser,
+    pub(crate) peer_id: sys::DiscordNetworkPeerId,
+    pub(crate) inbox: Inbox,
+}
+
+pub(crate) struct Service {
+    pub(crate) next_id: i64,
+    pub(crate) lobbies: BTreeMap<sys::DiscordLobbyId, Lobby>,
+    pub(crate) clients: Vec<Client>,
+}
+
+impl Default for Service {
+    fn default() -> Self {
+        Self {
+            next_id: 1_000,
+            lobbies: BTreeMap::new(),
+            clients: Vec::new(),
+        }
+    }
+}
+
+impl Service {
+    pub(crate) fn next_id(&mut self) -> i64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    /// Hands `delivery` to every instance of `user_id`
+    pub(crate) fn deliver(
+        &self,
+        user_id: sys::DiscordUserId,
+        delivery: impl 'static + Send + Clone + FnOnce(&Mock),
+    ) {
+        for client in self
+            .clients
+            .iter()
+            .filter(|client| client.user.id == user_id)
+        {
+            client
+                .inbox
+                .lock()
+                .unwrap()
+                .push(Box::new(delivery.clone()));
+        }
+    }
+}
+
+impl MockService {
+    /// Creates a service without instances or lobbies
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes a user start or stop speaking in the voice chat of every lobby they are connected
+    /// to, their fellow members get [`Event::Speaking`](enum.Event.html#variant.Speaking)
+    pub fn set_speaking(&self, user_id: UserID, speaking: bool) {
+        let service = self.service.lock().unwrap();
+
+        for lobby in service.lobbies.values() {
+            if !matches!(lobby.member(user_id), Some(member) if member.voice) {
+                continue;
+            }
+
+            let lobby_id = lobby.lobby.id;
+
+            for member in &lobby.members {
+                service.deliver(member.user.id, move |mock| unsafe {
+                    (*mock.params.lobby_events).on_speaking.unwrap()(
+                        mock.params.event_data,
+                        lobby_id,
+                        user_id,
+                        speaking,
+                    )
+                });
+            }
+        }
+    }
+}
+
+impl fmt::Debug for MockService {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let service = self.service.lock().unwrap();
+
+        fmt.debug_struct("MockService")
+            .field("instances", &service.clients.len())
+            .field("lobbies", &service.lobbies.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::fixtures::{create_lobby, queued, service_user},
+        Discord, Event, EventQueue, LobbyKind, LobbyTransaction, Reliability, SearchQuery,
+    };
+
+    #[test]
+    fn instances_meet_through_a_service() {
+        fn tick(instances: &mut [&mut Discord<'_, EventQueue>]) -> Vec<Vec<Event>> {
+            instances
+                .iter_mut()
+                .map(|discord| {
+                    discord.run_callbacks().unwrap();
+                    discord.drain_events().collect()
+                })
+                .collect()
+        }
+
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<MockService>();
+
+        let service = MockService::new();
+        let mut host = queued(&service_user(&service, 1, "host"));
+        let mut guest = queued(&service_user(&service, 2, "guest"));
+
+        let lobby = create_lobby(
+            &mut host,
+            LobbyTransaction::new()
+                .kind(LobbyKind::Public)
+                .add_metadata("mode".to_string(), "ranked".to_string()),
+        );
+
+        let lobby_id = lobby.id();
+        guest.lobby_search(&SearchQuery::new(), |_, res| res.unwrap());
+        tick(&mut [&mut host, &mut guest]);
+
+        assert_eq!(guest.lobby_id_at(0).unwrap(), lobby_id);
+        assert_eq!(guest.lobby_metadata(lobby_id, "mode").unwrap(), "ranked");
+
+        guest.connect_lobby(lobby_id, lobby.secret(), |_, res| assert!(res.is_ok()));
+        host.connect_lobby_voice(lobby_id, |_, res| res.unwrap());
+
+        let events = tick(&mut [&mut guest, &mut host]);
+        assert_eq!(
+            events[1],
+            vec![Event::MemberConnect {
+                lobby_id,
+                member_id: 2
+            }]
+        );
+        assert_eq!(
+            guest.lobby_member_user(lobby_id, 1).unwrap().username(),
+            "host"
+        );
+
+        guest.send_lobby_message(lobby_id, b"hello", |_, res| res.unwrap());
+
+        let events = tick(&mut [&mut guest, &mut host]);
+        let message = Event::LobbyMessage {
+            lobby_id,
+            member_id: 2,
+            data: b"hello".to_vec(),
+        };
+        assert_eq!(events, vec![vec![message.clone()], vec![message]]);
+
+        service.set_speaking(1, true);
+
+        let events = tick(&mut [&mut guest, &mut host]);
+        let speaking = Event::Speaking {
+            lobby_id,
+            member_id: 1,
+            speaking: true,
+        };
+        assert_eq!(events, vec![vec![speaking.clone()], vec![speaking]]);
+
+        host.open_peer(2, "mock-route-2").unwrap();
+        host.open_channel(2, 0, Reliability::Reliable).unwrap();
+        host.send_message(2, 0, b"dropped").unwrap();
+        tick(&mut [&mut host, &mut guest]);
+
+        guest.open_peer(1, "mock-route-1").unwrap();
+        guest.open_channel(1, 0, Reliability::Reliable).unwrap();
+        host.send_message(2, 0, b"ping").unwrap();
+
+        let events = tick(&mut [&mut host, &mut guest]);
+        assert_eq!(
+            events[1],
+            vec![Event::NetworkMessage {
+                peer_id: 1,
+                channel_id: 0,
+                data: b"ping".to_vec(),
+            }]
+        );
+
+        drop(guest);
+
+        let events = tick(&mut [&mut host]);
+        assert_eq!(
+            events[0],
+            vec![Event::MemberDisconnect {
+                lobby_id,
+                member_id: 2
+            }]
+        );
+        assert_eq!(host.lobby_member_count(lobby_id).unwrap(), 1);
+    }
+}
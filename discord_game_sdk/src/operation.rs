@@ -0,0 +1,137 @@
+use crate::sys;
+
+/// Asynchronous SDK operation, from the request until its callback fires
+///
+/// With the `tracing` feature, this is a span named after the method, with the IDs the method
+/// was given and its result once it completes.
+pub(crate) struct Operation {
+    #[cfg(feature = "tracing")]
+    pub(crate) span: tracing::Span,
+}
+
+/// Creates the [`Operation`] of a method, named after it and given its IDs:
+/// `operation!("update_member", lobby_id, user_id)`
+#[cfg(feature = "tracing")]
+macro_rules! operation {
+    ($name:literal $(, $field:ident $(= $value:expr)?)* $(,)?) => {
+        $crate::operation::Operation {
+            span: tracing::debug_span!(
+                $name,
+                $( $field $(= $value)?, )*
+                result = tracing::field::Empty,
+            ),
+        }
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! operation {
+    ($name:literal $(, $field:ident $(= $value:expr)?)* $(,)?) => {
+        $crate::operation::Operation {}
+    };
+}
+
+impl Operation {
+    /// Runs the callback of the operation, within its span
+    #[cfg(feature = "tracing")]
+    pub(crate) fn complete<R>(
+        self,
+        result: sys::EDiscordResult,
+        callback: impl FnOnce() -> R,
+    ) -> R {
+        use crate::to_result::ToResult;
+
+        match result.to_result() {
+            Ok(()) => self.span.record("result", "Ok"),
+            Err(error) => self.span.record("result", tracing::field::display(error)),
+        };
+
+        self.span.in_scope(callback)
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn complete<R>(self, _: sys::EDiscordResult, callback: impl FnOnce() -> R) -> R {
+        callback()
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use crate::{mock::fixtures::run, Activity, Discord};
+
+    #[test]
+    fn operations_and_events_are_traced() {
+        use std::sync::{Arc, Mutex};
+        use tracing::{
+            field::{Field, Visit},
+            span, Event, Metadata, Subscriber,
+        };
+
+        #[derive(Default)]
+        struct Recorder {
+            names: Mutex<Vec<&'static str>>,
+            lines: Arc<Mutex<Vec<String>>>,
+        }
+
+        struct Fields<'a>(&'a mut String);
+
+        impl Visit for Fields<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0.push_str(&format!(" {}={:?}", field, value));
+            }
+        }
+
+        impl Subscriber for Recorder {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+                let mut names = self.names.lock().unwrap();
+                let mut line = span.metadata().name().to_string();
+                span.record(&mut Fields(&mut line));
+
+                names.push(span.metadata().name());
+                self.lines.lock().unwrap().push(line);
+                span::Id::from_u64(names.len() as u64)
+            }
+
+            fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+                let mut line = self.names.lock().unwrap()[span.into_u64() as usize - 1].to_string();
+                values.record(&mut Fields(&mut line));
+
+                self.lines.lock().unwrap().push(line);
+            }
+
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+            fn event(&self, _: &Event<'_>) {}
+
+            fn enter(&self, _: &span::Id) {}
+
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let recorder = Recorder::default();
+        let lines = recorder.lines.clone();
+
+        tracing::subscriber::with_default(recorder, || {
+            let mut discord = Discord::<()>::mock();
+
+            discord.update_activity(&Activity::empty(), |_, _| {});
+            discord.delete_lobby(404, |_, _| {});
+            run(&mut discord);
+        });
+
+        let lines = lines.lock().unwrap();
+        for line in &[
+            "update_activity",
+            "update_activity result=\"Ok\"",
+            "delete_lobby lobby_id=404",
+            "delete_lobby result=not found",
+            "event name=\"on_current_user_update\"",
+        ] {
+            assert!(lines.iter().any(|recorded| recorded == line), "{}", line);
+        }
+    }
+}
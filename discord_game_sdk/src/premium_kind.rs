@@ -4,6 +4,7 @@ use crate::sys;
 ///
 /// > [Enum in official docs](https://discordapp.com/developers/docs/game-sdk/users#data-models-premiumtype-enum)
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PremiumKind {
     /// Not a Nitro subscriber
     None,
@@ -25,3 +26,14 @@ impl From<sys::EDiscordPremiumType> for PremiumKind {
         }
     }
 }
+
+impl From<PremiumKind> for sys::EDiscordPremiumType {
+    fn from(source: PremiumKind) -> Self {
+        match source {
+            PremiumKind::None => sys::DiscordPremiumType_None,
+            PremiumKind::Tier1 => sys::DiscordPremiumType_Tier1,
+            PremiumKind::Tier2 => sys::DiscordPremiumType_Tier2,
+            PremiumKind::Undefined(n) => n,
+        }
+    }
+}
@@ -4,6 +4,7 @@ use crate::sys;
 ///
 /// > [Enum in official docs](https://discordapp.com/developers/docs/game-sdk/relationships#data-models-relationshiptype-enum)
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RelationshipKind {
     /// User is blocked
     Blocked,
@@ -34,3 +35,17 @@ impl From<sys::EDiscordRelationshipType> for RelationshipKind {
         }
     }
 }
+
+impl From<RelationshipKind> for sys::EDiscordRelationshipType {
+    fn from(source: RelationshipKind) -> Self {
+        match source {
+            RelationshipKind::Blocked => sys::DiscordRelationshipType_Blocked,
+            RelationshipKind::Friend => sys::DiscordRelationshipType_Friend,
+            RelationshipKind::Implicit => sys::DiscordRelationshipType_Implicit,
+            RelationshipKind::None => sys::DiscordRelationshipType_None,
+            RelationshipKind::PendingIncoming => sys::DiscordRelationshipType_PendingIncoming,
+            RelationshipKind::PendingOutgoing => sys::DiscordRelationshipType_PendingOutgoing,
+            RelationshipKind::Undefined(n) => n,
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{
+    discord::{Discord, DiscordInner},
+    discord_builder::Setup,
+    events, sys, ClientID, CreateFlags, Error, EventHandler, LogLevel, Managers, Result,
+};
+use std::{
+    cell::{Cell, UnsafeCell},
+    convert::TryFrom,
+    io::{BufReader, Read, Write},
+    marker::PhantomData,
+};
+
+#[macro_use]
+mod tape;
+
+mod play;
+mod record;
+
+pub(crate) use self::record::record;
+
+/// # Recording and Replay
+///
+/// An instance can write down everything that crosses the SDK boundary: every call into a
+/// manager with its arguments and the values the SDK returned, every callback, every event and
+/// every message the SDK logs, along with the time since the recording started. An instance
+/// created from that recording plays it back without the SDK, so a session can be reproduced
+/// exactly as it happened.
+///
+/// The program is expected to make the same calls in the same order. A call the recording
+/// does not have at that point fails with `Error::Internal`, or calls back with it during the
+/// next [`run_callbacks`](#method.run_callbacks); calls that are not made anymore are skipped.
+/// Both are logged as warnings.
+///
+/// Requires the `replay` feature.
+impl<E> Discord<'_, E> {
+    /// Creates an instance of the main interface with the SDK, as
+    /// [`with_create_flags`](#method.with_create_flags) does, and records its use to
+    /// `recording`.
+    ///
+    /// `recording` is flushed after every call to `run_callbacks`. If writing fails, the error
+    /// is logged and recording stops; the instance keeps working.
+    ///
+    /// ```rust,no_run
+    /// # use discord_game_sdk::*;
+    /// # const DISCORD_CLIENT_ID: ClientID = 0;
+    /// # fn example() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    /// let recording = std::io::BufWriter::new(std::fs::File::create("session.rec")?);
+    /// let discord = Discord::<()>::with_recording(DISCORD_CLIENT_ID, CreateFlags::Default, recording)?;
+    ///
+    /// // Later, without Discord
+    /// let recording = std::fs::File::open("session.rec")?;
+    /// let discord = Discord::<()>::replay(recording)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn with_recording(
+        client_id: ClientID,
+        flags: CreateFlags,
+        recording: impl 'static + Write,
+    ) -> Result<Self>
+    where
+        E: EventHandler,
+    {
+        #[cfg(feature = "dynamic")]
+        return Self::create(
+            client_id,
+            flags,
+            Self::open_library(sys::DEFAULT_LIBRARY_NAME.as_ref())?,
+            Some(Box::new(recording)),
+            Setup::default(),
+        );
+
+        #[cfg(not(feature = "dynamic"))]
+        return Self::create(
+            client_id,
+            flags,
+            Some(Box::new(recording)),
+            Setup::default(),
+        );
+    }
+
+    /// Creates an instance that plays back a recording made with
+    /// [`with_recording`](#method.with_recording).
+    ///
+    /// Every call to `run_callbacks` plays the recording up to where `run_callbacks` returned
+    /// during the recording. Once the recording is over, `run_callbacks` fails with
+    /// `Error::NotRunning`.
+    ///
+    /// ## Errors
+    ///
+    /// [`Error::InvalidPayload`](enum.Error.html#variant.InvalidPayload) if `recording` could
+    /// not be read, the reason is logged.
+    pub fn replay(recording: impl Read) -> Result<Self>
+    where
+        E: EventHandler,
+    {
+        let recording = tape::read(BufReader::new(recording)).map_err(|error| {
+            log::error!("could not read recording: {}", error);
+            Error::InvalidPayload
+        })?;
+
+        let flags = sys::EDiscordCreateFlags::try_from(recording.flags).map_err(|_| {
+            log::error!("could not read recording: invalid flags");
+            Error::InvalidPayload
+        })?;
+
+        log::debug!("replaying with client ID {}", recording.client_id);
+
+        let mut instance = Discord(Box::into_raw(Box::new(DiscordInner {
+            _invariant_lifetime: PhantomData,
+
+            core: std::ptr::null_mut(),
+            client_id: recording.client_id,
+            event_handler: UnsafeCell::new(None),
+            log_level: Cell::new(LogLevel::Debug),
+            log_sink: UnsafeCell::new(None),
+            log_sink_generation: Cell::new(0),
+
+            #[cfg(feature = "dynamic")]
+            library: None,
+
+            achievement_events: events::achievement::<E>(),
+            activity_events: events::activity::<E>(),
+            lobby_events: events::lobby::<E>(),
+            network_events: events::network::<E>(),
+            overlay_events: events::overlay::<E>(),
+            relationship_events: events::relationship::<E>(),
+            store_events: events::store::<E>(),
+            user_events: events::user::<E>(),
+            voice_events: events::voice::<E>(),
+        })));
+
+        let params = instance.create_params(flags);
+
+        instance.inner_mut().core = unsafe { play::create_player(params, recording.entries) };
+
+        instance.set_log_hook();
+        instance.kickstart_managers(Managers::all());
+
+        Ok(instance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{
+            fixtures::{recorded, run},
+            MockConfig,
+        },
+        Activity, EventQueue, LobbyTransaction, RelationshipKind, Status,
+    };
+    use std::{cell::RefCell, io, rc::Rc};
+
+    #[test]
+    fn recorded_sessions_replay_identically() {
+        #[derive(Clone, Default)]
+        struct Shared(Rc<RefCell<Vec<u8>>>);
+
+        impl Write for Shared {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.borrow_mut().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        // Everything the program observes, callbacks and events in the order they happen
+        fn session(discord: &mut Discord<'_, EventQueue>) -> Vec<String> {
+            *discord.event_handler_mut() = Some(EventQueue::default());
+            let seen = Rc::new(RefCell::new(Vec::new()));
+
+            discord.run_callbacks().unwrap();
+            seen.borrow_mut().push(format!(
+                "{:?}",
+                discord.current_user().map(|user| user.id())
+            ));
+
+            let s = seen.clone();
+            discord.create_lobby(
+                LobbyTransaction::new().capacity(4),
+                move |discord, lobby| {
+                    let lobby_id = lobby.unwrap().id();
+                    s.borrow_mut().push(format!("created {}", lobby_id));
+
+                    let s = s.clone();
+                    discord.send_lobby_message(lobby_id, b"hello", move |_, res| {
+                        s.borrow_mut().push(format!("sent {:?}", res))
+                    });
+                },
+            );
+
+            let s = seen.clone();
+            discord.update_activity(Activity::empty().with_state("In a match"), move |_, res| {
+                s.borrow_mut().push(format!("activity {:?}", res))
+            });
+
+            discord.write_file("save", b"level 2").unwrap();
+            let s = seen.clone();
+            discord.read_file_async("save", move |_, res| {
+                s.borrow_mut().push(format!("read {:?}", res))
+            });
+
+            discord.filter_relationships(|_| true);
+            seen.borrow_mut()
+                .push(format!("relationships {:?}", discord.relationship_count()));
+
+            run(discord);
+
+            seen.borrow_mut().extend(
+                discord
+                    .drain_events()
+                    .map(|event| format!("event {:?}", event)),
+            );
+
+            let seen = seen.borrow().clone();
+            seen
+        }
+
+        let mut config = MockConfig::new();
+        config.with_relationship(RelationshipKind::Friend, 43, Status::Online);
+
+        let recording = Shared::default();
+        let mut recorded = recorded(&config, recording.clone());
+
+        let seen = session(&mut recorded);
+        drop(recorded);
+
+        assert!(seen.contains(&"sent Ok(())".to_string()));
+        assert!(seen.contains(&"read Ok([108, 101, 118, 101, 108, 32, 50])".to_string()));
+
+        let file = recording.0.borrow().clone();
+        assert!(String::from_utf8_lossy(&file).contains(" event core.log "));
+
+        let mut replayed = Discord::<EventQueue>::replay(&file[..]).unwrap();
+        assert_eq!(session(&mut replayed), seen);
+        assert_eq!(replayed.run_callbacks(), Err(Error::NotRunning));
+
+        // The recording has no call before the first tick, this one fails during it
+        let mut replayed = Discord::<EventQueue>::replay(&file[..]).unwrap();
+        let cleared = Rc::new(RefCell::new(None));
+        let c = cleared.clone();
+        replayed.clear_activity(move |_, res| *c.borrow_mut() = Some(res));
+        replayed.run_callbacks().unwrap();
+        assert_eq!(*cleared.borrow(), Some(Err(Error::Internal)));
+        assert_eq!(replayed.current_user().unwrap().id(), 1);
+
+        assert_eq!(
+            Discord::<()>::replay(&b"not a recording"[..]).err(),
+            Some(Error::InvalidPayload)
+        );
+    }
+}
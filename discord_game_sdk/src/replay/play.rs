@@ -0,0 +1,484 @@
+// The player stands in for the SDK: calls are matched against the recording in order, the
+// values the SDK wrote are read back from it, and callbacks and events are fired where they
+// happened during the recording.
+//
+// A call the recording does not have at that point leaves the program behind the recording: it
+// fails with `InternalError`, and its callback is called with that error during the next tick.
+// Calls the program does not make anymore are skipped.
+
+use super::tape::{self, Entry, Tape};
+use crate::{
+    backend::{self, Backend, Interface, LogHook, Managers},
+    sys,
+    tables::Pointer,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::c_void,
+};
+
+// SAFETY: `interface` must point to the `vtable` of an `Interface<T, Player>` owned by a live
+// `Player`
+unsafe fn player<'a, T>(interface: *mut T) -> &'a Player {
+    backend::backend(interface)
+}
+
+trait Vtable {
+    const VTABLE: Self;
+}
+
+/// Hands out a transaction or search query, they only need to lead back to the player
+fn handle<T: Vtable>(player: &Player) -> *mut T {
+    let mut interface = Box::new(Interface::new(T::VTABLE));
+    interface.backend = player;
+
+    Box::into_raw(interface) as *mut T
+}
+
+/// Value returned by a call the recording does not have
+trait Returned: Tape {
+    const DIVERGED: Self;
+}
+
+impl Returned for sys::EDiscordResult {
+    const DIVERGED: Self = sys::DiscordResult_InternalError;
+}
+
+impl Returned for () {
+    const DIVERGED: Self = ();
+}
+
+type Args<'a> = Result<&'a [u8], sys::EDiscordResult>;
+
+type Once = Box<dyn FnOnce(Args<'_>)>;
+type Many = Box<dyn FnMut(&[u8])>;
+
+enum Callback {
+    Once(Once),
+    Many(Many),
+}
+
+pub(crate) struct Player {
+    core: Interface<sys::IDiscordCore, Player>,
+    managers: Managers<Player>,
+
+    params: sys::DiscordCreateParams,
+    log_hook: Cell<Option<(*mut c_void, LogHook)>>,
+
+    entries: Vec<Entry>,
+    cursor: Cell<usize>,
+    callbacks: RefCell<HashMap<u64, Callback>>,
+    diverged: RefCell<Vec<Once>>,
+}
+
+impl Player {
+    fn next_entry(&self) -> Option<Entry> {
+        let entry = self.entries.get(self.cursor.get())?.clone();
+        self.cursor.set(self.cursor.get() + 1);
+        Some(entry)
+    }
+
+    fn warn(&self, message: &str) {
+        log::warn!("replay diverged: {}", message);
+
+        if let Some((hook_data, hook)) = self.log_hook.get() {
+            let message = format!("replay diverged: {}\0", message);
+            unsafe { hook(hook_data, sys::DiscordLogLevel_Warn, message.as_ptr()) }
+        }
+    }
+
+    /// Hands a message the SDK logged during the recording to the program
+    fn log(&self, mut args: &[u8]) {
+        let level = sys::EDiscordLogLevel::decode(&mut args).unwrap_or(sys::DiscordLogLevel_Info);
+        let message = tape::decode_cstr(&mut args).unwrap_or_else(|| b"\0".to_vec());
+
+        if let Some((hook_data, hook)) = self.log_hook.get() {
+            unsafe { hook(hook_data, level, message.as_ptr()) }
+        }
+    }
+
+    /// Matches a call of the program against the next entry of the recording
+    fn call(&self, name: &str, input: &[u8]) -> Option<u64> {
+        match self.entries.get(self.cursor.get()) {
+            Some(Entry::Call {
+                seq,
+                name: recorded,
+                input: recorded_input,
+            }) if recorded == name => {
+                if recorded_input[..] != *input {
+                    self.warn(&format!("{} was called with other arguments", name));
+                }
+
+                self.cursor.set(self.cursor.get() + 1);
+                Some(*seq)
+            }
+            _ => {
+                self.warn(&format!("{} was not called at this point", name));
+                None
+            }
+        }
+    }
+
+    /// Plays the recording until the call `seq` returns, with the values the SDK wrote
+    fn finish(&self, seq: u64) -> Vec<u8> {
+        while let Some(entry) = self.next_entry() {
+            match entry {
+                Entry::Return {
+                    seq: returned,
+                    output,
+                } if returned == seq => return output,
+                entry => self.play(entry),
+            }
+        }
+
+        Vec::new()
+    }
+
+    /// Skips a call the program did not make, along with what happened during it
+    fn skip(&self, seq: u64, name: &str) {
+        self.warn(&format!("{} was not called", name));
+
+        while let Some(entry) = self.next_entry() {
+            if let Entry::Return { seq: returned, .. } = entry {
+                if returned == seq {
+                    break;
+                }
+            }
+        }
+    }
+
+    fn play(&self, entry: Entry) {
+        match entry {
+            Entry::Callback { seq, args } => {
+                let callback = self.callbacks.borrow_mut().remove(&seq);
+
+                match callback {
+                    Some(Callback::Once(callback)) => callback(Ok(&args)),
+                    Some(Callback::Many(mut callback)) => {
+                        callback(&args);
+                        self.callbacks
+                            .borrow_mut()
+                            .insert(seq, Callback::Many(callback));
+                    }
+                    // The call diverged, its callback was already given an error
+                    None => {}
+                }
+            }
+            Entry::Event { name, args } if name == tape::LOG_EVENT => self.log(&args),
+            Entry::Event { name, args } => unsafe { self.fire(&name, &args) },
+            Entry::Call { seq, name, .. } => self.skip(seq, &name),
+            Entry::Return { .. } | Entry::Tick | Entry::Ticked { .. } => {}
+        }
+    }
+
+    fn tick(&self) -> sys::EDiscordResult {
+        let diverged = std::mem::take(&mut *self.diverged.borrow_mut());
+
+        for callback in diverged {
+            callback(Err(sys::DiscordResult_InternalError));
+        }
+
+        loop {
+            match self.next_entry() {
+                Some(Entry::Tick) => break,
+                Some(entry) => self.play(entry),
+                None => return sys::DiscordResult_NotRunning,
+            }
+        }
+
+        loop {
+            match self.next_entry() {
+                Some(Entry::Ticked { result }) => return result,
+                Some(entry) => self.play(entry),
+                None => return sys::DiscordResult_NotRunning,
+            }
+        }
+    }
+
+    fn abort_all(&self) {
+        let callbacks = std::mem::take(&mut *self.callbacks.borrow_mut());
+        let diverged = std::mem::take(&mut *self.diverged.borrow_mut());
+
+        for (_, callback) in callbacks {
+            if let Callback::Once(callback) = callback {
+                callback(Err(sys::DiscordResult_TransactionAborted));
+            }
+        }
+
+        for callback in diverged {
+            callback(Err(sys::DiscordResult_TransactionAborted));
+        }
+    }
+}
+
+macro_rules! play_arg {
+    (val $arg:ident: $ty:ty, $input:ident) => {
+        let $arg: $ty = Tape::decode(&mut $input).unwrap_or_else(Tape::blank);
+    };
+    (ptr $arg:ident: $ty:ty, $input:ident) => {
+        let mut $arg =
+            <<$ty as Pointer>::Target as Tape>::decode(&mut $input).unwrap_or_else(Tape::blank);
+        let $arg: $ty = &mut $arg;
+    };
+    (str $arg:ident: $ty:ty, $input:ident) => {
+        let mut $arg = tape::decode_cstr(&mut $input).unwrap_or_else(|| vec![0]);
+        let $arg: $ty = $arg.as_mut_ptr();
+    };
+    (buf $arg:ident [$len:ident]: $ty:ty, $input:ident) => {
+        let mut $arg = tape::decode_bytes(&mut $input).unwrap_or_default();
+        let $len = $arg.len() as u32;
+        let $arg: $ty = $arg.as_mut_ptr();
+    };
+    (len $arg:ident: $ty:ty, $input:ident) => {};
+}
+
+macro_rules! play_handle {
+    (handle $arg:ident: $ty:ty) => {
+        if !$arg.is_null() {
+            drop(Box::from_raw(
+                $arg as *mut Interface<<$ty as Pointer>::Target, Player>,
+            ));
+        }
+    };
+    ($kind:ident $arg:ident $([$len:ident])?: $ty:ty) => {};
+}
+
+macro_rules! play_output {
+    (out $arg:ident, $player:ident, $output:ident) => {
+        *$arg = Tape::decode(&mut $output).unwrap_or_else(Tape::blank)
+    };
+    (out_buf $arg:ident [$len:ident], $player:ident, $output:ident) => {
+        if let (false, Some(data)) = ($arg.is_null(), tape::decode_bytes(&mut $output)) {
+            let len = data.len().min($len as usize);
+            std::slice::from_raw_parts_mut($arg, len).copy_from_slice(&data[..len]);
+        }
+    };
+    (out_handle $arg:ident, $player:ident, $output:ident) => {
+        *$arg = handle($player)
+    };
+    ($kind:ident $arg:ident $([$len:ident])?, $player:ident, $output:ident) => {};
+}
+
+macro_rules! play_methods {
+    ($(
+        $field:ident $iface:ident {
+            $(
+                fn $name:ident (
+                    $( $kind:ident $arg:ident $([$len:ident])? : $ty:ty ),*
+                )
+                $( => $callback:ident (
+                    $( $ckind:ident $carg:ident $([$clen:ident])? : $cty:ty ),*
+                ) )?
+                $( -> $ret:ty )?;
+            )*
+        } manual { $( $manual:ident ),* }
+    )*) => {
+        $(
+            impl Vtable for sys::$iface {
+                const VTABLE: Self = sys::$iface {
+                    $(
+                        $name: {
+                            // Not every function has arguments to encode or decode
+                            #[allow(unused_mut, unused_variables)]
+                            unsafe extern "C" fn $name(
+                                interface: *mut sys::$iface,
+                                $( $arg: $ty, )*
+                                $(
+                                    callback_data: *mut c_void,
+                                    $callback: Option<
+                                        unsafe extern "C" fn(
+                                            *mut c_void,
+                                            sys::EDiscordResult
+                                            $(, $cty)*
+                                        )
+                                    >,
+                                )?
+                            ) -> ret!($($ret)?) {
+                                let player = self::player(interface);
+
+                                let mut input = Vec::new();
+                                $( encode_arg!($kind $arg $([$len])?, input); )*
+
+                                $( play_handle!($kind $arg $([$len])?: $ty); )*
+
+                                let seq = player.call(
+                                    concat!(stringify!($field), ".", stringify!($name)),
+                                    &input,
+                                );
+
+                                $(
+                                    let $callback = move |args: Args<'_>| {
+                                        let (result, mut args) = match args {
+                                            Ok(mut args) => (
+                                                sys::EDiscordResult::decode(&mut args)
+                                                    .unwrap_or(sys::DiscordResult_InternalError),
+                                                args,
+                                            ),
+                                            Err(result) => (result, &[][..]),
+                                        };
+
+                                        $( play_arg!($ckind $carg $([$clen])?: $cty, args); )*
+
+                                        if let Some(callback) = $callback {
+                                            callback(callback_data, result $(, $carg)*)
+                                        }
+                                    };
+
+                                    match seq {
+                                        Some(seq) => {
+                                            player
+                                                .callbacks
+                                                .borrow_mut()
+                                                .insert(seq, Callback::Once(Box::new($callback)));
+                                        }
+                                        None => player.diverged.borrow_mut().push(Box::new($callback)),
+                                    }
+                                )?
+
+                                let output = match seq {
+                                    Some(seq) => player.finish(seq),
+                                    None => Vec::new(),
+                                };
+                                let mut output = &output[..];
+
+                                $( play_output!($kind $arg $([$len])?, player, output); )*
+
+                                <ret!($($ret)?)>::decode(&mut output).unwrap_or(Returned::DIVERGED)
+                            }
+
+                            Some($name)
+                        },
+                    )*
+                    $( $manual: Some(manual::$manual), )*
+                };
+            }
+        )*
+    };
+}
+
+sdk_methods!(play_methods);
+sdk_handles!(play_methods);
+
+mod manual {
+    use super::*;
+
+    pub(super) unsafe extern "C" fn filter(
+        interface: *mut sys::IDiscordRelationshipManager,
+        filter_data: *mut c_void,
+        filter: Option<unsafe extern "C" fn(*mut c_void, *mut sys::DiscordRelationship) -> bool>,
+    ) {
+        let player = super::player(interface);
+
+        let seq = match player.call("relationships.filter", &[]) {
+            Some(seq) => seq,
+            None => return,
+        };
+
+        // The results of the filter were recorded, the program's filter is still given every
+        // relationship it saw then
+        let callback = move |mut args: &[u8]| {
+            let mut relationship =
+                sys::DiscordRelationship::decode(&mut args).unwrap_or_else(Tape::blank);
+
+            filter.unwrap()(filter_data, &mut relationship);
+        };
+
+        player
+            .callbacks
+            .borrow_mut()
+            .insert(seq, Callback::Many(Box::new(callback)));
+
+        player.finish(seq);
+        player.callbacks.borrow_mut().remove(&seq);
+    }
+}
+
+macro_rules! play_events {
+    ($(
+        $field:ident $iface:ident {
+            $( fn $name:ident ( $( $kind:ident $arg:ident $([$len:ident])? : $ty:ty ),* ); )*
+        }
+    )*) => {
+        impl Player {
+            unsafe fn fire(&self, name: &str, mut args: &[u8]) {
+                $($(
+                    if name == concat!(stringify!($field), ".", stringify!($name)) {
+                        $( play_arg!($kind $arg $([$len])?: $ty, args); )*
+
+                        let events = self.params.$field;
+
+                        if let Some(event) = events.as_ref().and_then(|events| events.$name) {
+                            event(self.params.event_data $(, $arg)*)
+                        }
+
+                        return;
+                    }
+                )*)*
+
+                self.warn(&format!("unknown event {}", name));
+            }
+        }
+    };
+}
+
+sdk_events!(play_events);
+
+macro_rules! play_managers {
+    ($( $getter:ident $field:ident $iface:ident ),*) => {
+        fn managers() -> Managers<Player> {
+            Managers {
+                $( $field: Interface::new(<sys::$iface as Vtable>::VTABLE), )*
+            }
+        }
+    };
+}
+
+sdk_managers!(play_managers);
+
+impl Backend for Player {
+    fn managers(&self) -> &Managers<Self> {
+        &self.managers
+    }
+
+    unsafe fn destroy(player: *mut Self) {
+        (*player).abort_all();
+
+        drop(Box::from_raw(player));
+    }
+
+    unsafe fn run_callbacks(&self) -> sys::EDiscordResult {
+        self.tick()
+    }
+
+    unsafe fn set_log_hook(
+        &self,
+        _min_level: sys::EDiscordLogLevel,
+        hook: Option<(*mut c_void, LogHook)>,
+    ) {
+        self.log_hook.set(hook);
+    }
+}
+
+pub(crate) unsafe fn create_player(
+    params: sys::DiscordCreateParams,
+    entries: Vec<Entry>,
+) -> *mut sys::IDiscordCore {
+    let player = Box::into_raw(Box::new(Player {
+        core: backend::core(),
+        managers: managers(),
+
+        params,
+        log_hook: Cell::new(None),
+
+        entries,
+        cursor: Cell::new(0),
+        callbacks: RefCell::new(HashMap::new()),
+        diverged: RefCell::new(Vec::new()),
+    }));
+
+    (*player).core.backend = player;
+    (*player).managers.attach(player);
+
+    (*player).core.as_ptr()
+}
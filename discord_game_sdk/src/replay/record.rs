@@ -0,0 +1,482 @@
+// The recorder sits between the SDK and the program: every table of function pointers handed to
+// the program is one of ours, which writes down the call, forwards it to the SDK, and writes
+// down what came back. The SDK is given our event tables, which do the same for events.
+
+use super::tape::{self, Entry, Tape};
+use crate::{
+    backend::{self, Backend, Interface, LogHook, Managers},
+    sys,
+};
+use std::{
+    cell::{Cell, RefCell},
+    ffi::c_void,
+    io::Write,
+    time::Instant,
+};
+
+// SAFETY: `interface` must point to the `vtable` of an `Interface<T, Recorder>` whose `Recorder` is
+// alive
+unsafe fn interface<'a, T>(interface: *mut T) -> &'a Interface<T, Recorder> {
+    backend::interface(interface)
+}
+
+trait Vtable {
+    const VTABLE: Self;
+}
+
+/// Hands out a transaction or search query of the SDK wrapped in one of ours
+unsafe fn wrap<T: Vtable>(recorder: &Recorder, real: *mut T) -> *mut T {
+    if real.is_null() {
+        return real;
+    }
+
+    let mut interface = Box::new(Interface::new(T::VTABLE));
+    interface.backend = recorder;
+    interface.real.set(real);
+
+    Box::into_raw(interface) as *mut T
+}
+
+/// Takes back a transaction or search query handed out by `wrap`
+unsafe fn unwrap<T>(wrapped: *mut T) -> *mut T {
+    if wrapped.is_null() {
+        return wrapped;
+    }
+
+    Box::from_raw(wrapped as *mut Interface<T, Recorder>)
+        .real
+        .get()
+}
+
+/// Callback of the program, waiting on the SDK
+struct Pending<C> {
+    recorder: *const Recorder,
+    seq: u64,
+    data: *mut c_void,
+    callback: C,
+}
+
+pub(crate) struct Recorder {
+    core: Interface<sys::IDiscordCore, Recorder>,
+    managers: Managers<Recorder>,
+    events: Events,
+
+    real_core: *mut sys::IDiscordCore,
+    app: sys::DiscordCreateParams,
+
+    writer: RefCell<Option<Box<dyn Write>>>,
+    start: Instant,
+    next_seq: Cell<u64>,
+    log_hook: Cell<Option<(*mut c_void, LogHook)>>,
+}
+
+impl Recorder {
+    fn write(&self, entry: &Entry) {
+        let mut writer = self.writer.borrow_mut();
+
+        if let Some(output) = writer.as_mut() {
+            let micros = self.start.elapsed().as_micros();
+
+            if let Err(error) = tape::write_entry(output, micros, entry) {
+                log::error!("recording stopped, could not write to it: {}", error);
+                *writer = None;
+            }
+        }
+    }
+
+    fn flush(&self) {
+        let mut writer = self.writer.borrow_mut();
+
+        if let Some(Err(error)) = writer.as_mut().map(|output| output.flush()) {
+            log::error!("recording stopped, could not write to it: {}", error);
+            *writer = None;
+        }
+    }
+
+    /// Writes down a call, before it is forwarded
+    fn call(&self, name: &str, input: Vec<u8>) -> u64 {
+        let seq = self.next_seq.get();
+        self.next_seq.set(seq + 1);
+
+        self.write(&Entry::Call {
+            seq,
+            name: name.to_string(),
+            input,
+        });
+
+        seq
+    }
+}
+
+macro_rules! record_handle {
+    (handle $arg:ident: $ty:ty) => {
+        let $arg = unwrap($arg);
+    };
+    ($kind:ident $arg:ident $([$len:ident])?: $ty:ty) => {};
+}
+
+macro_rules! record_output {
+    (out $arg:ident, $recorder:ident, $output:ident) => {
+        Tape::encode(&*$arg, &mut $output)
+    };
+    (out_buf $arg:ident [$len:ident], $recorder:ident, $output:ident) => {
+        tape::encode_bytes(tape::bytes($arg, $len), &mut $output)
+    };
+    (out_handle $arg:ident, $recorder:ident, $output:ident) => {
+        *$arg = wrap($recorder, *$arg)
+    };
+    ($kind:ident $arg:ident $([$len:ident])?, $recorder:ident, $output:ident) => {};
+}
+
+macro_rules! record_methods {
+    ($(
+        $field:ident $iface:ident {
+            $(
+                fn $name:ident (
+                    $( $kind:ident $arg:ident $([$len:ident])? : $ty:ty ),*
+                )
+                $( => $callback:ident (
+                    $( $ckind:ident $carg:ident $([$clen:ident])? : $cty:ty ),*
+                ) )?
+                $( -> $ret:ty )?;
+            )*
+        } manual { $( $manual:ident ),* }
+    )*) => {
+        $(
+            impl Vtable for sys::$iface {
+                const VTABLE: Self = sys::$iface {
+                    $(
+                        $name: {
+                            // Not every function has arguments to encode or decode
+                            #[allow(unused_mut, unused_variables)]
+                            unsafe extern "C" fn $name(
+                                interface: *mut sys::$iface,
+                                $( $arg: $ty, )*
+                                $(
+                                    callback_data: *mut c_void,
+                                    $callback: Option<
+                                        unsafe extern "C" fn(
+                                            *mut c_void,
+                                            sys::EDiscordResult
+                                            $(, $cty)*
+                                        )
+                                    >,
+                                )?
+                            ) -> ret!($($ret)?) {
+                                let interface = self::interface(interface);
+                                let recorder = &*interface.backend;
+
+                                let mut input = Vec::new();
+                                $( encode_arg!($kind $arg $([$len])?, input); )*
+
+                                let seq = recorder.call(
+                                    concat!(stringify!($field), ".", stringify!($name)),
+                                    input,
+                                );
+
+                                $( record_handle!($kind $arg $([$len])?: $ty); )*
+
+                                $(
+                                    type Callback = Option<
+                                        unsafe extern "C" fn(
+                                            *mut c_void,
+                                            sys::EDiscordResult
+                                            $(, $cty)*
+                                        )
+                                    >;
+
+                                    unsafe extern "C" fn trampoline(
+                                        data: *mut c_void,
+                                        result: sys::EDiscordResult,
+                                        $( $carg: $cty ),*
+                                    ) {
+                                        let pending = Box::from_raw(data as *mut Pending<Callback>);
+
+                                        let mut args = Vec::new();
+                                        result.encode(&mut args);
+                                        $( encode_arg!($ckind $carg $([$clen])?, args); )*
+
+                                        (*pending.recorder).write(&Entry::Callback {
+                                            seq: pending.seq,
+                                            args,
+                                        });
+
+                                        if let Some(callback) = pending.callback {
+                                            callback(pending.data, result $(, $carg)*)
+                                        }
+                                    }
+
+                                    let pending = Box::new(Pending {
+                                        recorder,
+                                        seq,
+                                        data: callback_data,
+                                        callback: $callback,
+                                    });
+
+                                    let $callback: (*mut c_void, Callback) =
+                                        (Box::into_raw(pending) as *mut c_void, Some(trampoline));
+                                )?
+
+                                let real = interface.real.get();
+                                let ret = (*real).$name.unwrap()(
+                                    real,
+                                    $( $arg, )*
+                                    $( $callback.0, $callback.1, )?
+                                );
+
+                                let mut output = Vec::new();
+                                $( record_output!($kind $arg $([$len])?, recorder, output); )*
+                                ret.encode(&mut output);
+
+                                recorder.write(&Entry::Return { seq, output });
+
+                                ret
+                            }
+
+                            Some($name)
+                        },
+                    )*
+                    $( $manual: Some(manual::$manual), )*
+                };
+            }
+        )*
+    };
+}
+
+sdk_methods!(record_methods);
+sdk_handles!(record_methods);
+
+mod manual {
+    use super::*;
+
+    type Filter = Option<unsafe extern "C" fn(*mut c_void, *mut sys::DiscordRelationship) -> bool>;
+
+    // Called for every relationship before `filter` returns
+    unsafe extern "C" fn trampoline(
+        data: *mut c_void,
+        relationship: *mut sys::DiscordRelationship,
+    ) -> bool {
+        let pending = &*(data as *const Pending<Filter>);
+        let keep = pending.callback.unwrap()(pending.data, relationship);
+
+        let mut args = Vec::new();
+        (*relationship).encode(&mut args);
+        keep.encode(&mut args);
+
+        (*pending.recorder).write(&Entry::Callback {
+            seq: pending.seq,
+            args,
+        });
+
+        keep
+    }
+
+    pub(super) unsafe extern "C" fn filter(
+        interface: *mut sys::IDiscordRelationshipManager,
+        filter_data: *mut c_void,
+        filter: Filter,
+    ) {
+        let interface = super::interface(interface);
+        let recorder = &*interface.backend;
+
+        let seq = recorder.call("relationships.filter", Vec::new());
+
+        let pending = Pending {
+            recorder,
+            seq,
+            data: filter_data,
+            callback: filter,
+        };
+
+        let real = interface.real.get();
+        (*real).filter.unwrap()(
+            real,
+            &pending as *const Pending<Filter> as *mut c_void,
+            Some(trampoline),
+        );
+
+        recorder.write(&Entry::Return {
+            seq,
+            output: Vec::new(),
+        });
+    }
+}
+
+macro_rules! record_events {
+    ($(
+        $field:ident $iface:ident {
+            $( fn $name:ident ( $( $kind:ident $arg:ident $([$len:ident])? : $ty:ty ),* ); )*
+        }
+    )*) => {
+        struct Events {
+            $( $field: sys::$iface, )*
+        }
+
+        const EVENTS: Events = Events {
+            $(
+                $field: sys::$iface {
+                    $(
+                        $name: {
+                            // Not every function has arguments to encode or decode
+                            #[allow(unused_mut, unused_variables)]
+                            unsafe extern "C" fn $name(event_data: *mut c_void, $( $arg: $ty ),*) {
+                                let recorder = &*(event_data as *const Recorder);
+
+                                let mut args = Vec::new();
+                                $( encode_arg!($kind $arg $([$len])?, args); )*
+
+                                recorder.write(&Entry::Event {
+                                    name: concat!(stringify!($field), ".", stringify!($name))
+                                        .to_string(),
+                                    args,
+                                });
+
+                                let events = recorder.app.$field;
+
+                                if let Some(event) = events.as_ref().and_then(|events| events.$name) {
+                                    event(recorder.app.event_data $(, $arg)*)
+                                }
+                            }
+
+                            Some($name)
+                        },
+                    )*
+                },
+            )*
+        };
+
+        impl Recorder {
+            /// Parameters for the SDK: events go through the recorder first
+            fn params(&self) -> sys::DiscordCreateParams {
+                sys::DiscordCreateParams {
+                    event_data: self as *const Recorder as *mut c_void,
+                    $( $field: &self.events.$field as *const sys::$iface as *mut sys::$iface, )*
+                    ..self.app
+                }
+            }
+        }
+    };
+}
+
+sdk_events!(record_events);
+
+macro_rules! record_managers {
+    ($( $getter:ident $field:ident $iface:ident ),*) => {
+        fn managers() -> Managers<Recorder> {
+            Managers {
+                $( $field: Interface::new(<sys::$iface as Vtable>::VTABLE), )*
+            }
+        }
+    };
+}
+
+sdk_managers!(record_managers);
+
+impl Backend for Recorder {
+    fn managers(&self) -> &Managers<Self> {
+        &self.managers
+    }
+
+    unsafe fn destroy(recorder: *mut Self) {
+        // Pending callbacks are aborted by the SDK, they still need the recorder
+        (*(*recorder).real_core).destroy.unwrap()((*recorder).real_core);
+
+        (*recorder).flush();
+        drop(Box::from_raw(recorder));
+    }
+
+    unsafe fn run_callbacks(&self) -> sys::EDiscordResult {
+        self.write(&Entry::Tick);
+        let result = (*self.real_core).run_callbacks.unwrap()(self.real_core);
+        self.write(&Entry::Ticked { result });
+        self.flush();
+
+        result
+    }
+
+    unsafe fn set_log_hook(
+        &self,
+        min_level: sys::EDiscordLogLevel,
+        hook: Option<(*mut c_void, LogHook)>,
+    ) {
+        self.log_hook.set(hook);
+
+        // Messages go through the recorder first, as events do
+        (*self.real_core).set_log_hook.unwrap()(
+            self.real_core,
+            min_level,
+            self as *const Recorder as *mut c_void,
+            hook.map(|_| record_log as LogHook),
+        )
+    }
+}
+
+unsafe extern "C" fn record_log(
+    hook_data: *mut c_void,
+    level: sys::EDiscordLogLevel,
+    message: *const u8,
+) {
+    let recorder = &*(hook_data as *const Recorder);
+
+    let mut args = Vec::new();
+    level.encode(&mut args);
+    tape::encode_cstr(message, &mut args);
+
+    recorder.write(&Entry::Event {
+        name: tape::LOG_EVENT.to_string(),
+        args,
+    });
+
+    if let Some((hook_data, hook)) = recorder.log_hook.get() {
+        hook(hook_data, level, message)
+    }
+}
+
+/// Creates an instance of the SDK with `create`, recording its use to `writer`
+pub(crate) unsafe fn record(
+    params: &mut sys::DiscordCreateParams,
+    core: &mut *mut sys::IDiscordCore,
+    mut writer: Box<dyn Write>,
+    create: impl FnOnce(
+        &mut sys::DiscordCreateParams,
+        &mut *mut sys::IDiscordCore,
+    ) -> sys::EDiscordResult,
+) -> sys::EDiscordResult {
+    let writer = match tape::write_header(&mut writer, params.client_id, params.flags) {
+        Ok(()) => Some(writer),
+        Err(error) => {
+            log::error!("could not start the recording: {}", error);
+            None
+        }
+    };
+
+    let recorder = Box::into_raw(Box::new(Recorder {
+        core: backend::core(),
+        managers: managers(),
+        events: EVENTS,
+
+        real_core: std::ptr::null_mut(),
+        app: *params,
+
+        writer: RefCell::new(writer),
+        start: Instant::now(),
+        next_seq: Cell::new(1),
+        log_hook: Cell::new(None),
+    }));
+
+    (*recorder).core.backend = recorder;
+    (*recorder).managers.attach(recorder);
+
+    let mut real_params = (*recorder).params();
+    let res = create(&mut real_params, &mut (*recorder).real_core);
+
+    if res != sys::DiscordResult_Ok {
+        drop(Box::from_raw(recorder));
+        return res;
+    }
+
+    (*recorder).managers.wrap((*recorder).real_core);
+
+    *core = (*recorder).core.as_ptr();
+    res
+}
@@ -0,0 +1,430 @@
+// Encoding of the values crossing the SDK boundary, and of the recording file itself
+//
+// Structures are encoded field by field: their padding is never read, and decoding rejects
+// values the rest of the crate would not expect from the SDK (invalid `bool`s, strings that are
+// not UTF-8 or not terminated).
+
+use crate::sys;
+use std::{
+    convert::TryInto,
+    fmt::Write as _,
+    io::{self, BufRead, Write},
+    mem::size_of,
+};
+
+/// Encodes an argument described in the tables of `crate::tables`
+macro_rules! encode_arg {
+    (val $arg:ident, $out:ident) => {
+        $crate::replay::tape::Tape::encode(&$arg, &mut $out)
+    };
+    (ptr $arg:ident, $out:ident) => {
+        $crate::replay::tape::Tape::encode(&*$arg, &mut $out)
+    };
+    (str $arg:ident, $out:ident) => {
+        $crate::replay::tape::encode_cstr($arg, &mut $out)
+    };
+    (buf $arg:ident [$len:ident], $out:ident) => {
+        $crate::replay::tape::encode_bytes($crate::replay::tape::bytes($arg, $len), &mut $out)
+    };
+    ($kind:ident $arg:ident $([$len:ident])?, $out:ident) => {};
+}
+
+/// Name of the event recorded for every message the SDK logs
+pub(crate) const LOG_EVENT: &str = "core.log";
+
+pub(crate) trait Tape: Sized {
+    fn encode(&self, out: &mut Vec<u8>);
+    fn decode(input: &mut &[u8]) -> Option<Self>;
+
+    /// Value used when decoding fails
+    fn blank() -> Self;
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if input.len() < len {
+        return None;
+    }
+
+    let (head, tail) = input.split_at(len);
+    *input = tail;
+    Some(head)
+}
+
+macro_rules! tape_integers {
+    ($($ty:ty),*) => {
+        $(
+            impl Tape for $ty {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(input: &mut &[u8]) -> Option<Self> {
+                    Some(Self::from_le_bytes(take(input, size_of::<Self>())?.try_into().ok()?))
+                }
+
+                fn blank() -> Self {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+tape_integers!(u8, u32, u64, i32, i64);
+
+impl Tape for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        match take(input, 1)? {
+            [0] => Some(false),
+            [1] => Some(true),
+            _ => None,
+        }
+    }
+
+    fn blank() -> Self {
+        false
+    }
+}
+
+impl Tape for () {
+    fn encode(&self, _: &mut Vec<u8>) {}
+
+    fn decode(_: &mut &[u8]) -> Option<Self> {
+        Some(())
+    }
+
+    fn blank() -> Self {}
+}
+
+// Every character array of the SDK holds a NUL-terminated string
+macro_rules! tape_charbufs {
+    ($($len:expr),*) => {
+        $(
+            impl Tape for [u8; $len] {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(self);
+                }
+
+                fn decode(input: &mut &[u8]) -> Option<Self> {
+                    let bytes = take(input, $len)?;
+                    let len = memchr::memchr(0, bytes)?;
+                    std::str::from_utf8(&bytes[..len]).ok()?;
+
+                    let mut charbuf = [0; $len];
+                    charbuf.copy_from_slice(bytes);
+                    Some(charbuf)
+                }
+
+                fn blank() -> Self {
+                    [0; $len]
+                }
+            }
+        )*
+    };
+}
+
+tape_charbufs!(8, 16, 64, 128, 256, 260, 1024, 4096);
+
+macro_rules! tape_structs {
+    ($($ty:ident { $($field:ident),* })*) => {
+        $(
+            impl Tape for sys::$ty {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    $( self.$field.encode(out); )*
+                }
+
+                fn decode(input: &mut &[u8]) -> Option<Self> {
+                    Some(sys::$ty {
+                        $( $field: Tape::decode(input)?, )*
+                    })
+                }
+
+                fn blank() -> Self {
+                    Default::default()
+                }
+            }
+        )*
+    };
+}
+
+tape_structs! {
+    DiscordUser { id, username, discriminator, avatar, bot }
+    DiscordOAuth2Token { access_token, scopes, expires }
+    DiscordImageHandle { type_, id, size }
+    DiscordImageDimensions { width, height }
+    DiscordActivityTimestamps { start, end }
+    DiscordActivityAssets { large_image, large_text, small_image, small_text }
+    DiscordPartySize { current_size, max_size }
+    DiscordActivityParty { id, size }
+    DiscordActivitySecrets { match_, join, spectate }
+    DiscordActivity {
+        type_, application_id, name, state, details, timestamps, assets, party, secrets, instance
+    }
+    DiscordPresence { status, activity }
+    DiscordRelationship { type_, user, presence }
+    DiscordLobby { id, type_, owner_id, secret, capacity, locked }
+    DiscordFileStat { filename, size, last_modified }
+    DiscordEntitlement { id, type_, sku_id }
+    DiscordSkuPrice { amount, currency }
+    DiscordSku { id, type_, name, price }
+    DiscordInputMode { type_, shortcut }
+    DiscordUserAchievement { user_id, achievement_id, percent_complete, unlocked_at }
+}
+
+pub(crate) fn encode_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+    (bytes.len() as u32).encode(out);
+    out.extend_from_slice(bytes);
+}
+
+pub(crate) fn decode_bytes(input: &mut &[u8]) -> Option<Vec<u8>> {
+    let len = u32::decode(input)? as usize;
+    Some(take(input, len)?.to_vec())
+}
+
+/// Views a buffer the SDK or the program passed along with its length
+pub(crate) unsafe fn bytes<'a>(ptr: *const u8, len: u32) -> &'a [u8] {
+    if ptr.is_null() {
+        return &[];
+    }
+
+    std::slice::from_raw_parts(ptr, len as usize)
+}
+
+pub(crate) unsafe fn encode_cstr(ptr: *const u8, out: &mut Vec<u8>) {
+    let bytes = if ptr.is_null() {
+        &[]
+    } else {
+        std::ffi::CStr::from_ptr(ptr as *const _).to_bytes()
+    };
+
+    encode_bytes(bytes, out)
+}
+
+/// Decodes a string, NUL-terminated
+pub(crate) fn decode_cstr(input: &mut &[u8]) -> Option<Vec<u8>> {
+    let mut bytes = decode_bytes(input)?;
+
+    if memchr::memchr(0, &bytes).is_some() || std::str::from_utf8(&bytes).is_err() {
+        return None;
+    }
+
+    bytes.push(0);
+    Some(bytes)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Entry {
+    /// The program called the SDK
+    Call {
+        seq: u64,
+        name: String,
+        input: Vec<u8>,
+    },
+    /// The SDK returned from a call, with the values it wrote
+    Return { seq: u64, output: Vec<u8> },
+    /// The SDK called back for a call
+    Callback { seq: u64, args: Vec<u8> },
+    /// The SDK fired an event
+    Event { name: String, args: Vec<u8> },
+    /// `run_callbacks` was called
+    Tick,
+    /// `run_callbacks` returned
+    Ticked { result: sys::EDiscordResult },
+}
+
+const HEADER: &str = "discord_game_sdk-recording";
+const VERSION: u32 = 1;
+
+pub(crate) fn write_header(
+    writer: &mut dyn Write,
+    client_id: sys::DiscordClientId,
+    flags: u64,
+) -> io::Result<()> {
+    writeln!(writer, "{} {} {} {}", HEADER, VERSION, client_id, flags)
+}
+
+/// Writes `entry` on a line, `micros` is the time since the recording started
+pub(crate) fn write_entry(writer: &mut dyn Write, micros: u128, entry: &Entry) -> io::Result<()> {
+    fn hex(bytes: &[u8]) -> String {
+        if bytes.is_empty() {
+            return "-".to_string();
+        }
+
+        bytes.iter().fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+    }
+
+    match entry {
+        Entry::Call { seq, name, input } => {
+            writeln!(writer, "{} call {} {} {}", micros, seq, name, hex(input))
+        }
+        Entry::Return { seq, output } => {
+            writeln!(writer, "{} return {} {}", micros, seq, hex(output))
+        }
+        Entry::Callback { seq, args } => {
+            writeln!(writer, "{} callback {} {}", micros, seq, hex(args))
+        }
+        Entry::Event { name, args } => writeln!(writer, "{} event {} {}", micros, name, hex(args)),
+        Entry::Tick => writeln!(writer, "{} tick", micros),
+        Entry::Ticked { result } => writeln!(writer, "{} ticked {}", micros, result),
+    }
+}
+
+pub(crate) struct Recording {
+    pub(crate) client_id: sys::DiscordClientId,
+    pub(crate) flags: u64,
+    pub(crate) entries: Vec<Entry>,
+}
+
+/// Reads a recording, the error describes the first invalid line
+pub(crate) fn read(reader: impl BufRead) -> Result<Recording, String> {
+    fn unhex(hex: &str) -> Option<Vec<u8>> {
+        if hex == "-" {
+            return Some(Vec::new());
+        }
+
+        let digit = |digit: u8| (digit as char).to_digit(16).map(|digit| digit as u8);
+
+        hex.as_bytes()
+            .chunks(2)
+            .map(|pair| match *pair {
+                [high, low] => Some(digit(high)? << 4 | digit(low)?),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn entry(line: &str) -> Option<Entry> {
+        let mut words = line.split_whitespace().skip(1);
+        let kind = words.next()?;
+        let mut word = || words.next();
+
+        let entry = match kind {
+            "call" => Entry::Call {
+                seq: word()?.parse().ok()?,
+                name: word()?.to_string(),
+                input: unhex(word()?)?,
+            },
+            "return" => Entry::Return {
+                seq: word()?.parse().ok()?,
+                output: unhex(word()?)?,
+            },
+            "callback" => Entry::Callback {
+                seq: word()?.parse().ok()?,
+                args: unhex(word()?)?,
+            },
+            "event" => Entry::Event {
+                name: word()?.to_string(),
+                args: unhex(word()?)?,
+            },
+            "tick" => Entry::Tick,
+            "ticked" => Entry::Ticked {
+                result: word()?.parse().ok()?,
+            },
+            _ => return None,
+        };
+
+        match word() {
+            None => Some(entry),
+            Some(_) => None,
+        }
+    }
+
+    let mut lines = reader.lines();
+
+    let header = lines
+        .next()
+        .and_then(|line| line.ok())
+        .ok_or_else(|| "empty recording".to_string())?;
+
+    let header: Vec<_> = header.split_whitespace().collect();
+
+    let (client_id, flags) = match header.as_slice() {
+        [HEADER, version, client_id, flags] if version.parse() == Ok(VERSION) => (
+            client_id.parse().map_err(|_| "invalid client ID")?,
+            flags.parse().map_err(|_| "invalid flags")?,
+        ),
+        _ => return Err("not a recording, or an unsupported version".to_string()),
+    };
+
+    let mut entries = Vec::new();
+
+    for (number, line) in lines.enumerate() {
+        let line = line.map_err(|error| error.to_string())?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        entries.push(entry(&line).ok_or_else(|| format!("invalid entry on line {}", number + 2))?);
+    }
+
+    Ok(Recording {
+        client_id,
+        flags,
+        entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_round_trip() {
+        let mut user = sys::DiscordUser {
+            id: 42,
+            bot: true,
+            ..Default::default()
+        };
+        user.username[..4].copy_from_slice(b"mock");
+
+        let mut args = Vec::new();
+        user.encode(&mut args);
+
+        let entries = vec![
+            Entry::Tick,
+            Entry::Call {
+                seq: 1,
+                name: "users.get_user".to_string(),
+                input: vec![0xde, 0xad],
+            },
+            Entry::Return {
+                seq: 1,
+                output: Vec::new(),
+            },
+            Entry::Callback { seq: 1, args },
+            Entry::Event {
+                name: "user_events.on_current_user_update".to_string(),
+                args: Vec::new(),
+            },
+            Entry::Ticked { result: 0 },
+        ];
+
+        let mut file = Vec::new();
+        write_header(&mut file, 7, 1).unwrap();
+        for (micros, entry) in entries.iter().enumerate() {
+            write_entry(&mut file, micros as u128, entry).unwrap();
+        }
+
+        let recording = read(&file[..]).unwrap();
+        assert_eq!((recording.client_id, recording.flags), (7, 1));
+        assert_eq!(recording.entries, entries);
+
+        let args = match &recording.entries[3] {
+            Entry::Callback { args, .. } => args,
+            _ => unreachable!(),
+        };
+        assert_eq!(sys::DiscordUser::decode(&mut &args[..]), Some(user));
+
+        // Not terminated, the crate expects strings to be
+        assert_eq!(<[u8; 8]>::decode(&mut &[b'a'; 8][..]), None);
+        assert!(read(&b"discord_game_sdk-recording 1 7 1\n0 tick extra\n"[..]).is_err());
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{sys, to_result::ToResult, Cast, Comparison, Distance, Result};
+use crate::{
+    sys,
+    to_result::ToResult,
+    validation_error::{validate_str, ValidationError, METADATA_KEY_LIMIT, METADATA_VALUE_LIMIT},
+    Cast, Comparison, Distance, Error, Result,
+};
 
 /// Lobby Search
 ///
@@ -45,6 +50,24 @@ impl SearchQuery {
         self
     }
 
+    /// Filters lobbies based on metadata comparison, checking that `key` and `value` fit
+    ///
+    /// ## Errors
+    ///
+    /// If `key` or `value` is longer than the SDK allows or contains a nul byte, the query is
+    /// left untouched.
+    pub fn try_filter(
+        &mut self,
+        key: String,
+        comparison: Comparison,
+        value: String,
+        cast: Cast,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        validate_pair("filter", &key, &value)?;
+
+        Ok(self.filter(key, comparison, value, cast))
+    }
+
     /// Sorts the filtered lobbies based on "near-ness" to a given value
     ///
     /// ## Performance
@@ -65,6 +88,24 @@ impl SearchQuery {
         self
     }
 
+    /// Sorts the filtered lobbies based on "near-ness" to a given value, checking that `key`
+    /// and `value` fit
+    ///
+    /// ## Errors
+    ///
+    /// If `key` or `value` is longer than the SDK allows or contains a nul byte, the query is
+    /// left untouched.
+    pub fn try_sort(
+        &mut self,
+        key: String,
+        value: String,
+        cast: Cast,
+    ) -> std::result::Result<&mut Self, ValidationError> {
+        validate_pair("sort", &key, &value)?;
+
+        Ok(self.sort(key, value, cast))
+    }
+
     /// Limits the number of lobbies returned in a search
     ///
     /// > [Method in official docs](https://discordapp.com/developers/docs/game-sdk/lobbies#lobbysearchlimit)
@@ -81,7 +122,30 @@ impl SearchQuery {
         self
     }
 
+    /// Checks that the keys and values of the filter and the sort fit in the buffers of the SDK
+    ///
+    /// The SDK would truncate strings that do not fit without reporting an error, so searching
+    /// checks them too and fails with `Error::InvalidString`. This and the `try_` methods find
+    /// out before searching.
+    ///
+    /// ## Errors
+    ///
+    /// The first key or value that is too long or contains a nul byte.
+    pub fn validate(&self) -> std::result::Result<(), ValidationError> {
+        if let Some((key, value, _, _)) = &self.filter {
+            validate_pair("filter", key, value)?;
+        }
+
+        if let Some((key, value, _)) = &self.sort {
+            validate_pair("sort", key, value)?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) unsafe fn process(&self, tx: *mut sys::IDiscordLobbySearchQuery) -> Result<()> {
+        self.validate().map_err(Error::InvalidString)?;
+
         if let Some((key, value, comparison, cast)) = self.filter.as_ref() {
             (*tx).filter.unwrap()(
                 tx,
@@ -118,3 +182,14 @@ impl SearchQuery {
         Ok(())
     }
 }
+
+fn validate_pair(clause: &str, key: &str, value: &str) -> std::result::Result<(), ValidationError> {
+    validate_str(format_args!("{} key", clause), key, METADATA_KEY_LIMIT)?;
+    validate_str(
+        format_args!("{} value", clause),
+        value,
+        METADATA_VALUE_LIMIT,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,526 @@
+// Data types are serialized through a representation mirroring their getters, strings are
+// decoded from the fixed buffers of the SDK and checked to fit back in when deserializing.
+
+use crate::{
+    sys, Activity, ActivityKind, ClientID, Entitlement, EntitlementKind, FileStat, ImageHandle,
+    ImageKind, InputMode, InputModeKind, Lobby, LobbyID, LobbyKind, OAuth2Token, Presence,
+    Relationship, RelationshipKind, Sku, SkuKind, Snowflake, Status, UnixTimestamp, User,
+    UserAchievement, UserID,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{borrow::Cow, convert::TryFrom};
+
+fn write<E: de::Error>(charbuf: &mut [u8], value: &str) -> Result<(), E> {
+    if value.len() > charbuf.len() {
+        return Err(E::custom(format_args!(
+            "string longer than {} bytes: {:?}",
+            charbuf.len(),
+            value
+        )));
+    }
+
+    if value.contains('\0') {
+        return Err(E::custom(format_args!(
+            "string contains a nul byte: {:?}",
+            value
+        )));
+    }
+
+    charbuf[..value.len()].copy_from_slice(value.as_bytes());
+
+    if value.len() < charbuf.len() {
+        charbuf[value.len()] = 0;
+    }
+
+    Ok(())
+}
+
+fn convert<T: TryFrom<U>, U: Copy + std::fmt::Display, E: de::Error>(value: U) -> Result<T, E> {
+    T::try_from(value).map_err(|_| E::custom(format_args!("out of range: {}", value)))
+}
+
+macro_rules! via_repr {
+    ($ty:ident, $repr:ident, |$this:ident| $to:expr, |$from:ident| $of:expr) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let $this = self;
+                $to.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let $from = $repr::deserialize(deserializer)?;
+                $of
+            }
+        }
+    };
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Activity")]
+struct ActivityRepr<'a> {
+    kind: ActivityKind,
+    application_id: ClientID,
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[serde(borrow)]
+    state: Cow<'a, str>,
+    #[serde(borrow)]
+    details: Cow<'a, str>,
+    start_time: UnixTimestamp,
+    end_time: UnixTimestamp,
+    #[serde(borrow)]
+    large_image_key: Cow<'a, str>,
+    #[serde(borrow)]
+    large_image_tooltip: Cow<'a, str>,
+    #[serde(borrow)]
+    small_image_key: Cow<'a, str>,
+    #[serde(borrow)]
+    small_image_tooltip: Cow<'a, str>,
+    #[serde(borrow)]
+    party_id: Cow<'a, str>,
+    party_amount: u32,
+    party_capacity: u32,
+    instance: bool,
+    #[serde(borrow)]
+    match_secret: Cow<'a, str>,
+    #[serde(borrow)]
+    join_secret: Cow<'a, str>,
+    #[serde(borrow)]
+    spectate_secret: Cow<'a, str>,
+}
+
+via_repr!(
+    Activity,
+    ActivityRepr,
+    |activity| ActivityRepr {
+        kind: activity.kind(),
+        application_id: activity.application_id(),
+        name: activity.name().into(),
+        state: activity.state().into(),
+        details: activity.details().into(),
+        start_time: activity.start_time(),
+        end_time: activity.end_time(),
+        large_image_key: activity.large_image_key().into(),
+        large_image_tooltip: activity.large_image_tooltip().into(),
+        small_image_key: activity.small_image_key().into(),
+        small_image_tooltip: activity.small_image_tooltip().into(),
+        party_id: activity.party_id().into(),
+        party_amount: activity.party_amount(),
+        party_capacity: activity.party_capacity(),
+        instance: activity.instance(),
+        match_secret: activity.match_secret().into(),
+        join_secret: activity.join_secret().into(),
+        spectate_secret: activity.spectate_secret().into(),
+    },
+    |repr| {
+        let mut activity = sys::DiscordActivity {
+            type_: repr.kind.into(),
+            application_id: repr.application_id,
+            instance: repr.instance,
+            ..sys::DiscordActivity::default()
+        };
+
+        write(&mut activity.name, &repr.name)?;
+        write(&mut activity.state, &repr.state)?;
+        write(&mut activity.details, &repr.details)?;
+        activity.timestamps.start = repr.start_time;
+        activity.timestamps.end = repr.end_time;
+        write(&mut activity.assets.large_image, &repr.large_image_key)?;
+        write(&mut activity.assets.large_text, &repr.large_image_tooltip)?;
+        write(&mut activity.assets.small_image, &repr.small_image_key)?;
+        write(&mut activity.assets.small_text, &repr.small_image_tooltip)?;
+        write(&mut activity.party.id, &repr.party_id)?;
+        activity.party.size.current_size = convert(repr.party_amount)?;
+        activity.party.size.max_size = convert(repr.party_capacity)?;
+        write(&mut activity.secrets.match_, &repr.match_secret)?;
+        write(&mut activity.secrets.join, &repr.join_secret)?;
+        write(&mut activity.secrets.spectate, &repr.spectate_secret)?;
+
+        Ok(Activity(activity))
+    }
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "User")]
+struct UserRepr<'a> {
+    id: UserID,
+    #[serde(borrow)]
+    username: Cow<'a, str>,
+    #[serde(borrow)]
+    discriminator: Cow<'a, str>,
+    #[serde(borrow)]
+    avatar: Cow<'a, str>,
+    is_bot: bool,
+}
+
+via_repr!(
+    User,
+    UserRepr,
+    |user| UserRepr {
+        id: user.id(),
+        username: user.username().into(),
+        discriminator: user.discriminator().into(),
+        avatar: user.avatar().into(),
+        is_bot: user.is_bot(),
+    },
+    |repr| {
+        let mut user = sys::DiscordUser {
+            id: repr.id,
+            bot: repr.is_bot,
+            ..sys::DiscordUser::default()
+        };
+
+        write(&mut user.username, &repr.username)?;
+        write(&mut user.discriminator, &repr.discriminator)?;
+        write(&mut user.avatar, &repr.avatar)?;
+
+        Ok(User(user))
+    }
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Lobby")]
+struct LobbyRepr<'a> {
+    id: LobbyID,
+    kind: LobbyKind,
+    owner_id: UserID,
+    #[serde(borrow)]
+    secret: Cow<'a, str>,
+    capacity: u32,
+    locked: bool,
+}
+
+via_repr!(
+    Lobby,
+    LobbyRepr,
+    |lobby| LobbyRepr {
+        id: lobby.id(),
+        kind: lobby.kind(),
+        owner_id: lobby.owner_id(),
+        secret: lobby.secret().into(),
+        capacity: lobby.capacity(),
+        locked: lobby.locked(),
+    },
+    |repr| {
+        let mut lobby = sys::DiscordLobby {
+            id: repr.id,
+            type_: repr.kind.into(),
+            owner_id: repr.owner_id,
+            capacity: repr.capacity,
+            locked: repr.locked,
+            ..sys::DiscordLobby::default()
+        };
+
+        write(&mut lobby.secret, &repr.secret)?;
+
+        Ok(Lobby(lobby))
+    }
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Presence")]
+struct PresenceRepr<'a> {
+    status: Status,
+    activity: Cow<'a, Activity>,
+}
+
+via_repr!(
+    Presence,
+    PresenceRepr,
+    |presence| PresenceRepr {
+        status: presence.status(),
+        activity: Cow::Borrowed(presence.activity()),
+    },
+    |repr| Ok(Presence(sys::DiscordPresence {
+        status: repr.status.into(),
+        activity: repr.activity.into_owned().0,
+    }))
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Relationship")]
+struct RelationshipRepr<'a> {
+    kind: RelationshipKind,
+    user: Cow<'a, User>,
+    presence: Cow<'a, Presence>,
+}
+
+via_repr!(
+    Relationship,
+    RelationshipRepr,
+    |relationship| RelationshipRepr {
+        kind: relationship.kind(),
+        user: Cow::Borrowed(relationship.user()),
+        presence: Cow::Borrowed(relationship.presence()),
+    },
+    |repr| Ok(Relationship(sys::DiscordRelationship {
+        type_: repr.kind.into(),
+        user: repr.user.into_owned().0,
+        presence: repr.presence.into_owned().0,
+    }))
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Entitlement")]
+struct EntitlementRepr {
+    id: Snowflake,
+    kind: EntitlementKind,
+    sku_id: Snowflake,
+}
+
+via_repr!(
+    Entitlement,
+    EntitlementRepr,
+    |entitlement| EntitlementRepr {
+        id: entitlement.id(),
+        kind: entitlement.kind(),
+        sku_id: entitlement.sku_id(),
+    },
+    |repr| Ok(Entitlement(sys::DiscordEntitlement {
+        id: repr.id,
+        type_: repr.kind.into(),
+        sku_id: repr.sku_id,
+    }))
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Sku")]
+struct SkuRepr<'a> {
+    id: Snowflake,
+    kind: SkuKind,
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    price_amount: u32,
+    #[serde(borrow)]
+    price_currency: Cow<'a, str>,
+}
+
+via_repr!(
+    Sku,
+    SkuRepr,
+    |sku| SkuRepr {
+        id: sku.id(),
+        kind: sku.kind(),
+        name: sku.name().into(),
+        price_amount: sku.price_amount(),
+        price_currency: sku.price_currency().into(),
+    },
+    |repr| {
+        let mut sku = sys::DiscordSku {
+            id: repr.id,
+            type_: repr.kind.into(),
+            ..sys::DiscordSku::default()
+        };
+
+        write(&mut sku.name, &repr.name)?;
+        sku.price.amount = repr.price_amount;
+        write(&mut sku.price.currency, &repr.price_currency)?;
+
+        Ok(Sku(sku))
+    }
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "FileStat")]
+struct FileStatRepr<'a> {
+    #[serde(borrow)]
+    filename: Cow<'a, str>,
+    size: u64,
+    last_modified: UnixTimestamp,
+}
+
+via_repr!(
+    FileStat,
+    FileStatRepr,
+    |stat| FileStatRepr {
+        filename: stat.filename().into(),
+        size: stat.size(),
+        last_modified: stat.last_modified(),
+    },
+    |repr| {
+        let mut stat = sys::DiscordFileStat {
+            size: repr.size,
+            last_modified: convert(repr.last_modified)?,
+            ..sys::DiscordFileStat::default()
+        };
+
+        write(&mut stat.filename, &repr.filename)?;
+
+        Ok(FileStat(stat))
+    }
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "UserAchievement")]
+struct UserAchievementRepr<'a> {
+    user_id: UserID,
+    achievement_id: Snowflake,
+    percent_complete: u8,
+    #[serde(borrow)]
+    unlocked_at: Cow<'a, str>,
+}
+
+via_repr!(
+    UserAchievement,
+    UserAchievementRepr,
+    |achievement| UserAchievementRepr {
+        user_id: achievement.user_id(),
+        achievement_id: achievement.achievement_id(),
+        percent_complete: achievement.percent_complete(),
+        unlocked_at: achievement.unlocked_at().into(),
+    },
+    |repr| {
+        if repr.percent_complete > 100 {
+            return Err(de::Error::custom(format_args!(
+                "percentage above 100: {}",
+                repr.percent_complete
+            )));
+        }
+
+        let mut achievement = sys::DiscordUserAchievement {
+            user_id: repr.user_id,
+            achievement_id: repr.achievement_id,
+            percent_complete: repr.percent_complete,
+            ..sys::DiscordUserAchievement::default()
+        };
+
+        write(&mut achievement.unlocked_at, &repr.unlocked_at)?;
+
+        Ok(UserAchievement(achievement))
+    }
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "InputMode")]
+struct InputModeRepr<'a> {
+    kind: InputModeKind,
+    #[serde(borrow)]
+    shortcut: Cow<'a, str>,
+}
+
+via_repr!(
+    InputMode,
+    InputModeRepr,
+    |mode| InputModeRepr {
+        kind: mode.kind(),
+        shortcut: mode.shortcut().into(),
+    },
+    |repr| {
+        let mut mode = sys::DiscordInputMode {
+            type_: repr.kind.into(),
+            ..sys::DiscordInputMode::default()
+        };
+
+        write(&mut mode.shortcut, &repr.shortcut)?;
+
+        Ok(InputMode(mode))
+    }
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "OAuth2Token")]
+struct OAuth2TokenRepr<'a> {
+    #[serde(borrow)]
+    access_token: Cow<'a, str>,
+    #[serde(borrow)]
+    scopes: Cow<'a, str>,
+    expires: UnixTimestamp,
+}
+
+via_repr!(
+    OAuth2Token,
+    OAuth2TokenRepr,
+    |token| OAuth2TokenRepr {
+        access_token: token.access_token().into(),
+        scopes: token.scopes().into(),
+        expires: token.expires(),
+    },
+    |repr| {
+        let mut token = sys::DiscordOAuth2Token {
+            expires: repr.expires,
+            ..sys::DiscordOAuth2Token::default()
+        };
+
+        write(&mut token.access_token, &repr.access_token)?;
+        write(&mut token.scopes, &repr.scopes)?;
+
+        Ok(OAuth2Token(token))
+    }
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "ImageHandle")]
+struct ImageHandleRepr {
+    kind: ImageKind,
+    id: Snowflake,
+    size: u32,
+}
+
+via_repr!(
+    ImageHandle,
+    ImageHandleRepr,
+    |handle| ImageHandleRepr {
+        kind: handle.kind(),
+        id: handle.id(),
+        size: handle.size(),
+    },
+    |repr| Ok(ImageHandle(sys::DiscordImageHandle {
+        type_: repr.kind.into(),
+        id: repr.id,
+        size: repr.size,
+    }))
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_types_round_trip() {
+        let mut activity = Activity::empty();
+        activity
+            .with_state("In a \"match\"")
+            .with_party_id("party")
+            .with_party_amount(2)
+            .with_party_capacity(4);
+
+        let relationship = Relationship(sys::DiscordRelationship {
+            type_: RelationshipKind::Friend.into(),
+            user: sys::DiscordUser {
+                id: 42,
+                ..Default::default()
+            },
+            presence: sys::DiscordPresence {
+                status: Status::Online.into(),
+                activity: activity.0,
+            },
+        });
+
+        let json = serde_json::to_value(&relationship).unwrap();
+        assert_eq!(json["kind"], "Friend");
+        assert_eq!(json["user"]["id"], 42);
+        assert_eq!(json["presence"]["status"], "Online");
+        assert_eq!(json["presence"]["activity"]["state"], "In a \"match\"");
+
+        let text = serde_json::to_string(&relationship).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Relationship>(&text).unwrap(),
+            relationship
+        );
+    }
+
+    #[test]
+    fn strings_must_fit_their_buffers() {
+        let mode = InputMode::push_to_talk("ctrl");
+        let mut json = serde_json::to_value(&mode).unwrap();
+        assert_eq!(
+            serde_json::from_value::<InputMode>(json.clone()).unwrap(),
+            mode
+        );
+
+        json["shortcut"] = "x".repeat(257).into();
+        assert!(serde_json::from_value::<InputMode>(json).is_err());
+    }
+}
@@ -4,6 +4,7 @@ use crate::sys;
 ///
 /// > [Enum in official docs](https://discordapp.com/developers/docs/game-sdk/store#data-models-skutype-enum)
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SkuKind {
     /// SKU is a game
     Application,
@@ -28,3 +29,15 @@ impl From<sys::EDiscordSkuType> for SkuKind {
         }
     }
 }
+
+impl From<SkuKind> for sys::EDiscordSkuType {
+    fn from(source: SkuKind) -> Self {
+        match source {
+            SkuKind::Application => sys::DiscordSkuType_Application,
+            SkuKind::Bundle => sys::DiscordSkuType_Bundle,
+            SkuKind::Consumable => sys::DiscordSkuType_Consumable,
+            SkuKind::DLC => sys::DiscordSkuType_DLC,
+            SkuKind::Undefined(n) => n,
+        }
+    }
+}
@@ -4,6 +4,7 @@ use crate::sys;
 ///
 /// > [Enum in official docs](https://discordapp.com/developers/docs/game-sdk/relationships#data-models-status-enum)
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Status {
     /// User does not want to be disturbed (red dot)
     DoNotDisturb,
@@ -29,6 +30,18 @@ impl From<sys::EDiscordStatus> for Status {
     }
 }
 
+impl From<Status> for sys::EDiscordStatus {
+    fn from(source: Status) -> Self {
+        match source {
+            Status::DoNotDisturb => sys::DiscordStatus_DoNotDisturb,
+            Status::Idle => sys::DiscordStatus_Idle,
+            Status::Offline => sys::DiscordStatus_Offline,
+            Status::Online => sys::DiscordStatus_Online,
+            Status::Undefined(n) => n,
+        }
+    }
+}
+
 impl std::fmt::Display for Status {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -0,0 +1,190 @@
+use crate::{Discord, Error};
+use std::{
+    fmt,
+    io::{self, Cursor, Read, Seek, SeekFrom, Write},
+};
+
+/// Handle to a file of the game's cloud storage, implementing `Read`, `Seek` and `Write`
+///
+/// The SDK only reads and writes whole files synchronously: the contents are read once when
+/// opening and writes are kept in memory until [`flush`](#method.flush) or
+/// [`close`](#method.close). Dropping the handle flushes it, ignoring errors.
+///
+/// Obtained with [`Discord::open_file`](struct.Discord.html#method.open_file) or
+/// [`Discord::create_file`](struct.Discord.html#method.create_file).
+///
+/// ```rust
+/// # use discord_game_sdk::*;
+/// # use std::io::{Read, Write};
+/// # fn example(discord: Discord<'_, ()>) -> std::result::Result<(), Box<dyn std::error::Error>> {
+/// let mut file = discord.create_file("settings.json");
+/// write!(file, "{{\"volume\": {}}}", 80)?;
+/// file.close()?;
+///
+/// let mut settings = String::new();
+/// discord.open_file("settings.json")?.read_to_string(&mut settings)?;
+/// # Ok(()) }
+/// ```
+pub struct StorageFile<'a, 'd, E> {
+    discord: &'a Discord<'d, E>,
+    filename: String,
+    contents: Cursor<Vec<u8>>,
+    dirty: bool,
+}
+
+impl<'a, 'd, E> StorageFile<'a, 'd, E> {
+    pub(crate) fn new(
+        discord: &'a Discord<'d, E>,
+        filename: String,
+        contents: Vec<u8>,
+        dirty: bool,
+    ) -> Self {
+        Self {
+            discord,
+            filename,
+            contents: Cursor::new(contents),
+            dirty,
+        }
+    }
+
+    /// The name of the file
+    pub fn filename(&self) -> &str {
+        self.filename.trim_end_matches('\0')
+    }
+
+    /// The current length of the file, including writes that were not flushed
+    pub fn len(&self) -> u64 {
+        self.contents.get_ref().len() as u64
+    }
+
+    /// Whether the file is empty, including writes that were not flushed
+    pub fn is_empty(&self) -> bool {
+        self.contents.get_ref().is_empty()
+    }
+
+    /// Truncates or extends the file with zeroes, the position is left unchanged
+    pub fn set_len(&mut self, len: u64) -> io::Result<()> {
+        if len > u64::from(u32::MAX) {
+            return Err(too_large());
+        }
+
+        self.contents.get_mut().resize(len as usize, 0);
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Writes the file if it was modified, then closes it.
+    ///
+    /// Prefer this over dropping the handle to learn whether writing succeeded.
+    pub fn close(mut self) -> io::Result<()> {
+        self.flush()
+    }
+}
+
+fn too_large() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "storage files are limited to 4 294 967 295 bytes",
+    )
+}
+
+fn io_error(error: Error) -> io::Error {
+    let kind = match error {
+        Error::NotFound => io::ErrorKind::NotFound,
+        Error::InvalidPermissions => io::ErrorKind::PermissionDenied,
+        _ => io::ErrorKind::Other,
+    };
+
+    io::Error::new(kind, error)
+}
+
+impl<E> Read for StorageFile<'_, '_, E> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.contents.read(buf)
+    }
+}
+
+impl<E> Seek for StorageFile<'_, '_, E> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.contents.seek(pos)
+    }
+}
+
+impl<E> Write for StorageFile<'_, '_, E> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.contents.position() + buf.len() as u64 > u64::from(u32::MAX) {
+            return Err(too_large());
+        }
+
+        self.dirty = true;
+        self.contents.write(buf)
+    }
+
+    /// Writes the whole file to storage if it was modified
+    fn flush(&mut self) -> io::Result<()> {
+        if self.dirty {
+            self.discord
+                .write_file(self.filename.as_str(), self.contents.get_ref())
+                .map_err(io_error)?;
+
+            self.dirty = false;
+        }
+
+        Ok(())
+    }
+}
+
+impl<E> Drop for StorageFile<'_, '_, E> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl<E> fmt::Debug for StorageFile<'_, '_, E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("StorageFile")
+            .field("filename", &self.filename())
+            .field("len", &self.len())
+            .field("position", &self.contents.position())
+            .field("dirty", &self.dirty)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockConfig;
+
+    #[test]
+    fn storage_files_are_streamed() {
+        let mut config = MockConfig::new();
+        config.with_file("existing", b"hello world".to_vec());
+
+        let discord = Discord::<()>::mock_with(&config);
+
+        let mut file = discord.open_file("existing").unwrap();
+        file.seek(SeekFrom::Start(6)).unwrap();
+        file.write_all(b"there").unwrap();
+        file.write_all(b"!").unwrap();
+        assert_eq!(file.len(), 12);
+
+        // Nothing is written before flushing
+        let mut buffer = [0; 16];
+        assert_eq!(discord.read_file("existing", &mut buffer[..]).unwrap(), 11);
+        file.close().unwrap();
+
+        let mut contents = String::new();
+        discord
+            .open_file("existing")
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "hello there!");
+
+        drop(discord.create_file("existing"));
+        assert_eq!(discord.file_stat("existing").unwrap().size(), 0);
+
+        assert_eq!(discord.open_file("missing").err(), Some(Error::NotFound));
+    }
+}
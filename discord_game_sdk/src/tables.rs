@@ -0,0 +1,512 @@
+// Every function and event of the SDK, described once for the backends that wrap the SDK or
+// stand in for it. Each table is handed to a macro of theirs, which generates the function
+// pointer tables: `sdk_methods` has the managers, `sdk_handles` the transactions and search
+// queries.
+//
+// Each argument is tagged with how it crosses the boundary:
+//
+// - `val`: passed by value
+// - `ptr`: points to a structure that is only read
+// - `str`: points to a NUL-terminated string
+// - `buf[len]`: points to `len` bytes that are only read
+// - `len`: length of a preceding `buf` or `out_buf`
+// - `out`: points to a value the SDK writes
+// - `out_buf[len]`: points to `len` bytes the SDK writes
+// - `handle`: a transaction or search query, consumed by the call
+// - `out_handle`: a transaction or search query the SDK provides
+//
+// Functions taking a callback list the arguments it receives after the result.
+// `manual` functions are implemented by hand.
+
+macro_rules! sdk_methods {
+    ($then:ident) => {
+        $then! {
+            achievements IDiscordAchievementManager {
+                fn set_user_achievement(
+                    val achievement_id: sys::DiscordSnowflake,
+                    val percent_complete: u8
+                ) => callback();
+                fn fetch_user_achievements() => callback();
+                fn count_user_achievements(out count: *mut i32);
+                fn get_user_achievement(
+                    val user_achievement_id: sys::DiscordSnowflake,
+                    out user_achievement: *mut sys::DiscordUserAchievement
+                ) -> sys::EDiscordResult;
+                fn get_user_achievement_at(
+                    val index: i32,
+                    out user_achievement: *mut sys::DiscordUserAchievement
+                ) -> sys::EDiscordResult;
+            } manual {}
+
+            activities IDiscordActivityManager {
+                fn register_command(str command: *const u8) -> sys::EDiscordResult;
+                fn register_steam(val steam_id: u32) -> sys::EDiscordResult;
+                fn update_activity(ptr activity: *mut sys::DiscordActivity) => callback();
+                fn clear_activity() => callback();
+                fn send_request_reply(
+                    val user_id: sys::DiscordUserId,
+                    val reply: sys::EDiscordActivityJoinRequestReply
+                ) => callback();
+                fn send_invite(
+                    val user_id: sys::DiscordUserId,
+                    val type_: sys::EDiscordActivityActionType,
+                    str content: *const u8
+                ) => callback();
+                fn accept_invite(val user_id: sys::DiscordUserId) => callback();
+            } manual {}
+
+            applications IDiscordApplicationManager {
+                fn validate_or_exit() => callback();
+                fn get_current_locale(out locale: *mut sys::DiscordLocale);
+                fn get_current_branch(out branch: *mut sys::DiscordBranch);
+                fn get_oauth2_token() => callback(ptr oauth2_token: *mut sys::DiscordOAuth2Token);
+                fn get_ticket() => callback(str data: *const u8);
+            } manual {}
+
+            images IDiscordImageManager {
+                fn fetch(
+                    val handle: sys::DiscordImageHandle,
+                    val refresh: bool
+                ) => callback(val handle_result: sys::DiscordImageHandle);
+                fn get_dimensions(
+                    val handle: sys::DiscordImageHandle,
+                    out dimensions: *mut sys::DiscordImageDimensions
+                ) -> sys::EDiscordResult;
+                fn get_data(
+                    val handle: sys::DiscordImageHandle,
+                    out_buf data[data_length]: *mut u8,
+                    len data_length: u32
+                ) -> sys::EDiscordResult;
+            } manual {}
+
+            lobbies IDiscordLobbyManager {
+                fn get_lobby_create_transaction(
+                    out_handle transaction: *mut *mut sys::IDiscordLobbyTransaction
+                ) -> sys::EDiscordResult;
+                fn get_lobby_update_transaction(
+                    val lobby_id: sys::DiscordLobbyId,
+                    out_handle transaction: *mut *mut sys::IDiscordLobbyTransaction
+                ) -> sys::EDiscordResult;
+                fn get_member_update_transaction(
+                    val lobby_id: sys::DiscordLobbyId,
+                    val user_id: sys::DiscordUserId,
+                    out_handle transaction: *mut *mut sys::IDiscordLobbyMemberTransaction
+                ) -> sys::EDiscordResult;
+                fn create_lobby(
+                    handle transaction: *mut sys::IDiscordLobbyTransaction
+                ) => callback(ptr lobby: *mut sys::DiscordLobby);
+                fn update_lobby(
+                    val lobby_id: sys::DiscordLobbyId,
+                    handle transaction: *mut sys::IDiscordLobbyTransaction
+                ) => callback();
+                fn delete_lobby(val lobby_id: sys::DiscordLobbyId) => callback();
+                fn connect_lobby(
+                    val lobby_id: sys::DiscordLobbyId,
+                    str secret: *mut u8
+                ) => callback(ptr lobby: *mut sys::DiscordLobby);
+                fn connect_lobby_with_activity_secret(
+                    str activity_secret: *mut u8
+                ) => callback(ptr lobby: *mut sys::DiscordLobby);
+                fn disconnect_lobby(val lobby_id: sys::DiscordLobbyId) => callback();
+                fn get_lobby(
+                    val lobby_id: sys::DiscordLobbyId,
+                    out lobby: *mut sys::DiscordLobby
+                ) -> sys::EDiscordResult;
+                fn get_lobby_activity_secret(
+                    val lobby_id: sys::DiscordLobbyId,
+                    out secret: *mut sys::DiscordLobbySecret
+                ) -> sys::EDiscordResult;
+                fn get_lobby_metadata_value(
+                    val lobby_id: sys::DiscordLobbyId,
+                    str key: *mut u8,
+                    out value: *mut sys::DiscordMetadataValue
+                ) -> sys::EDiscordResult;
+                fn get_lobby_metadata_key(
+                    val lobby_id: sys::DiscordLobbyId,
+                    val index: i32,
+                    out key: *mut sys::DiscordMetadataKey
+                ) -> sys::EDiscordResult;
+                fn lobby_metadata_count(
+                    val lobby_id: sys::DiscordLobbyId,
+                    out count: *mut i32
+                ) -> sys::EDiscordResult;
+                fn member_count(
+                    val lobby_id: sys::DiscordLobbyId,
+                    out count: *mut i32
+                ) -> sys::EDiscordResult;
+                fn get_member_user_id(
+                    val lobby_id: sys::DiscordLobbyId,
+                    val index: i32,
+                    out user_id: *mut sys::DiscordUserId
+                ) -> sys::EDiscordResult;
+                fn get_member_user(
+                    val lobby_id: sys::DiscordLobbyId,
+                    val user_id: sys::DiscordUserId,
+                    out user: *mut sys::DiscordUser
+                ) -> sys::EDiscordResult;
+                fn get_member_metadata_value(
+                    val lobby_id: sys::DiscordLobbyId,
+                    val user_id: sys::DiscordUserId,
+                    str key: *mut u8,
+                    out value: *mut sys::DiscordMetadataValue
+                ) -> sys::EDiscordResult;
+                fn get_member_metadata_key(
+                    val lobby_id: sys::DiscordLobbyId,
+                    val user_id: sys::DiscordUserId,
+                    val index: i32,
+                    out key: *mut sys::DiscordMetadataKey
+                ) -> sys::EDiscordResult;
+                fn member_metadata_count(
+                    val lobby_id: sys::DiscordLobbyId,
+                    val user_id: sys::DiscordUserId,
+                    out count: *mut i32
+                ) -> sys::EDiscordResult;
+                fn update_member(
+                    val lobby_id: sys::DiscordLobbyId,
+                    val user_id: sys::DiscordUserId,
+                    handle transaction: *mut sys::IDiscordLobbyMemberTransaction
+                ) => callback();
+                fn send_lobby_message(
+                    val lobby_id: sys::DiscordLobbyId,
+                    buf data[data_length]: *mut u8,
+                    len data_length: u32
+                ) => callback();
+                fn get_search_query(
+                    out_handle query: *mut *mut sys::IDiscordLobbySearchQuery
+                ) -> sys::EDiscordResult;
+                fn search(handle query: *mut sys::IDiscordLobbySearchQuery) => callback();
+                fn lobby_count(out count: *mut i32);
+                fn get_lobby_id(
+                    val index: i32,
+                    out lobby_id: *mut sys::DiscordLobbyId
+                ) -> sys::EDiscordResult;
+                fn connect_voice(val lobby_id: sys::DiscordLobbyId) => callback();
+                fn disconnect_voice(val lobby_id: sys::DiscordLobbyId) => callback();
+                fn connect_network(val lobby_id: sys::DiscordLobbyId) -> sys::EDiscordResult;
+                fn disconnect_network(val lobby_id: sys::DiscordLobbyId) -> sys::EDiscordResult;
+                fn flush_network() -> sys::EDiscordResult;
+                fn open_network_channel(
+                    val lobby_id: sys::DiscordLobbyId,
+                    val channel_id: u8,
+                    val reliable: bool
+                ) -> sys::EDiscordResult;
+                fn send_network_message(
+                    val lobby_id: sys::DiscordLobbyId,
+                    val user_id: sys::DiscordUserId,
+                    val channel_id: u8,
+                    buf data[data_length]: *mut u8,
+                    len data_length: u32
+                ) -> sys::EDiscordResult;
+            } manual {}
+
+            networking IDiscordNetworkManager {
+                fn get_peer_id(out peer_id: *mut sys::DiscordNetworkPeerId);
+                fn flush() -> sys::EDiscordResult;
+                fn open_peer(
+                    val peer_id: sys::DiscordNetworkPeerId,
+                    str route_data: *const u8
+                ) -> sys::EDiscordResult;
+                fn update_peer(
+                    val peer_id: sys::DiscordNetworkPeerId,
+                    str route_data: *const u8
+                ) -> sys::EDiscordResult;
+                fn close_peer(val peer_id: sys::DiscordNetworkPeerId) -> sys::EDiscordResult;
+                fn open_channel(
+                    val peer_id: sys::DiscordNetworkPeerId,
+                    val channel_id: sys::DiscordNetworkChannelId,
+                    val reliable: bool
+                ) -> sys::EDiscordResult;
+                fn close_channel(
+                    val peer_id: sys::DiscordNetworkPeerId,
+                    val channel_id: sys::DiscordNetworkChannelId
+                ) -> sys::EDiscordResult;
+                fn send_message(
+                    val peer_id: sys::DiscordNetworkPeerId,
+                    val channel_id: sys::DiscordNetworkChannelId,
+                    buf data[data_length]: *mut u8,
+                    len data_length: u32
+                ) -> sys::EDiscordResult;
+            } manual {}
+
+            overlay IDiscordOverlayManager {
+                fn is_enabled(out enabled: *mut bool);
+                fn is_locked(out locked: *mut bool);
+                fn set_locked(val locked: bool) => callback();
+                fn open_activity_invite(val type_: sys::EDiscordActivityActionType) => callback();
+                fn open_guild_invite(str code: *const u8) => callback();
+                fn open_voice_settings() => callback();
+            } manual {}
+
+            relationships IDiscordRelationshipManager {
+                fn count(out count: *mut i32) -> sys::EDiscordResult;
+                fn get(
+                    val user_id: sys::DiscordUserId,
+                    out relationship: *mut sys::DiscordRelationship
+                ) -> sys::EDiscordResult;
+                fn get_at(
+                    val index: u32,
+                    out relationship: *mut sys::DiscordRelationship
+                ) -> sys::EDiscordResult;
+            } manual { filter }
+
+            storage IDiscordStorageManager {
+                fn read(
+                    str name: *const u8,
+                    out_buf data[data_length]: *mut u8,
+                    len data_length: u32,
+                    out read: *mut u32
+                ) -> sys::EDiscordResult;
+                fn read_async(
+                    str name: *const u8
+                ) => callback(buf data[data_length]: *mut u8, len data_length: u32);
+                fn read_async_partial(
+                    str name: *const u8,
+                    val offset: u64,
+                    val length: u64
+                ) => callback(buf data[data_length]: *mut u8, len data_length: u32);
+                fn write(
+                    str name: *const u8,
+                    buf data[data_length]: *mut u8,
+                    len data_length: u32
+                ) -> sys::EDiscordResult;
+                fn write_async(
+                    str name: *const u8,
+                    buf data[data_length]: *mut u8,
+                    len data_length: u32
+                ) => callback();
+                fn delete_(str name: *const u8) -> sys::EDiscordResult;
+                fn exists(str name: *const u8, out exists: *mut bool) -> sys::EDiscordResult;
+                fn count(out count: *mut i32);
+                fn stat(
+                    str name: *const u8,
+                    out stat: *mut sys::DiscordFileStat
+                ) -> sys::EDiscordResult;
+                fn stat_at(
+                    val index: i32,
+                    out stat: *mut sys::DiscordFileStat
+                ) -> sys::EDiscordResult;
+                fn get_path(out path: *mut sys::DiscordPath) -> sys::EDiscordResult;
+            } manual {}
+
+            store IDiscordStoreManager {
+                fn fetch_skus() => callback();
+                fn count_skus(out count: *mut i32);
+                fn get_sku(
+                    val sku_id: sys::DiscordSnowflake,
+                    out sku: *mut sys::DiscordSku
+                ) -> sys::EDiscordResult;
+                fn get_sku_at(val index: i32, out sku: *mut sys::DiscordSku) -> sys::EDiscordResult;
+                fn fetch_entitlements() => callback();
+                fn count_entitlements(out count: *mut i32);
+                fn get_entitlement(
+                    val entitlement_id: sys::DiscordSnowflake,
+                    out entitlement: *mut sys::DiscordEntitlement
+                ) -> sys::EDiscordResult;
+                fn get_entitlement_at(
+                    val index: i32,
+                    out entitlement: *mut sys::DiscordEntitlement
+                ) -> sys::EDiscordResult;
+                fn has_sku_entitlement(
+                    val sku_id: sys::DiscordSnowflake,
+                    out has_entitlement: *mut bool
+                ) -> sys::EDiscordResult;
+                fn start_purchase(val sku_id: sys::DiscordSnowflake) => callback();
+            } manual {}
+
+            users IDiscordUserManager {
+                fn get_current_user(out current_user: *mut sys::DiscordUser) -> sys::EDiscordResult;
+                fn get_user(
+                    val user_id: sys::DiscordUserId
+                ) => callback(ptr user: *mut sys::DiscordUser);
+                fn get_current_user_premium_type(
+                    out premium_type: *mut sys::EDiscordPremiumType
+                ) -> sys::EDiscordResult;
+                fn current_user_has_flag(
+                    val flag: sys::EDiscordUserFlag,
+                    out has_flag: *mut bool
+                ) -> sys::EDiscordResult;
+            } manual {}
+
+            voice IDiscordVoiceManager {
+                fn get_input_mode(out input_mode: *mut sys::DiscordInputMode) -> sys::EDiscordResult;
+                fn set_input_mode(val input_mode: sys::DiscordInputMode) => callback();
+                fn is_self_mute(out mute: *mut bool) -> sys::EDiscordResult;
+                fn set_self_mute(val mute: bool) -> sys::EDiscordResult;
+                fn is_self_deaf(out deaf: *mut bool) -> sys::EDiscordResult;
+                fn set_self_deaf(val deaf: bool) -> sys::EDiscordResult;
+                fn is_local_mute(
+                    val user_id: sys::DiscordSnowflake,
+                    out mute: *mut bool
+                ) -> sys::EDiscordResult;
+                fn set_local_mute(
+                    val user_id: sys::DiscordSnowflake,
+                    val mute: bool
+                ) -> sys::EDiscordResult;
+                fn get_local_volume(
+                    val user_id: sys::DiscordSnowflake,
+                    out volume: *mut u8
+                ) -> sys::EDiscordResult;
+                fn set_local_volume(
+                    val user_id: sys::DiscordSnowflake,
+                    val volume: u8
+                ) -> sys::EDiscordResult;
+            } manual {}
+        }
+    };
+}
+
+#[cfg(feature = "replay")]
+macro_rules! sdk_handles {
+    ($then:ident) => {
+        $then! {
+            lobby_transaction IDiscordLobbyTransaction {
+                fn set_type(val type_: sys::EDiscordLobbyType) -> sys::EDiscordResult;
+                fn set_owner(val owner_id: sys::DiscordUserId) -> sys::EDiscordResult;
+                fn set_capacity(val capacity: u32) -> sys::EDiscordResult;
+                fn set_metadata(str key: *mut u8, str value: *mut u8) -> sys::EDiscordResult;
+                fn delete_metadata(str key: *mut u8) -> sys::EDiscordResult;
+                fn set_locked(val locked: bool) -> sys::EDiscordResult;
+            } manual {}
+
+            lobby_member_transaction IDiscordLobbyMemberTransaction {
+                fn set_metadata(str key: *mut u8, str value: *mut u8) -> sys::EDiscordResult;
+                fn delete_metadata(str key: *mut u8) -> sys::EDiscordResult;
+            } manual {}
+
+            lobby_search_query IDiscordLobbySearchQuery {
+                fn filter(
+                    str key: *mut u8,
+                    val comparison: sys::EDiscordLobbySearchComparison,
+                    val cast: sys::EDiscordLobbySearchCast,
+                    str value: *mut u8
+                ) -> sys::EDiscordResult;
+                fn sort(
+                    str key: *mut u8,
+                    val cast: sys::EDiscordLobbySearchCast,
+                    str value: *mut u8
+                ) -> sys::EDiscordResult;
+                fn limit(val limit: u32) -> sys::EDiscordResult;
+                fn distance(val distance: sys::EDiscordLobbySearchDistance) -> sys::EDiscordResult;
+            } manual {}
+        }
+    };
+}
+
+#[cfg(feature = "replay")]
+macro_rules! sdk_events {
+    ($then:ident) => {
+        $then! {
+            achievement_events IDiscordAchievementEvents {
+                fn on_user_achievement_update(
+                    ptr user_achievement: *mut sys::DiscordUserAchievement
+                );
+            }
+
+            activity_events IDiscordActivityEvents {
+                fn on_activity_join(str secret: *const u8);
+                fn on_activity_spectate(str secret: *const u8);
+                fn on_activity_join_request(ptr user: *mut sys::DiscordUser);
+                fn on_activity_invite(
+                    val type_: sys::EDiscordActivityActionType,
+                    ptr user: *mut sys::DiscordUser,
+                    ptr activity: *mut sys::DiscordActivity
+                );
+            }
+
+            lobby_events IDiscordLobbyEvents {
+                fn on_lobby_update(val lobby_id: i64);
+                fn on_lobby_delete(val lobby_id: i64, val reason: u32);
+                fn on_member_connect(val lobby_id: i64, val user_id: i64);
+                fn on_member_update(val lobby_id: i64, val user_id: i64);
+                fn on_member_disconnect(val lobby_id: i64, val user_id: i64);
+                fn on_lobby_message(
+                    val lobby_id: i64,
+                    val user_id: i64,
+                    buf data[data_length]: *mut u8,
+                    len data_length: u32
+                );
+                fn on_speaking(val lobby_id: i64, val user_id: i64, val speaking: bool);
+                fn on_network_message(
+                    val lobby_id: i64,
+                    val user_id: i64,
+                    val channel_id: u8,
+                    buf data[data_length]: *mut u8,
+                    len data_length: u32
+                );
+            }
+
+            network_events IDiscordNetworkEvents {
+                fn on_message(
+                    val peer_id: sys::DiscordNetworkPeerId,
+                    val channel_id: sys::DiscordNetworkChannelId,
+                    buf data[data_length]: *mut u8,
+                    len data_length: u32
+                );
+                fn on_route_update(str route_data: *const u8);
+            }
+
+            overlay_events IDiscordOverlayEvents {
+                fn on_toggle(val locked: bool);
+            }
+
+            relationship_events IDiscordRelationshipEvents {
+                fn on_refresh();
+                fn on_relationship_update(ptr relationship: *mut sys::DiscordRelationship);
+            }
+
+            store_events IDiscordStoreEvents {
+                fn on_entitlement_create(ptr entitlement: *mut sys::DiscordEntitlement);
+                fn on_entitlement_delete(ptr entitlement: *mut sys::DiscordEntitlement);
+            }
+
+            user_events IDiscordUserEvents {
+                fn on_current_user_update();
+            }
+
+            voice_events IDiscordVoiceEvents {
+                fn on_settings_update();
+            }
+        }
+    };
+}
+
+macro_rules! sdk_managers {
+    ($then:ident) => {
+        $then! {
+            get_achievement_manager achievements IDiscordAchievementManager,
+            get_activity_manager activities IDiscordActivityManager,
+            get_application_manager applications IDiscordApplicationManager,
+            get_image_manager images IDiscordImageManager,
+            get_lobby_manager lobbies IDiscordLobbyManager,
+            get_network_manager networking IDiscordNetworkManager,
+            get_overlay_manager overlay IDiscordOverlayManager,
+            get_relationship_manager relationships IDiscordRelationshipManager,
+            get_storage_manager storage IDiscordStorageManager,
+            get_store_manager store IDiscordStoreManager,
+            get_user_manager users IDiscordUserManager,
+            get_voice_manager voice IDiscordVoiceManager
+        }
+    };
+}
+
+/// Type pointed to by an argument of a pointer type
+pub(crate) trait Pointer {
+    type Target;
+}
+
+impl<T> Pointer for *mut T {
+    type Target = T;
+}
+
+impl<T> Pointer for *const T {
+    type Target = T;
+}
+
+macro_rules! ret {
+    () => {
+        ()
+    };
+    ($ret:ty) => {
+        $ret
+    };
+}
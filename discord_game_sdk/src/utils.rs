@@ -27,6 +27,19 @@ pub(crate) fn abort_on_panic<R>(callback: impl FnOnce() -> R + std::panic::Unwin
     }
 }
 
+/// A metadata entry as transactions store it: nul-terminated, with `None` deleting the key
+pub(crate) fn metadata_entry(key: String, value: Option<String>) -> (String, Option<String>) {
+    fn nul_terminated(mut string: String) -> String {
+        if !string.ends_with('\0') {
+            string.push('\0')
+        }
+
+        string
+    }
+
+    (nul_terminated(key), value.map(nul_terminated))
+}
+
 pub(crate) fn charbuf_to_str(charbuf: &[u8]) -> &str {
     let bytes = &charbuf[..charbuf_len(charbuf)];
 
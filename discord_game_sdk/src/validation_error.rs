@@ -0,0 +1,268 @@
+use std::{collections::HashMap, fmt};
+
+/// Metadata keys are sent as nul-terminated strings and copied into a `DiscordMetadataKey`
+pub(crate) const METADATA_KEY_LIMIT: usize =
+    std::mem::size_of::<crate::sys::DiscordMetadataKey>() - 1;
+
+/// Metadata values are sent as nul-terminated strings and copied into a `DiscordMetadataValue`
+pub(crate) const METADATA_VALUE_LIMIT: usize =
+    std::mem::size_of::<crate::sys::DiscordMetadataValue>() - 1;
+
+/// A string does not fit the buffer the SDK keeps for it
+///
+/// The SDK copies strings into fixed-size buffers, longer strings would be truncated and nul
+/// bytes would end them early.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValidationError {
+    /// The string is longer than the buffer of `field`
+    TooLong {
+        /// Field the string was given for
+        field: String,
+        /// Length of the string, in bytes
+        len: usize,
+        /// Most bytes the field can hold
+        limit: usize,
+        /// Whether the limit falls inside a character, truncating would leave invalid UTF-8
+        splits_char: bool,
+    },
+
+    /// The string contains a nul byte, which would end it early
+    ContainsNul {
+        /// Field the string was given for
+        field: String,
+        /// Position of the first nul byte
+        position: usize,
+    },
+}
+
+impl ValidationError {
+    /// Field the string was given for
+    pub fn field(&self) -> &str {
+        match self {
+            ValidationError::TooLong { field, .. } | ValidationError::ContainsNul { field, .. } => {
+                field
+            }
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::TooLong {
+                field,
+                len,
+                limit,
+                splits_char,
+            } => {
+                write!(f, "{} is {} bytes long, over {} bytes", field, len, limit)?;
+
+                if *splits_char {
+                    write!(f, " and cut inside a character")?;
+                }
+
+                Ok(())
+            }
+            ValidationError::ContainsNul { field, position } => {
+                write!(f, "{} contains a nul byte at {}", field, position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Checks that `value` fits in `limit` bytes without nul bytes, a trailing nul is ignored and
+/// left out of the value returned
+pub(crate) fn validate_str(
+    field: impl fmt::Display,
+    value: &str,
+    limit: usize,
+) -> Result<&str, ValidationError> {
+    let value = value.strip_suffix('\0').unwrap_or(value);
+
+    if let Some(position) = memchr::memchr(0, value.as_bytes()) {
+        return Err(ValidationError::ContainsNul {
+            field: field.to_string(),
+            position,
+        });
+    }
+
+    if value.len() > limit {
+        return Err(ValidationError::TooLong {
+            field: field.to_string(),
+            len: value.len(),
+            limit,
+            splits_char: !value.is_char_boundary(limit),
+        });
+    }
+
+    Ok(value)
+}
+
+/// Checks a metadata key and the value it is set to, `None` when it is deleted
+pub(crate) fn validate_metadata(key: &str, value: Option<&str>) -> Result<(), ValidationError> {
+    let name = key.strip_suffix('\0').unwrap_or(key);
+
+    validate_str(
+        format_args!("metadata key {:?}", name),
+        key,
+        METADATA_KEY_LIMIT,
+    )?;
+
+    if let Some(value) = value {
+        validate_str(
+            format_args!("metadata value of {:?}", name),
+            value,
+            METADATA_VALUE_LIMIT,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Checks the metadata of a transaction, in the order of its keys
+pub(crate) fn validate_metadata_map(
+    metadata: &HashMap<String, Option<String>>,
+) -> Result<(), ValidationError> {
+    let mut metadata: Vec<_> = metadata.iter().collect();
+    metadata.sort_unstable_by_key(|(key, _)| *key);
+
+    for (key, value) in metadata {
+        validate_metadata(key, value.as_deref())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::fixtures::{create_lobby, run},
+        Activity, Cast, Comparison, Discord, Error, LobbyMemberTransaction, LobbyTransaction,
+        Result, SearchQuery,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn oversized_strings_are_refused() {
+        let long_key = "k".repeat(300);
+        let mut transaction = LobbyTransaction::new();
+        transaction.add_metadata(long_key.clone(), "v".to_string());
+        assert_eq!(
+            transaction.validate(),
+            Err(ValidationError::TooLong {
+                field: format!("metadata key {:?}", long_key),
+                len: 300,
+                limit: 255,
+                splits_char: false,
+            })
+        );
+
+        assert_eq!(
+            LobbyMemberTransaction::new()
+                .try_add_metadata("name".to_string(), "\u{e9}".repeat(2048))
+                .err(),
+            Some(ValidationError::TooLong {
+                field: "metadata value of \"name\"".to_string(),
+                len: 4096,
+                limit: 4095,
+                splits_char: true,
+            })
+        );
+
+        assert_eq!(
+            SearchQuery::new()
+                .try_filter(
+                    "mode".to_string(),
+                    Comparison::Equal,
+                    "a\0b".to_string(),
+                    Cast::String
+                )
+                .err(),
+            Some(ValidationError::ContainsNul {
+                field: "filter value".to_string(),
+                position: 1,
+            })
+        );
+
+        let mut activity = Activity::empty();
+        assert_eq!(
+            activity.try_with_state(&"x".repeat(128)).err(),
+            Some(ValidationError::TooLong {
+                field: "state".to_string(),
+                len: 128,
+                limit: 127,
+                splits_char: false,
+            })
+        );
+        assert_eq!(activity.state(), "");
+        assert!(activity.try_with_state(&"x".repeat(127)).is_ok());
+        assert!(activity
+            .try_with_state(&format!("{}\0", "y".repeat(127)))
+            .is_ok());
+        assert_eq!(activity.state(), "y".repeat(127));
+        assert!(activity
+            .try_with_party_id(&format!("{}\0", "p".repeat(128)))
+            .is_err());
+    }
+
+    #[test]
+    fn oversized_strings_are_not_sent() {
+        let mut discord = Discord::<()>::mock();
+        let lobby = create_lobby(&mut discord, &LobbyTransaction::new());
+        let results = Rc::new(RefCell::new(Vec::new()));
+
+        let push = |results: &Rc<RefCell<Vec<Result<()>>>>| {
+            let results = results.clone();
+            move |_: &Discord<'_, ()>, res| results.borrow_mut().push(res)
+        };
+
+        discord.update_lobby(
+            lobby.id(),
+            LobbyTransaction::new().add_metadata("k".repeat(300), "v".to_string()),
+            push(&results),
+        );
+        discord.update_member(
+            lobby.id(),
+            1,
+            LobbyMemberTransaction::new().add_metadata("team".to_string(), "v".repeat(4096)),
+            push(&results),
+        );
+        discord.lobby_search(
+            SearchQuery::new().filter(
+                "mode".to_string(),
+                Comparison::Equal,
+                "a\0b".to_string(),
+                Cast::String,
+            ),
+            push(&results),
+        );
+        run(&mut discord);
+
+        assert_eq!(
+            *results.borrow(),
+            vec![
+                Err(Error::InvalidString(ValidationError::TooLong {
+                    field: format!("metadata key {:?}", "k".repeat(300)),
+                    len: 300,
+                    limit: 255,
+                    splits_char: false,
+                })),
+                Err(Error::InvalidString(ValidationError::TooLong {
+                    field: "metadata value of \"team\"".to_string(),
+                    len: 4096,
+                    limit: 4095,
+                    splits_char: false,
+                })),
+                Err(Error::InvalidString(ValidationError::ContainsNul {
+                    field: "filter value".to_string(),
+                    position: 1,
+                })),
+            ]
+        );
+        assert_eq!(discord.lobby_metadata_count(lobby.id()), Ok(0));
+    }
+}
@@ -0,0 +1,209 @@
+//! Derive macros for [`discord_game_sdk`](https://docs.rs/discord_game_sdk).
+//!
+//! Use them through the `derive` feature of `discord_game_sdk` rather than depending on this
+//! crate directly.
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// Implements `LobbyMetadata` for a struct with named fields.
+///
+/// Every field type must implement `MetadataValue`. Fields are stored under their name, which
+/// can be changed with `#[lobby_metadata(rename = "key")]`.
+#[proc_macro_derive(LobbyMetadata, attributes(lobby_metadata))]
+pub fn derive_lobby_metadata(input: TokenStream) -> TokenStream {
+    match parse_struct(input) {
+        Ok((name, fields)) => expand(&name, &fields),
+        Err(message) => format!("compile_error!({:?});", message).parse().unwrap(),
+    }
+}
+
+struct Field {
+    ident: String,
+    key: String,
+}
+
+fn parse_struct(input: TokenStream) -> Result<(String, Vec<Field>), String> {
+    let mut tokens = input.into_iter().peekable();
+
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => break,
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "enum" => {
+                return Err("LobbyMetadata can only be derived for structs".into())
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "union" => {
+                return Err("LobbyMetadata can only be derived for structs".into())
+            }
+            Some(_) => {}
+            None => return Err("expected a struct".into()),
+        }
+    }
+
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return Err("expected the name of the struct".into()),
+    };
+
+    match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+            Ok((name, parse_fields(group.stream())?))
+        }
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+            Err("LobbyMetadata cannot be derived for generic structs".into())
+        }
+        _ => Err("LobbyMetadata can only be derived for structs with named fields".into()),
+    }
+}
+
+fn parse_fields(input: TokenStream) -> Result<Vec<Field>, String> {
+    let mut fields = Vec::new();
+    let mut tokens = input.into_iter().peekable();
+
+    while tokens.peek().is_some() {
+        let mut rename = None;
+
+        // Attributes
+        while let Some(TokenTree::Punct(punct)) = tokens.peek() {
+            if punct.as_char() != '#' {
+                break;
+            }
+            tokens.next();
+
+            match tokens.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+                    if let Some(key) = parse_attribute(group.stream())? {
+                        rename = Some(key);
+                    }
+                }
+                _ => return Err("expected an attribute".into()),
+            }
+        }
+
+        // Visibility
+        if let Some(TokenTree::Ident(ident)) = tokens.peek() {
+            if ident.to_string() == "pub" {
+                tokens.next();
+
+                if let Some(TokenTree::Group(group)) = tokens.peek() {
+                    if group.delimiter() == Delimiter::Parenthesis {
+                        tokens.next();
+                    }
+                }
+            }
+        }
+
+        let ident = match tokens.next() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => return Err("expected a field name".into()),
+        };
+
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {}
+            _ => return Err(format!("expected a type for field `{}`", ident)),
+        }
+
+        // The type is inferred in the generated code, skip to the next field
+        let mut depth = 0_usize;
+        let mut previous = ' ';
+        for token in &mut tokens {
+            if let TokenTree::Punct(punct) = token {
+                match punct.as_char() {
+                    '<' => depth += 1,
+                    '>' if previous != '-' => depth = depth.saturating_sub(1),
+                    ',' if depth == 0 => break,
+                    _ => {}
+                }
+                previous = punct.as_char();
+            } else {
+                previous = ' ';
+            }
+        }
+
+        let key = rename.unwrap_or_else(|| ident.trim_start_matches("r#").to_string());
+
+        fields.push(Field { ident, key });
+    }
+
+    Ok(fields)
+}
+
+/// Returns the key given by `lobby_metadata(rename = "key")`, other attributes are ignored.
+fn parse_attribute(input: TokenStream) -> Result<Option<String>, String> {
+    let mut tokens = input.into_iter();
+
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "lobby_metadata" => {}
+        _ => return Ok(None),
+    }
+
+    let arguments = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            group.stream().into_iter().collect::<Vec<_>>()
+        }
+        _ => return Err("expected `#[lobby_metadata(rename = \"key\")]`".into()),
+    };
+
+    match arguments.as_slice() {
+        [TokenTree::Ident(ident), TokenTree::Punct(punct), TokenTree::Literal(literal)]
+            if ident.to_string() == "rename" && punct.as_char() == '=' =>
+        {
+            let literal = literal.to_string();
+
+            if literal.len() >= 2
+                && literal.starts_with('"')
+                && literal.ends_with('"')
+                && !literal.contains('\\')
+            {
+                Ok(Some(literal[1..literal.len() - 1].to_string()))
+            } else {
+                Err("`rename` expects a plain string literal".into())
+            }
+        }
+        _ => Err("expected `#[lobby_metadata(rename = \"key\")]`".into()),
+    }
+}
+
+fn expand(name: &str, fields: &[Field]) -> TokenStream {
+    let mut encode = String::new();
+    let mut decode = String::new();
+
+    for Field { ident, key } in fields {
+        encode.push_str(&format!(
+            "(::std::string::String::from({key:?}), \
+             ::discord_game_sdk::MetadataValue::encode(&self.{ident}, {key:?})?),",
+            ident = ident,
+            key = key,
+        ));
+
+        decode.push_str(&format!(
+            "{ident}: ::discord_game_sdk::MetadataValue::decode(\
+             {key:?}, metadata.get({key:?}).map(::std::string::String::as_str))?,",
+            ident = ident,
+            key = key,
+        ));
+    }
+
+    format!(
+        "impl ::discord_game_sdk::LobbyMetadata for {name} {{
+            fn to_metadata(&self) -> ::std::result::Result<
+                ::std::vec::Vec<(::std::string::String, ::std::option::Option<::std::string::String>)>,
+                ::discord_game_sdk::MetadataError,
+            > {{
+                ::std::result::Result::Ok(::std::vec![{encode}])
+            }}
+
+            fn from_metadata(
+                metadata: &::std::collections::HashMap<::std::string::String, ::std::string::String>,
+            ) -> ::std::result::Result<Self, ::discord_game_sdk::MetadataError> {{
+                ::std::result::Result::Ok(Self {{ {decode} }})
+            }}
+        }}",
+        name = name,
+        encode = encode,
+        decode = decode,
+    )
+    .parse()
+    .unwrap()
+}
@@ -6,15 +6,34 @@ fn main() {
         return generate_ffi_bindings(bindgen::builder().header("discord_game_sdk.h"));
     }
 
-    let sdk_path = PathBuf::from(env::var("DISCORD_GAME_SDK_PATH").expect(MISSING_SDK_PATH));
+    let sdk_path = env::var_os("DISCORD_GAME_SDK_PATH").map(PathBuf::from);
     println!("cargo:rerun-if-env-changed=DISCORD_GAME_SDK_PATH");
-    println!("cargo:rerun-if-changed={}", sdk_path.display());
 
-    generate_ffi_bindings(
-        bindgen::builder().header(sdk_path.join("c/discord_game_sdk.h").to_str().unwrap()),
-    );
+    if let Some(sdk_path) = &sdk_path {
+        println!("cargo:rerun-if-changed={}", sdk_path.display());
+    }
+
+    if cfg!(feature = "pregenerated") {
+        if let Some(sdk_path) = &sdk_path {
+            verify_header(&sdk_path.join("c/discord_game_sdk.h"));
+        }
+
+        copy_pregenerated_bindings();
+    } else {
+        let sdk_path = sdk_path.as_ref().expect(MISSING_SDK_PATH);
+
+        generate_ffi_bindings(
+            bindgen::builder().header(sdk_path.join("c/discord_game_sdk.h").to_str().unwrap()),
+        );
+    }
+
+    // The library is loaded at runtime instead
+    if cfg!(feature = "dynamic") {
+        return;
+    }
 
     if cfg!(feature = "link") {
+        let sdk_path = sdk_path.expect(MISSING_SDK_PATH);
         let target = env::var("TARGET").unwrap();
 
         verify_installation(&target, &sdk_path);
@@ -22,6 +41,31 @@ fn main() {
     }
 }
 
+// The pregenerated bindings match the vendored header, any other header could have different
+// layouts or signatures. Line endings differ between the archive's files and git checkouts.
+fn verify_header(header_path: &Path) {
+    let read = |path: &Path| {
+        std::fs::read_to_string(path)
+            .unwrap_or_else(|err| {
+                panic!("discord_game_sdk_sys: could not read {:?}: {}", path, err)
+            })
+            .replace("\r\n", "\n")
+    };
+
+    if read(header_path) != read(Path::new("discord_game_sdk.h")) {
+        panic!("{}", HEADER_MISMATCH);
+    }
+}
+
+fn copy_pregenerated_bindings() {
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    println!("cargo:rerun-if-changed=src/bindings.rs");
+
+    std::fs::copy("src/bindings.rs", out_path.join("bindings.rs"))
+        .expect("discord_game_sdk_sys: could not write bindings to file");
+}
+
 fn verify_installation(target: &str, sdk_path: &Path) {
     match target {
         "x86_64-unknown-linux-gnu" => {
@@ -151,6 +195,24 @@ Thanks, and apologies for the inconvenience
 
 "#;
 
+const HEADER_MISMATCH: &str = r#"
+
+discord_game_sdk_sys: Hello,
+
+You are trying to use the pregenerated bindings of the Discord Game SDK.
+They were generated from the header vendored in this crate, but the header found in
+`DISCORD_GAME_SDK_PATH` is different, most likely because it is from another version of the SDK.
+
+Either download the version of the SDK that matches this crate,
+or disable the `pregenerated` feature to generate bindings from your header.
+
+Please report any issues you have at:
+https://github.com/ldesgoui/discord_game_sdk
+
+Thanks, and apologies for the inconvenience
+
+"#;
+
 const MISSING_SETUP: &str = r#"
 
 discord_game_sdk_sys: Hello,
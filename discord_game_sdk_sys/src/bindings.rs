@@ -0,0 +1,1831 @@
+/* automatically generated by rust-bindgen 0.59.2 */
+
+pub const DISCORD_VERSION: i32 = 2;
+pub const DISCORD_APPLICATION_MANAGER_VERSION: i32 = 1;
+pub const DISCORD_USER_MANAGER_VERSION: i32 = 1;
+pub const DISCORD_IMAGE_MANAGER_VERSION: i32 = 1;
+pub const DISCORD_ACTIVITY_MANAGER_VERSION: i32 = 1;
+pub const DISCORD_RELATIONSHIP_MANAGER_VERSION: i32 = 1;
+pub const DISCORD_LOBBY_MANAGER_VERSION: i32 = 1;
+pub const DISCORD_NETWORK_MANAGER_VERSION: i32 = 1;
+pub const DISCORD_OVERLAY_MANAGER_VERSION: i32 = 1;
+pub const DISCORD_STORAGE_MANAGER_VERSION: i32 = 1;
+pub const DISCORD_STORE_MANAGER_VERSION: i32 = 1;
+pub const DISCORD_VOICE_MANAGER_VERSION: i32 = 1;
+pub const DISCORD_ACHIEVEMENT_MANAGER_VERSION: i32 = 1;
+pub const DiscordResult_Ok: EDiscordResult = 0;
+pub const DiscordResult_ServiceUnavailable: EDiscordResult = 1;
+pub const DiscordResult_InvalidVersion: EDiscordResult = 2;
+pub const DiscordResult_LockFailed: EDiscordResult = 3;
+pub const DiscordResult_InternalError: EDiscordResult = 4;
+pub const DiscordResult_InvalidPayload: EDiscordResult = 5;
+pub const DiscordResult_InvalidCommand: EDiscordResult = 6;
+pub const DiscordResult_InvalidPermissions: EDiscordResult = 7;
+pub const DiscordResult_NotFetched: EDiscordResult = 8;
+pub const DiscordResult_NotFound: EDiscordResult = 9;
+pub const DiscordResult_Conflict: EDiscordResult = 10;
+pub const DiscordResult_InvalidSecret: EDiscordResult = 11;
+pub const DiscordResult_InvalidJoinSecret: EDiscordResult = 12;
+pub const DiscordResult_NoEligibleActivity: EDiscordResult = 13;
+pub const DiscordResult_InvalidInvite: EDiscordResult = 14;
+pub const DiscordResult_NotAuthenticated: EDiscordResult = 15;
+pub const DiscordResult_InvalidAccessToken: EDiscordResult = 16;
+pub const DiscordResult_ApplicationMismatch: EDiscordResult = 17;
+pub const DiscordResult_InvalidDataUrl: EDiscordResult = 18;
+pub const DiscordResult_InvalidBase64: EDiscordResult = 19;
+pub const DiscordResult_NotFiltered: EDiscordResult = 20;
+pub const DiscordResult_LobbyFull: EDiscordResult = 21;
+pub const DiscordResult_InvalidLobbySecret: EDiscordResult = 22;
+pub const DiscordResult_InvalidFilename: EDiscordResult = 23;
+pub const DiscordResult_InvalidFileSize: EDiscordResult = 24;
+pub const DiscordResult_InvalidEntitlement: EDiscordResult = 25;
+pub const DiscordResult_NotInstalled: EDiscordResult = 26;
+pub const DiscordResult_NotRunning: EDiscordResult = 27;
+pub const DiscordResult_InsufficientBuffer: EDiscordResult = 28;
+pub const DiscordResult_PurchaseCanceled: EDiscordResult = 29;
+pub const DiscordResult_InvalidGuild: EDiscordResult = 30;
+pub const DiscordResult_InvalidEvent: EDiscordResult = 31;
+pub const DiscordResult_InvalidChannel: EDiscordResult = 32;
+pub const DiscordResult_InvalidOrigin: EDiscordResult = 33;
+pub const DiscordResult_RateLimited: EDiscordResult = 34;
+pub const DiscordResult_OAuth2Error: EDiscordResult = 35;
+pub const DiscordResult_SelectChannelTimeout: EDiscordResult = 36;
+pub const DiscordResult_GetGuildTimeout: EDiscordResult = 37;
+pub const DiscordResult_SelectVoiceForceRequired: EDiscordResult = 38;
+pub const DiscordResult_CaptureShortcutAlreadyListening: EDiscordResult = 39;
+pub const DiscordResult_UnauthorizedForAchievement: EDiscordResult = 40;
+pub const DiscordResult_InvalidGiftCode: EDiscordResult = 41;
+pub const DiscordResult_PurchaseError: EDiscordResult = 42;
+pub const DiscordResult_TransactionAborted: EDiscordResult = 43;
+pub type EDiscordResult = ctypes::c_uint;
+pub const DiscordCreateFlags_Default: EDiscordCreateFlags = 0;
+pub const DiscordCreateFlags_NoRequireDiscord: EDiscordCreateFlags = 1;
+pub type EDiscordCreateFlags = ctypes::c_uint;
+pub const DiscordLogLevel_Error: EDiscordLogLevel = 1;
+pub const DiscordLogLevel_Warn: EDiscordLogLevel = 2;
+pub const DiscordLogLevel_Info: EDiscordLogLevel = 3;
+pub const DiscordLogLevel_Debug: EDiscordLogLevel = 4;
+pub type EDiscordLogLevel = ctypes::c_uint;
+pub const DiscordUserFlag_Partner: EDiscordUserFlag = 2;
+pub const DiscordUserFlag_HypeSquadEvents: EDiscordUserFlag = 4;
+pub const DiscordUserFlag_HypeSquadHouse1: EDiscordUserFlag = 64;
+pub const DiscordUserFlag_HypeSquadHouse2: EDiscordUserFlag = 128;
+pub const DiscordUserFlag_HypeSquadHouse3: EDiscordUserFlag = 256;
+pub type EDiscordUserFlag = ctypes::c_uint;
+pub const DiscordPremiumType_None: EDiscordPremiumType = 0;
+pub const DiscordPremiumType_Tier1: EDiscordPremiumType = 1;
+pub const DiscordPremiumType_Tier2: EDiscordPremiumType = 2;
+pub type EDiscordPremiumType = ctypes::c_uint;
+pub const DiscordImageType_User: EDiscordImageType = 0;
+pub type EDiscordImageType = ctypes::c_uint;
+pub const DiscordActivityType_Playing: EDiscordActivityType = 0;
+pub const DiscordActivityType_Streaming: EDiscordActivityType = 1;
+pub const DiscordActivityType_Listening: EDiscordActivityType = 2;
+pub const DiscordActivityType_Watching: EDiscordActivityType = 3;
+pub type EDiscordActivityType = ctypes::c_uint;
+pub const DiscordActivityActionType_Join: EDiscordActivityActionType = 1;
+pub const DiscordActivityActionType_Spectate: EDiscordActivityActionType = 2;
+pub type EDiscordActivityActionType = ctypes::c_uint;
+pub const DiscordActivityJoinRequestReply_No: EDiscordActivityJoinRequestReply = 0;
+pub const DiscordActivityJoinRequestReply_Yes: EDiscordActivityJoinRequestReply = 1;
+pub const DiscordActivityJoinRequestReply_Ignore: EDiscordActivityJoinRequestReply = 2;
+pub type EDiscordActivityJoinRequestReply = ctypes::c_uint;
+pub const DiscordStatus_Offline: EDiscordStatus = 0;
+pub const DiscordStatus_Online: EDiscordStatus = 1;
+pub const DiscordStatus_Idle: EDiscordStatus = 2;
+pub const DiscordStatus_DoNotDisturb: EDiscordStatus = 3;
+pub type EDiscordStatus = ctypes::c_uint;
+pub const DiscordRelationshipType_None: EDiscordRelationshipType = 0;
+pub const DiscordRelationshipType_Friend: EDiscordRelationshipType = 1;
+pub const DiscordRelationshipType_Blocked: EDiscordRelationshipType = 2;
+pub const DiscordRelationshipType_PendingIncoming: EDiscordRelationshipType = 3;
+pub const DiscordRelationshipType_PendingOutgoing: EDiscordRelationshipType = 4;
+pub const DiscordRelationshipType_Implicit: EDiscordRelationshipType = 5;
+pub type EDiscordRelationshipType = ctypes::c_uint;
+pub const DiscordLobbyType_Private: EDiscordLobbyType = 1;
+pub const DiscordLobbyType_Public: EDiscordLobbyType = 2;
+pub type EDiscordLobbyType = ctypes::c_uint;
+pub const DiscordLobbySearchComparison_LessThanOrEqual: EDiscordLobbySearchComparison = -2;
+pub const DiscordLobbySearchComparison_LessThan: EDiscordLobbySearchComparison = -1;
+pub const DiscordLobbySearchComparison_Equal: EDiscordLobbySearchComparison = 0;
+pub const DiscordLobbySearchComparison_GreaterThan: EDiscordLobbySearchComparison = 1;
+pub const DiscordLobbySearchComparison_GreaterThanOrEqual: EDiscordLobbySearchComparison = 2;
+pub const DiscordLobbySearchComparison_NotEqual: EDiscordLobbySearchComparison = 3;
+pub type EDiscordLobbySearchComparison = ctypes::c_int;
+pub const DiscordLobbySearchCast_String: EDiscordLobbySearchCast = 1;
+pub const DiscordLobbySearchCast_Number: EDiscordLobbySearchCast = 2;
+pub type EDiscordLobbySearchCast = ctypes::c_uint;
+pub const DiscordLobbySearchDistance_Local: EDiscordLobbySearchDistance = 0;
+pub const DiscordLobbySearchDistance_Default: EDiscordLobbySearchDistance = 1;
+pub const DiscordLobbySearchDistance_Extended: EDiscordLobbySearchDistance = 2;
+pub const DiscordLobbySearchDistance_Global: EDiscordLobbySearchDistance = 3;
+pub type EDiscordLobbySearchDistance = ctypes::c_uint;
+pub const DiscordEntitlementType_Purchase: EDiscordEntitlementType = 1;
+pub const DiscordEntitlementType_PremiumSubscription: EDiscordEntitlementType = 2;
+pub const DiscordEntitlementType_DeveloperGift: EDiscordEntitlementType = 3;
+pub const DiscordEntitlementType_TestModePurchase: EDiscordEntitlementType = 4;
+pub const DiscordEntitlementType_FreePurchase: EDiscordEntitlementType = 5;
+pub const DiscordEntitlementType_UserGift: EDiscordEntitlementType = 6;
+pub const DiscordEntitlementType_PremiumPurchase: EDiscordEntitlementType = 7;
+pub type EDiscordEntitlementType = ctypes::c_uint;
+pub const DiscordSkuType_Application: EDiscordSkuType = 1;
+pub const DiscordSkuType_DLC: EDiscordSkuType = 2;
+pub const DiscordSkuType_Consumable: EDiscordSkuType = 3;
+pub const DiscordSkuType_Bundle: EDiscordSkuType = 4;
+pub type EDiscordSkuType = ctypes::c_uint;
+pub const DiscordInputModeType_VoiceActivity: EDiscordInputModeType = 0;
+pub const DiscordInputModeType_PushToTalk: EDiscordInputModeType = 1;
+pub type EDiscordInputModeType = ctypes::c_uint;
+pub type DiscordClientId = i64;
+pub type DiscordVersion = i32;
+pub type DiscordSnowflake = i64;
+pub type DiscordTimestamp = i64;
+pub type DiscordUserId = DiscordSnowflake;
+pub type DiscordLocale = [ctypes::c_char; 128usize];
+pub type DiscordBranch = [ctypes::c_char; 4096usize];
+pub type DiscordLobbyId = DiscordSnowflake;
+pub type DiscordLobbySecret = [ctypes::c_char; 128usize];
+pub type DiscordMetadataKey = [ctypes::c_char; 256usize];
+pub type DiscordMetadataValue = [ctypes::c_char; 4096usize];
+pub type DiscordNetworkPeerId = u64;
+pub type DiscordNetworkChannelId = u8;
+pub type DiscordPath = [ctypes::c_char; 4096usize];
+pub type DiscordDateTime = [ctypes::c_char; 64usize];
+pub type IDiscordApplicationEvents = *mut ctypes::c_void;
+pub type IDiscordImageEvents = *mut ctypes::c_void;
+pub type IDiscordStorageEvents = *mut ctypes::c_void;
+pub type IDiscordCoreEvents = *mut ctypes::c_void;
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordUser {
+    pub id: DiscordUserId,
+    pub username: [ctypes::c_char; 256usize],
+    pub discriminator: [ctypes::c_char; 8usize],
+    pub avatar: [ctypes::c_char; 128usize],
+    pub bot: bool,
+}
+impl Default for DiscordUser {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordOAuth2Token {
+    pub access_token: [ctypes::c_char; 128usize],
+    pub scopes: [ctypes::c_char; 1024usize],
+    pub expires: DiscordTimestamp,
+}
+impl Default for DiscordOAuth2Token {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct DiscordImageHandle {
+    pub type_: EDiscordImageType,
+    pub id: i64,
+    pub size: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct DiscordImageDimensions {
+    pub width: u32,
+    pub height: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct DiscordActivityTimestamps {
+    pub start: DiscordTimestamp,
+    pub end: DiscordTimestamp,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordActivityAssets {
+    pub large_image: [ctypes::c_char; 128usize],
+    pub large_text: [ctypes::c_char; 128usize],
+    pub small_image: [ctypes::c_char; 128usize],
+    pub small_text: [ctypes::c_char; 128usize],
+}
+impl Default for DiscordActivityAssets {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct DiscordPartySize {
+    pub current_size: i32,
+    pub max_size: i32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordActivityParty {
+    pub id: [ctypes::c_char; 128usize],
+    pub size: DiscordPartySize,
+}
+impl Default for DiscordActivityParty {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordActivitySecrets {
+    pub match_: [ctypes::c_char; 128usize],
+    pub join: [ctypes::c_char; 128usize],
+    pub spectate: [ctypes::c_char; 128usize],
+}
+impl Default for DiscordActivitySecrets {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordActivity {
+    pub type_: EDiscordActivityType,
+    pub application_id: i64,
+    pub name: [ctypes::c_char; 128usize],
+    pub state: [ctypes::c_char; 128usize],
+    pub details: [ctypes::c_char; 128usize],
+    pub timestamps: DiscordActivityTimestamps,
+    pub assets: DiscordActivityAssets,
+    pub party: DiscordActivityParty,
+    pub secrets: DiscordActivitySecrets,
+    pub instance: bool,
+}
+impl Default for DiscordActivity {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct DiscordPresence {
+    pub status: EDiscordStatus,
+    pub activity: DiscordActivity,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct DiscordRelationship {
+    pub type_: EDiscordRelationshipType,
+    pub user: DiscordUser,
+    pub presence: DiscordPresence,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordLobby {
+    pub id: DiscordLobbyId,
+    pub type_: EDiscordLobbyType,
+    pub owner_id: DiscordUserId,
+    pub secret: DiscordLobbySecret,
+    pub capacity: u32,
+    pub locked: bool,
+}
+impl Default for DiscordLobby {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordFileStat {
+    pub filename: [ctypes::c_char; 260usize],
+    pub size: u64,
+    pub last_modified: u64,
+}
+impl Default for DiscordFileStat {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct DiscordEntitlement {
+    pub id: DiscordSnowflake,
+    pub type_: EDiscordEntitlementType,
+    pub sku_id: DiscordSnowflake,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct DiscordSkuPrice {
+    pub amount: u32,
+    pub currency: [ctypes::c_char; 16usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordSku {
+    pub id: DiscordSnowflake,
+    pub type_: EDiscordSkuType,
+    pub name: [ctypes::c_char; 256usize],
+    pub price: DiscordSkuPrice,
+}
+impl Default for DiscordSku {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordInputMode {
+    pub type_: EDiscordInputModeType,
+    pub shortcut: [ctypes::c_char; 256usize],
+}
+impl Default for DiscordInputMode {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordUserAchievement {
+    pub user_id: DiscordSnowflake,
+    pub achievement_id: DiscordSnowflake,
+    pub percent_complete: u8,
+    pub unlocked_at: DiscordDateTime,
+}
+impl Default for DiscordUserAchievement {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordLobbyTransaction {
+    pub set_type: Option<
+        unsafe extern "C" fn(
+            lobby_transaction: *mut IDiscordLobbyTransaction,
+            type_: EDiscordLobbyType,
+        ) -> EDiscordResult,
+    >,
+    pub set_owner: Option<
+        unsafe extern "C" fn(
+            lobby_transaction: *mut IDiscordLobbyTransaction,
+            owner_id: DiscordUserId,
+        ) -> EDiscordResult,
+    >,
+    pub set_capacity: Option<
+        unsafe extern "C" fn(
+            lobby_transaction: *mut IDiscordLobbyTransaction,
+            capacity: u32,
+        ) -> EDiscordResult,
+    >,
+    pub set_metadata: Option<
+        unsafe extern "C" fn(
+            lobby_transaction: *mut IDiscordLobbyTransaction,
+            key: *mut ctypes::c_char,
+            value: *mut ctypes::c_char,
+        ) -> EDiscordResult,
+    >,
+    pub delete_metadata: Option<
+        unsafe extern "C" fn(
+            lobby_transaction: *mut IDiscordLobbyTransaction,
+            key: *mut ctypes::c_char,
+        ) -> EDiscordResult,
+    >,
+    pub set_locked: Option<
+        unsafe extern "C" fn(
+            lobby_transaction: *mut IDiscordLobbyTransaction,
+            locked: bool,
+        ) -> EDiscordResult,
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordLobbyMemberTransaction {
+    pub set_metadata: Option<
+        unsafe extern "C" fn(
+            lobby_member_transaction: *mut IDiscordLobbyMemberTransaction,
+            key: *mut ctypes::c_char,
+            value: *mut ctypes::c_char,
+        ) -> EDiscordResult,
+    >,
+    pub delete_metadata: Option<
+        unsafe extern "C" fn(
+            lobby_member_transaction: *mut IDiscordLobbyMemberTransaction,
+            key: *mut ctypes::c_char,
+        ) -> EDiscordResult,
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordLobbySearchQuery {
+    pub filter: Option<
+        unsafe extern "C" fn(
+            lobby_search_query: *mut IDiscordLobbySearchQuery,
+            key: *mut ctypes::c_char,
+            comparison: EDiscordLobbySearchComparison,
+            cast: EDiscordLobbySearchCast,
+            value: *mut ctypes::c_char,
+        ) -> EDiscordResult,
+    >,
+    pub sort: Option<
+        unsafe extern "C" fn(
+            lobby_search_query: *mut IDiscordLobbySearchQuery,
+            key: *mut ctypes::c_char,
+            cast: EDiscordLobbySearchCast,
+            value: *mut ctypes::c_char,
+        ) -> EDiscordResult,
+    >,
+    pub limit: Option<
+        unsafe extern "C" fn(
+            lobby_search_query: *mut IDiscordLobbySearchQuery,
+            limit: u32,
+        ) -> EDiscordResult,
+    >,
+    pub distance: Option<
+        unsafe extern "C" fn(
+            lobby_search_query: *mut IDiscordLobbySearchQuery,
+            distance: EDiscordLobbySearchDistance,
+        ) -> EDiscordResult,
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordApplicationManager {
+    pub validate_or_exit: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordApplicationManager,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub get_current_locale: Option<
+        unsafe extern "C" fn(manager: *mut IDiscordApplicationManager, locale: *mut DiscordLocale),
+    >,
+    pub get_current_branch: Option<
+        unsafe extern "C" fn(manager: *mut IDiscordApplicationManager, branch: *mut DiscordBranch),
+    >,
+    pub get_oauth2_token: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordApplicationManager,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(
+                    callback_data: *mut ctypes::c_void,
+                    result: EDiscordResult,
+                    oauth2_token: *mut DiscordOAuth2Token,
+                ),
+            >,
+        ),
+    >,
+    pub get_ticket: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordApplicationManager,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(
+                    callback_data: *mut ctypes::c_void,
+                    result: EDiscordResult,
+                    data: *const ctypes::c_char,
+                ),
+            >,
+        ),
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordUserEvents {
+    pub on_current_user_update: Option<unsafe extern "C" fn(event_data: *mut ctypes::c_void)>,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordUserManager {
+    pub get_current_user: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordUserManager,
+            current_user: *mut DiscordUser,
+        ) -> EDiscordResult,
+    >,
+    pub get_user: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordUserManager,
+            user_id: DiscordUserId,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(
+                    callback_data: *mut ctypes::c_void,
+                    result: EDiscordResult,
+                    user: *mut DiscordUser,
+                ),
+            >,
+        ),
+    >,
+    pub get_current_user_premium_type: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordUserManager,
+            premium_type: *mut EDiscordPremiumType,
+        ) -> EDiscordResult,
+    >,
+    pub current_user_has_flag: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordUserManager,
+            flag: EDiscordUserFlag,
+            has_flag: *mut bool,
+        ) -> EDiscordResult,
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordImageManager {
+    pub fetch: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordImageManager,
+            handle: DiscordImageHandle,
+            refresh: bool,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(
+                    callback_data: *mut ctypes::c_void,
+                    result: EDiscordResult,
+                    handle_result: DiscordImageHandle,
+                ),
+            >,
+        ),
+    >,
+    pub get_dimensions: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordImageManager,
+            handle: DiscordImageHandle,
+            dimensions: *mut DiscordImageDimensions,
+        ) -> EDiscordResult,
+    >,
+    pub get_data: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordImageManager,
+            handle: DiscordImageHandle,
+            data: *mut u8,
+            data_length: u32,
+        ) -> EDiscordResult,
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordActivityEvents {
+    pub on_activity_join: Option<
+        unsafe extern "C" fn(event_data: *mut ctypes::c_void, secret: *const ctypes::c_char),
+    >,
+    pub on_activity_spectate: Option<
+        unsafe extern "C" fn(event_data: *mut ctypes::c_void, secret: *const ctypes::c_char),
+    >,
+    pub on_activity_join_request:
+        Option<unsafe extern "C" fn(event_data: *mut ctypes::c_void, user: *mut DiscordUser)>,
+    pub on_activity_invite: Option<
+        unsafe extern "C" fn(
+            event_data: *mut ctypes::c_void,
+            type_: EDiscordActivityActionType,
+            user: *mut DiscordUser,
+            activity: *mut DiscordActivity,
+        ),
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordActivityManager {
+    pub register_command: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordActivityManager,
+            command: *const ctypes::c_char,
+        ) -> EDiscordResult,
+    >,
+    pub register_steam: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordActivityManager,
+            steam_id: u32,
+        ) -> EDiscordResult,
+    >,
+    pub update_activity: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordActivityManager,
+            activity: *mut DiscordActivity,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub clear_activity: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordActivityManager,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub send_request_reply: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordActivityManager,
+            user_id: DiscordUserId,
+            reply: EDiscordActivityJoinRequestReply,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub send_invite: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordActivityManager,
+            user_id: DiscordUserId,
+            type_: EDiscordActivityActionType,
+            content: *const ctypes::c_char,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub accept_invite: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordActivityManager,
+            user_id: DiscordUserId,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordRelationshipEvents {
+    pub on_refresh: Option<unsafe extern "C" fn(event_data: *mut ctypes::c_void)>,
+    pub on_relationship_update: Option<
+        unsafe extern "C" fn(
+            event_data: *mut ctypes::c_void,
+            relationship: *mut DiscordRelationship,
+        ),
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordRelationshipManager {
+    pub filter: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordRelationshipManager,
+            filter_data: *mut ctypes::c_void,
+            filter: Option<
+                unsafe extern "C" fn(
+                    filter_data: *mut ctypes::c_void,
+                    relationship: *mut DiscordRelationship,
+                ) -> bool,
+            >,
+        ),
+    >,
+    pub count: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordRelationshipManager,
+            count: *mut i32,
+        ) -> EDiscordResult,
+    >,
+    pub get: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordRelationshipManager,
+            user_id: DiscordUserId,
+            relationship: *mut DiscordRelationship,
+        ) -> EDiscordResult,
+    >,
+    pub get_at: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordRelationshipManager,
+            index: u32,
+            relationship: *mut DiscordRelationship,
+        ) -> EDiscordResult,
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordLobbyEvents {
+    pub on_lobby_update:
+        Option<unsafe extern "C" fn(event_data: *mut ctypes::c_void, lobby_id: i64)>,
+    pub on_lobby_delete:
+        Option<unsafe extern "C" fn(event_data: *mut ctypes::c_void, lobby_id: i64, reason: u32)>,
+    pub on_member_connect:
+        Option<unsafe extern "C" fn(event_data: *mut ctypes::c_void, lobby_id: i64, user_id: i64)>,
+    pub on_member_update:
+        Option<unsafe extern "C" fn(event_data: *mut ctypes::c_void, lobby_id: i64, user_id: i64)>,
+    pub on_member_disconnect:
+        Option<unsafe extern "C" fn(event_data: *mut ctypes::c_void, lobby_id: i64, user_id: i64)>,
+    pub on_lobby_message: Option<
+        unsafe extern "C" fn(
+            event_data: *mut ctypes::c_void,
+            lobby_id: i64,
+            user_id: i64,
+            data: *mut u8,
+            data_length: u32,
+        ),
+    >,
+    pub on_speaking: Option<
+        unsafe extern "C" fn(
+            event_data: *mut ctypes::c_void,
+            lobby_id: i64,
+            user_id: i64,
+            speaking: bool,
+        ),
+    >,
+    pub on_network_message: Option<
+        unsafe extern "C" fn(
+            event_data: *mut ctypes::c_void,
+            lobby_id: i64,
+            user_id: i64,
+            channel_id: u8,
+            data: *mut u8,
+            data_length: u32,
+        ),
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordLobbyManager {
+    pub get_lobby_create_transaction: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            transaction: *mut *mut IDiscordLobbyTransaction,
+        ) -> EDiscordResult,
+    >,
+    pub get_lobby_update_transaction: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            transaction: *mut *mut IDiscordLobbyTransaction,
+        ) -> EDiscordResult,
+    >,
+    pub get_member_update_transaction: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            user_id: DiscordUserId,
+            transaction: *mut *mut IDiscordLobbyMemberTransaction,
+        ) -> EDiscordResult,
+    >,
+    pub create_lobby: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            transaction: *mut IDiscordLobbyTransaction,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(
+                    callback_data: *mut ctypes::c_void,
+                    result: EDiscordResult,
+                    lobby: *mut DiscordLobby,
+                ),
+            >,
+        ),
+    >,
+    pub update_lobby: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            transaction: *mut IDiscordLobbyTransaction,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub delete_lobby: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub connect_lobby: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            secret: *mut ctypes::c_char,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(
+                    callback_data: *mut ctypes::c_void,
+                    result: EDiscordResult,
+                    lobby: *mut DiscordLobby,
+                ),
+            >,
+        ),
+    >,
+    pub connect_lobby_with_activity_secret: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            activity_secret: *mut ctypes::c_char,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(
+                    callback_data: *mut ctypes::c_void,
+                    result: EDiscordResult,
+                    lobby: *mut DiscordLobby,
+                ),
+            >,
+        ),
+    >,
+    pub disconnect_lobby: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub get_lobby: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            lobby: *mut DiscordLobby,
+        ) -> EDiscordResult,
+    >,
+    pub get_lobby_activity_secret: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            secret: *mut DiscordLobbySecret,
+        ) -> EDiscordResult,
+    >,
+    pub get_lobby_metadata_value: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            key: *mut ctypes::c_char,
+            value: *mut DiscordMetadataValue,
+        ) -> EDiscordResult,
+    >,
+    pub get_lobby_metadata_key: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            index: i32,
+            key: *mut DiscordMetadataKey,
+        ) -> EDiscordResult,
+    >,
+    pub lobby_metadata_count: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            count: *mut i32,
+        ) -> EDiscordResult,
+    >,
+    pub member_count: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            count: *mut i32,
+        ) -> EDiscordResult,
+    >,
+    pub get_member_user_id: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            index: i32,
+            user_id: *mut DiscordUserId,
+        ) -> EDiscordResult,
+    >,
+    pub get_member_user: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            user_id: DiscordUserId,
+            user: *mut DiscordUser,
+        ) -> EDiscordResult,
+    >,
+    pub get_member_metadata_value: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            user_id: DiscordUserId,
+            key: *mut ctypes::c_char,
+            value: *mut DiscordMetadataValue,
+        ) -> EDiscordResult,
+    >,
+    pub get_member_metadata_key: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            user_id: DiscordUserId,
+            index: i32,
+            key: *mut DiscordMetadataKey,
+        ) -> EDiscordResult,
+    >,
+    pub member_metadata_count: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            user_id: DiscordUserId,
+            count: *mut i32,
+        ) -> EDiscordResult,
+    >,
+    pub update_member: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            user_id: DiscordUserId,
+            transaction: *mut IDiscordLobbyMemberTransaction,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub send_lobby_message: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            data: *mut u8,
+            data_length: u32,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub get_search_query: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            query: *mut *mut IDiscordLobbySearchQuery,
+        ) -> EDiscordResult,
+    >,
+    pub search: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            query: *mut IDiscordLobbySearchQuery,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub lobby_count:
+        Option<unsafe extern "C" fn(manager: *mut IDiscordLobbyManager, count: *mut i32)>,
+    pub get_lobby_id: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            index: i32,
+            lobby_id: *mut DiscordLobbyId,
+        ) -> EDiscordResult,
+    >,
+    pub connect_voice: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub disconnect_voice: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub connect_network: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+        ) -> EDiscordResult,
+    >,
+    pub disconnect_network: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+        ) -> EDiscordResult,
+    >,
+    pub flush_network:
+        Option<unsafe extern "C" fn(manager: *mut IDiscordLobbyManager) -> EDiscordResult>,
+    pub open_network_channel: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            channel_id: u8,
+            reliable: bool,
+        ) -> EDiscordResult,
+    >,
+    pub send_network_message: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordLobbyManager,
+            lobby_id: DiscordLobbyId,
+            user_id: DiscordUserId,
+            channel_id: u8,
+            data: *mut u8,
+            data_length: u32,
+        ) -> EDiscordResult,
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordNetworkEvents {
+    pub on_message: Option<
+        unsafe extern "C" fn(
+            event_data: *mut ctypes::c_void,
+            peer_id: DiscordNetworkPeerId,
+            channel_id: DiscordNetworkChannelId,
+            data: *mut u8,
+            data_length: u32,
+        ),
+    >,
+    pub on_route_update: Option<
+        unsafe extern "C" fn(event_data: *mut ctypes::c_void, route_data: *const ctypes::c_char),
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordNetworkManager {
+    pub get_peer_id: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordNetworkManager,
+            peer_id: *mut DiscordNetworkPeerId,
+        ),
+    >,
+    pub flush: Option<unsafe extern "C" fn(manager: *mut IDiscordNetworkManager) -> EDiscordResult>,
+    pub open_peer: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordNetworkManager,
+            peer_id: DiscordNetworkPeerId,
+            route_data: *const ctypes::c_char,
+        ) -> EDiscordResult,
+    >,
+    pub update_peer: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordNetworkManager,
+            peer_id: DiscordNetworkPeerId,
+            route_data: *const ctypes::c_char,
+        ) -> EDiscordResult,
+    >,
+    pub close_peer: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordNetworkManager,
+            peer_id: DiscordNetworkPeerId,
+        ) -> EDiscordResult,
+    >,
+    pub open_channel: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordNetworkManager,
+            peer_id: DiscordNetworkPeerId,
+            channel_id: DiscordNetworkChannelId,
+            reliable: bool,
+        ) -> EDiscordResult,
+    >,
+    pub close_channel: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordNetworkManager,
+            peer_id: DiscordNetworkPeerId,
+            channel_id: DiscordNetworkChannelId,
+        ) -> EDiscordResult,
+    >,
+    pub send_message: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordNetworkManager,
+            peer_id: DiscordNetworkPeerId,
+            channel_id: DiscordNetworkChannelId,
+            data: *mut u8,
+            data_length: u32,
+        ) -> EDiscordResult,
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordOverlayEvents {
+    pub on_toggle: Option<unsafe extern "C" fn(event_data: *mut ctypes::c_void, locked: bool)>,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordOverlayManager {
+    pub is_enabled:
+        Option<unsafe extern "C" fn(manager: *mut IDiscordOverlayManager, enabled: *mut bool)>,
+    pub is_locked:
+        Option<unsafe extern "C" fn(manager: *mut IDiscordOverlayManager, locked: *mut bool)>,
+    pub set_locked: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordOverlayManager,
+            locked: bool,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub open_activity_invite: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordOverlayManager,
+            type_: EDiscordActivityActionType,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub open_guild_invite: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordOverlayManager,
+            code: *const ctypes::c_char,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub open_voice_settings: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordOverlayManager,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordStorageManager {
+    pub read: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStorageManager,
+            name: *const ctypes::c_char,
+            data: *mut u8,
+            data_length: u32,
+            read: *mut u32,
+        ) -> EDiscordResult,
+    >,
+    pub read_async: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStorageManager,
+            name: *const ctypes::c_char,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(
+                    callback_data: *mut ctypes::c_void,
+                    result: EDiscordResult,
+                    data: *mut u8,
+                    data_length: u32,
+                ),
+            >,
+        ),
+    >,
+    pub read_async_partial: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStorageManager,
+            name: *const ctypes::c_char,
+            offset: u64,
+            length: u64,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(
+                    callback_data: *mut ctypes::c_void,
+                    result: EDiscordResult,
+                    data: *mut u8,
+                    data_length: u32,
+                ),
+            >,
+        ),
+    >,
+    pub write: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStorageManager,
+            name: *const ctypes::c_char,
+            data: *mut u8,
+            data_length: u32,
+        ) -> EDiscordResult,
+    >,
+    pub write_async: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStorageManager,
+            name: *const ctypes::c_char,
+            data: *mut u8,
+            data_length: u32,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub delete_: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStorageManager,
+            name: *const ctypes::c_char,
+        ) -> EDiscordResult,
+    >,
+    pub exists: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStorageManager,
+            name: *const ctypes::c_char,
+            exists: *mut bool,
+        ) -> EDiscordResult,
+    >,
+    pub count: Option<unsafe extern "C" fn(manager: *mut IDiscordStorageManager, count: *mut i32)>,
+    pub stat: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStorageManager,
+            name: *const ctypes::c_char,
+            stat: *mut DiscordFileStat,
+        ) -> EDiscordResult,
+    >,
+    pub stat_at: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStorageManager,
+            index: i32,
+            stat: *mut DiscordFileStat,
+        ) -> EDiscordResult,
+    >,
+    pub get_path: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStorageManager,
+            path: *mut DiscordPath,
+        ) -> EDiscordResult,
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordStoreEvents {
+    pub on_entitlement_create: Option<
+        unsafe extern "C" fn(event_data: *mut ctypes::c_void, entitlement: *mut DiscordEntitlement),
+    >,
+    pub on_entitlement_delete: Option<
+        unsafe extern "C" fn(event_data: *mut ctypes::c_void, entitlement: *mut DiscordEntitlement),
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordStoreManager {
+    pub fetch_skus: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStoreManager,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub count_skus:
+        Option<unsafe extern "C" fn(manager: *mut IDiscordStoreManager, count: *mut i32)>,
+    pub get_sku: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStoreManager,
+            sku_id: DiscordSnowflake,
+            sku: *mut DiscordSku,
+        ) -> EDiscordResult,
+    >,
+    pub get_sku_at: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStoreManager,
+            index: i32,
+            sku: *mut DiscordSku,
+        ) -> EDiscordResult,
+    >,
+    pub fetch_entitlements: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStoreManager,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub count_entitlements:
+        Option<unsafe extern "C" fn(manager: *mut IDiscordStoreManager, count: *mut i32)>,
+    pub get_entitlement: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStoreManager,
+            entitlement_id: DiscordSnowflake,
+            entitlement: *mut DiscordEntitlement,
+        ) -> EDiscordResult,
+    >,
+    pub get_entitlement_at: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStoreManager,
+            index: i32,
+            entitlement: *mut DiscordEntitlement,
+        ) -> EDiscordResult,
+    >,
+    pub has_sku_entitlement: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStoreManager,
+            sku_id: DiscordSnowflake,
+            has_entitlement: *mut bool,
+        ) -> EDiscordResult,
+    >,
+    pub start_purchase: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordStoreManager,
+            sku_id: DiscordSnowflake,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordVoiceEvents {
+    pub on_settings_update: Option<unsafe extern "C" fn(event_data: *mut ctypes::c_void)>,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordVoiceManager {
+    pub get_input_mode: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordVoiceManager,
+            input_mode: *mut DiscordInputMode,
+        ) -> EDiscordResult,
+    >,
+    pub set_input_mode: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordVoiceManager,
+            input_mode: DiscordInputMode,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub is_self_mute: Option<
+        unsafe extern "C" fn(manager: *mut IDiscordVoiceManager, mute: *mut bool) -> EDiscordResult,
+    >,
+    pub set_self_mute: Option<
+        unsafe extern "C" fn(manager: *mut IDiscordVoiceManager, mute: bool) -> EDiscordResult,
+    >,
+    pub is_self_deaf: Option<
+        unsafe extern "C" fn(manager: *mut IDiscordVoiceManager, deaf: *mut bool) -> EDiscordResult,
+    >,
+    pub set_self_deaf: Option<
+        unsafe extern "C" fn(manager: *mut IDiscordVoiceManager, deaf: bool) -> EDiscordResult,
+    >,
+    pub is_local_mute: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordVoiceManager,
+            user_id: DiscordSnowflake,
+            mute: *mut bool,
+        ) -> EDiscordResult,
+    >,
+    pub set_local_mute: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordVoiceManager,
+            user_id: DiscordSnowflake,
+            mute: bool,
+        ) -> EDiscordResult,
+    >,
+    pub get_local_volume: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordVoiceManager,
+            user_id: DiscordSnowflake,
+            volume: *mut u8,
+        ) -> EDiscordResult,
+    >,
+    pub set_local_volume: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordVoiceManager,
+            user_id: DiscordSnowflake,
+            volume: u8,
+        ) -> EDiscordResult,
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordAchievementEvents {
+    pub on_user_achievement_update: Option<
+        unsafe extern "C" fn(
+            event_data: *mut ctypes::c_void,
+            user_achievement: *mut DiscordUserAchievement,
+        ),
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordAchievementManager {
+    pub set_user_achievement: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordAchievementManager,
+            achievement_id: DiscordSnowflake,
+            percent_complete: u8,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub fetch_user_achievements: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordAchievementManager,
+            callback_data: *mut ctypes::c_void,
+            callback: Option<
+                unsafe extern "C" fn(callback_data: *mut ctypes::c_void, result: EDiscordResult),
+            >,
+        ),
+    >,
+    pub count_user_achievements:
+        Option<unsafe extern "C" fn(manager: *mut IDiscordAchievementManager, count: *mut i32)>,
+    pub get_user_achievement: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordAchievementManager,
+            user_achievement_id: DiscordSnowflake,
+            user_achievement: *mut DiscordUserAchievement,
+        ) -> EDiscordResult,
+    >,
+    pub get_user_achievement_at: Option<
+        unsafe extern "C" fn(
+            manager: *mut IDiscordAchievementManager,
+            index: i32,
+            user_achievement: *mut DiscordUserAchievement,
+        ) -> EDiscordResult,
+    >,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+pub struct IDiscordCore {
+    pub destroy: Option<unsafe extern "C" fn(core: *mut IDiscordCore)>,
+    pub run_callbacks: Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> EDiscordResult>,
+    pub set_log_hook: Option<
+        unsafe extern "C" fn(
+            core: *mut IDiscordCore,
+            min_level: EDiscordLogLevel,
+            hook_data: *mut ctypes::c_void,
+            hook: Option<
+                unsafe extern "C" fn(
+                    hook_data: *mut ctypes::c_void,
+                    level: EDiscordLogLevel,
+                    message: *const ctypes::c_char,
+                ),
+            >,
+        ),
+    >,
+    pub get_application_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordApplicationManager>,
+    pub get_user_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordUserManager>,
+    pub get_image_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordImageManager>,
+    pub get_activity_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordActivityManager>,
+    pub get_relationship_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordRelationshipManager>,
+    pub get_lobby_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordLobbyManager>,
+    pub get_network_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordNetworkManager>,
+    pub get_overlay_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordOverlayManager>,
+    pub get_storage_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordStorageManager>,
+    pub get_store_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordStoreManager>,
+    pub get_voice_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordVoiceManager>,
+    pub get_achievement_manager:
+        Option<unsafe extern "C" fn(core: *mut IDiscordCore) -> *mut IDiscordAchievementManager>,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DiscordCreateParams {
+    pub client_id: DiscordClientId,
+    pub flags: u64,
+    pub events: *mut IDiscordCoreEvents,
+    pub event_data: *mut ctypes::c_void,
+    pub application_events: *mut IDiscordApplicationEvents,
+    pub application_version: DiscordVersion,
+    pub user_events: *mut IDiscordUserEvents,
+    pub user_version: DiscordVersion,
+    pub image_events: *mut IDiscordImageEvents,
+    pub image_version: DiscordVersion,
+    pub activity_events: *mut IDiscordActivityEvents,
+    pub activity_version: DiscordVersion,
+    pub relationship_events: *mut IDiscordRelationshipEvents,
+    pub relationship_version: DiscordVersion,
+    pub lobby_events: *mut IDiscordLobbyEvents,
+    pub lobby_version: DiscordVersion,
+    pub network_events: *mut IDiscordNetworkEvents,
+    pub network_version: DiscordVersion,
+    pub overlay_events: *mut IDiscordOverlayEvents,
+    pub overlay_version: DiscordVersion,
+    pub storage_events: *mut IDiscordStorageEvents,
+    pub storage_version: DiscordVersion,
+    pub store_events: *mut IDiscordStoreEvents,
+    pub store_version: DiscordVersion,
+    pub voice_events: *mut IDiscordVoiceEvents,
+    pub voice_version: DiscordVersion,
+    pub achievement_events: *mut IDiscordAchievementEvents,
+    pub achievement_version: DiscordVersion,
+}
+impl Default for DiscordCreateParams {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[test]
+fn bindgen_test_layout_DiscordUser() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordUser>(),
+        408usize,
+        concat!("Size of: ", stringify!(DiscordUser))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordUser>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordUser))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordOAuth2Token() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordOAuth2Token>(),
+        1160usize,
+        concat!("Size of: ", stringify!(DiscordOAuth2Token))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordOAuth2Token>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordOAuth2Token))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordImageHandle() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordImageHandle>(),
+        24usize,
+        concat!("Size of: ", stringify!(DiscordImageHandle))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordImageHandle>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordImageHandle))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordImageDimensions() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordImageDimensions>(),
+        8usize,
+        concat!("Size of: ", stringify!(DiscordImageDimensions))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordImageDimensions>(),
+        4usize,
+        concat!("Alignment of ", stringify!(DiscordImageDimensions))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordActivityTimestamps() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordActivityTimestamps>(),
+        16usize,
+        concat!("Size of: ", stringify!(DiscordActivityTimestamps))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordActivityTimestamps>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordActivityTimestamps))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordActivityAssets() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordActivityAssets>(),
+        512usize,
+        concat!("Size of: ", stringify!(DiscordActivityAssets))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordActivityAssets>(),
+        1usize,
+        concat!("Alignment of ", stringify!(DiscordActivityAssets))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordPartySize() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordPartySize>(),
+        8usize,
+        concat!("Size of: ", stringify!(DiscordPartySize))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordPartySize>(),
+        4usize,
+        concat!("Alignment of ", stringify!(DiscordPartySize))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordActivityParty() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordActivityParty>(),
+        136usize,
+        concat!("Size of: ", stringify!(DiscordActivityParty))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordActivityParty>(),
+        4usize,
+        concat!("Alignment of ", stringify!(DiscordActivityParty))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordActivitySecrets() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordActivitySecrets>(),
+        384usize,
+        concat!("Size of: ", stringify!(DiscordActivitySecrets))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordActivitySecrets>(),
+        1usize,
+        concat!("Alignment of ", stringify!(DiscordActivitySecrets))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordActivity() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordActivity>(),
+        1456usize,
+        concat!("Size of: ", stringify!(DiscordActivity))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordActivity>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordActivity))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordPresence() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordPresence>(),
+        1464usize,
+        concat!("Size of: ", stringify!(DiscordPresence))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordPresence>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordPresence))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordRelationship() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordRelationship>(),
+        1880usize,
+        concat!("Size of: ", stringify!(DiscordRelationship))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordRelationship>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordRelationship))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordLobby() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordLobby>(),
+        160usize,
+        concat!("Size of: ", stringify!(DiscordLobby))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordLobby>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordLobby))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordFileStat() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordFileStat>(),
+        280usize,
+        concat!("Size of: ", stringify!(DiscordFileStat))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordFileStat>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordFileStat))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordEntitlement() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordEntitlement>(),
+        24usize,
+        concat!("Size of: ", stringify!(DiscordEntitlement))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordEntitlement>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordEntitlement))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordSkuPrice() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordSkuPrice>(),
+        20usize,
+        concat!("Size of: ", stringify!(DiscordSkuPrice))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordSkuPrice>(),
+        4usize,
+        concat!("Alignment of ", stringify!(DiscordSkuPrice))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordSku() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordSku>(),
+        288usize,
+        concat!("Size of: ", stringify!(DiscordSku))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordSku>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordSku))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordInputMode() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordInputMode>(),
+        260usize,
+        concat!("Size of: ", stringify!(DiscordInputMode))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordInputMode>(),
+        4usize,
+        concat!("Alignment of ", stringify!(DiscordInputMode))
+    );
+}
+#[test]
+fn bindgen_test_layout_DiscordUserAchievement() {
+    assert_eq!(
+        ::std::mem::size_of::<DiscordUserAchievement>(),
+        88usize,
+        concat!("Size of: ", stringify!(DiscordUserAchievement))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<DiscordUserAchievement>(),
+        8usize,
+        concat!("Alignment of ", stringify!(DiscordUserAchievement))
+    );
+}
+extern "C" {
+    pub fn DiscordCreateParamsSetDefault(params: *mut DiscordCreateParams);
+}
+extern "C" {
+    pub fn DiscordCreate(
+        version: DiscordVersion,
+        params: *mut DiscordCreateParams,
+        result: *mut *mut IDiscordCore,
+    ) -> EDiscordResult;
+}
@@ -0,0 +1,67 @@
+use crate::*;
+use std::ffi::OsStr;
+
+/// Signature of `DiscordCreate`
+pub type DiscordCreateFn = unsafe extern "C" fn(
+    version: DiscordVersion,
+    params: *mut DiscordCreateParams,
+    result: *mut *mut IDiscordCore,
+) -> EDiscordResult;
+
+/// File name of the SDK library as distributed in the SDK archive
+#[cfg(target_os = "windows")]
+pub const DEFAULT_LIBRARY_NAME: &str = "discord_game_sdk.dll";
+
+/// File name of the SDK library as distributed in the SDK archive
+#[cfg(target_os = "macos")]
+pub const DEFAULT_LIBRARY_NAME: &str = "discord_game_sdk.dylib";
+
+/// File name of the SDK library as distributed in the SDK archive
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub const DEFAULT_LIBRARY_NAME: &str = "discord_game_sdk.so";
+
+/// The SDK library, loaded at runtime
+///
+/// `DiscordCreate` is resolved when loading, the library must outlive every `IDiscordCore`
+/// created with it.
+#[derive(Debug)]
+pub struct DynamicLibrary {
+    create: DiscordCreateFn,
+    // Must be dropped last, `create` points inside of it
+    _library: libloading::Library,
+}
+
+impl DynamicLibrary {
+    /// Loads the library at `path` and resolves `DiscordCreate`.
+    ///
+    /// `path` follows the platform's rules for dynamic library lookup: a bare file name is
+    /// searched in the usual locations, a path is used as-is.
+    ///
+    /// ## Safety
+    ///
+    /// Loading a library runs its initialization routines, `path` must point to the Discord Game
+    /// SDK library.
+    pub unsafe fn open(path: impl AsRef<OsStr>) -> Result<Self, libloading::Error> {
+        let library = libloading::Library::new(path.as_ref())?;
+        let create = *library.get::<DiscordCreateFn>(b"DiscordCreate\0")?;
+
+        Ok(Self {
+            create,
+            _library: library,
+        })
+    }
+
+    /// Calls `DiscordCreate` from the loaded library.
+    ///
+    /// ## Safety
+    ///
+    /// Same requirements as `DiscordCreate`.
+    pub unsafe fn create(
+        &self,
+        version: DiscordVersion,
+        params: *mut DiscordCreateParams,
+        result: *mut *mut IDiscordCore,
+    ) -> EDiscordResult {
+        (self.create)(version, params, result)
+    }
+}
@@ -51,6 +51,26 @@
 //! This allows for `cargo run` to function.
 //!
 //!
+//! ### `pregenerated`
+//!
+//! Uses bindings that were generated ahead of time from the header vendored in this crate
+//! (`DISCORD_VERSION` 2, all managers at version 1) instead of running `bindgen`.
+//!
+//! Neither `libclang` nor the SDK download are needed to build, which is useful for CI.
+//! If `DISCORD_GAME_SDK_PATH` is set, its header is checked against the vendored one and the
+//! build fails if they differ. Linking with `link` still requires the SDK download.
+//!
+//!
+//! ### `dynamic`
+//!
+//! Does not link to the SDK, provides `DynamicLibrary` which loads it at runtime instead.
+//!
+//! The library can be used as it is found in the SDK archive, without renaming or setting
+//! library search paths. `DiscordCreate` must be called through `DynamicLibrary::create`.
+//!
+//! Takes precedence over `link` if both are enabled.
+//!
+//!
 //! # Legal
 //!
 //! You *MUST* acquaint yourself with and agree to the [official terms of the Discord Game SDK].
@@ -81,3 +101,9 @@ pub(crate) mod ctypes {
 }
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+#[cfg(feature = "dynamic")]
+mod dynamic;
+
+#[cfg(feature = "dynamic")]
+pub use dynamic::*;